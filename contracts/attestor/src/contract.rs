@@ -0,0 +1,432 @@
+//! This module handles the execution logic of the contract.
+
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+
+use crate::types::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state,
+};
+
+/// Instantiates a new contract.
+///
+/// # Errors
+/// Will return an error if the instantiation fails.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let client_state: state::ClientState = cosmwasm_std::from_json(&msg.client_state)?;
+    let consensus_state: state::ConsensusState = cosmwasm_std::from_json(&msg.consensus_state)?;
+
+    client_state.validate()?;
+    if client_state.is_frozen() {
+        return Err(LightClientError::InvalidClientState(
+            "client cannot be frozen at creation".to_string(),
+        )
+        .into());
+    }
+
+    state::CLIENT_STATE.save(deps.storage, &client_state)?;
+    state::CONSENSUS_STATES.save(
+        deps.storage,
+        (
+            client_state.latest_height.revision_number,
+            client_state.latest_height.revision_height,
+        ),
+        &consensus_state,
+    )?;
+
+    Ok(Response::default())
+}
+
+/// Handles the execution of the contract by routing the messages to the respective handlers.
+/// Can be called by anyone.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateState(msg) => {
+            execute::update_state(deps, env, cosmwasm_std::from_json(&msg.client_message)?)
+        }
+        ExecuteMsg::UpdateStateOnMisbehaviour(msg) => execute::update_state_on_misbehaviour(
+            deps,
+            env,
+            cosmwasm_std::from_json(&msg.client_message)?,
+        ),
+        ExecuteMsg::VerifyUpgradeAndUpdateState(_) => {
+            Err(LightClientError::UpgradeNotSupported.into())
+        }
+    }
+}
+
+/// Handles the query messages by routing them to the respective handlers.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Status(_) => query::status(deps, env),
+        QueryMsg::ExportMetadata(_) => query::export_metadata(),
+        QueryMsg::TimestampAtHeight(msg) => {
+            query::timestamp_at_height(deps, env, msg.height.into())
+        }
+        QueryMsg::VerifyClientMessage(msg) => {
+            query::verify_client_message(deps, env, cosmwasm_std::from_json(&msg.client_message)?)
+        }
+        QueryMsg::CheckForMisbehaviour(msg) => {
+            query::check_for_misbehaviour(deps, env, cosmwasm_std::from_json(&msg.client_message)?)
+        }
+        QueryMsg::VerifyMembership(_) | QueryMsg::VerifyNonMembership(_) => {
+            query::verify_membership(deps, env, msg.try_into()?)
+        }
+        QueryMsg::ClientState {} => query::client_state(deps, env),
+        QueryMsg::ConsensusState { height } => query::consensus_state(deps, env, height),
+        QueryMsg::LatestHeight {} => query::latest_height(deps, env),
+        QueryMsg::ConsensusHeights { start_after, limit } => {
+            query::consensus_heights(deps, env, start_after, limit)
+        }
+    }
+}
+
+mod execute {
+    use cw_ibc_lite_shared::types::clients::error::LightClientError;
+    use ibc_client_cw::types::ContractResult;
+
+    use crate::types::{
+        msg::{Attestation, ClientMessage},
+        state, verification,
+    };
+
+    use super::{ContractError, DepsMut, Env, Response};
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state(
+        deps: DepsMut,
+        _env: Env,
+        client_message: ClientMessage,
+    ) -> Result<Response, ContractError> {
+        let mut client_state = state::CLIENT_STATE.load(deps.storage)?;
+        if client_state.is_frozen() {
+            return Err(LightClientError::ClientFrozen.into());
+        }
+
+        match client_message {
+            ClientMessage::Attestation(attestation) => {
+                verification::verify_attestation(deps.api, &client_state, &attestation)?;
+                store_attestation(deps, &mut client_state, attestation)?;
+            }
+            ClientMessage::KeyRotation(key_rotation) => {
+                verification::verify_key_rotation(deps.api, &client_state, &key_rotation)?;
+
+                client_state.attestors = key_rotation.new_attestors;
+                client_state.threshold = key_rotation.new_threshold;
+                client_state.key_set_sequence += 1;
+                state::CLIENT_STATE.save(deps.storage, &client_state)?;
+            }
+            ClientMessage::Misbehaviour(_) => {
+                return Err(LightClientError::InvalidHeader(
+                    "update state does not accept misbehaviour, use update state on misbehaviour"
+                        .to_string(),
+                )
+                .into());
+            }
+        }
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state_on_misbehaviour(
+        deps: DepsMut,
+        _env: Env,
+        client_message: ClientMessage,
+    ) -> Result<Response, ContractError> {
+        let mut client_state = state::CLIENT_STATE.load(deps.storage)?;
+        if client_state.is_frozen() {
+            return Err(LightClientError::ClientFrozen.into());
+        }
+
+        let ClientMessage::Misbehaviour(misbehaviour) = client_message else {
+            return Err(LightClientError::InvalidMisbehaviour(
+                "update state on misbehaviour only accepts misbehaviour".to_string(),
+            )
+            .into());
+        };
+        verification::verify_misbehaviour(deps.api, &client_state, &misbehaviour)?;
+
+        client_state.frozen_height = Some(misbehaviour.attestation_one.height);
+        state::CLIENT_STATE.save(deps.storage, &client_state)?;
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+
+    /// Stores the consensus state of a verified attestation. If a different consensus state is
+    /// already stored at the attested height, the client is frozen instead.
+    fn store_attestation(
+        deps: DepsMut,
+        client_state: &mut state::ClientState,
+        attestation: Attestation,
+    ) -> Result<(), ContractError> {
+        let key = (
+            attestation.height.revision_number,
+            attestation.height.revision_height,
+        );
+        let consensus_state = state::ConsensusState {
+            timestamp: attestation.timestamp,
+            root: attestation.root,
+        };
+
+        match state::CONSENSUS_STATES.may_load(deps.storage, key)? {
+            Some(stored) if stored == consensus_state => return Ok(()),
+            Some(_) => client_state.frozen_height = Some(attestation.height),
+            None => {
+                state::CONSENSUS_STATES.save(deps.storage, key, &consensus_state)?;
+                if key
+                    > (
+                        client_state.latest_height.revision_number,
+                        client_state.latest_height.revision_height,
+                    )
+                {
+                    client_state.latest_height = attestation.height;
+                }
+            }
+        }
+
+        Ok(state::CLIENT_STATE.save(deps.storage, client_state)?)
+    }
+}
+
+mod query {
+    use cosmwasm_std::{Order, StdResult};
+    use cw_ibc_lite_shared::types::{
+        clients::{
+            error::LightClientError,
+            msg::{query_responses, VerifyMembershipQuery},
+        },
+        ibc::Height,
+    };
+    use cw_storage_plus::Bound;
+    use ibc_client_cw::types::ContractResult;
+
+    use crate::types::{
+        keys,
+        msg::{ClientMessage, MembershipProof, NonMembershipProof},
+        state, verification,
+    };
+
+    use super::{Binary, ContractError, Deps, Env};
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn status(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        let status = if client_state.is_frozen() {
+            keys::STATUS_FROZEN
+        } else {
+            keys::STATUS_ACTIVE
+        };
+
+        Ok(cosmwasm_std::to_json_binary(&query_responses::Status {
+            status: status.to_string(),
+        })?)
+    }
+
+    pub fn export_metadata() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ExportMetadata { metadata: vec![] },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn timestamp_at_height(
+        deps: Deps,
+        _env: Env,
+        height: Height,
+    ) -> Result<Binary, ContractError> {
+        let consensus_state = state::CONSENSUS_STATES.load(
+            deps.storage,
+            (height.revision_number, height.revision_height),
+        )?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::TimestampAtHeight {
+                timestamp: consensus_state.timestamp,
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_client_message(
+        deps: Deps,
+        _env: Env,
+        client_message: ClientMessage,
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        match &client_message {
+            ClientMessage::Attestation(attestation) => {
+                verification::verify_attestation(deps.api, &client_state, attestation)?;
+            }
+            ClientMessage::KeyRotation(key_rotation) => {
+                verification::verify_key_rotation(deps.api, &client_state, key_rotation)?;
+            }
+            ClientMessage::Misbehaviour(misbehaviour) => {
+                verification::verify_misbehaviour(deps.api, &client_state, misbehaviour)?;
+            }
+        }
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::VerifyClientMessage { is_valid: true },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn check_for_misbehaviour(
+        deps: Deps,
+        _env: Env,
+        client_message: ClientMessage,
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        let found_misbehaviour = match &client_message {
+            ClientMessage::KeyRotation(_) => false,
+            ClientMessage::Misbehaviour(misbehaviour) => {
+                verification::verify_misbehaviour(deps.api, &client_state, misbehaviour).is_ok()
+            }
+            ClientMessage::Attestation(attestation) => state::CONSENSUS_STATES
+                .may_load(
+                    deps.storage,
+                    (
+                        attestation.height.revision_number,
+                        attestation.height.revision_height,
+                    ),
+                )?
+                .is_some_and(|stored| {
+                    stored.timestamp != attestation.timestamp || stored.root != attestation.root
+                }),
+        };
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::CheckForMisbehaviour { found_misbehaviour },
+        )?)
+    }
+
+    /// Verifies a merkle proof against the consensus state attested at the given height.
+    /// The proof is a JSON encoded [`MembershipProof`] for membership, and a JSON encoded
+    /// [`NonMembershipProof`] for non-membership.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_membership(
+        deps: Deps,
+        _env: Env,
+        query: VerifyMembershipQuery,
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        if client_state.is_frozen() {
+            return Err(LightClientError::ClientFrozen.into());
+        }
+
+        let consensus_state = state::CONSENSUS_STATES.load(
+            deps.storage,
+            (query.height.revision_number, query.height.revision_height),
+        )?;
+        match &query.value {
+            Some(value) => verification::verify_membership(
+                &consensus_state,
+                &cosmwasm_std::from_json::<MembershipProof>(&query.proof)?,
+                &query.key_path,
+                value,
+            )?,
+            None => verification::verify_non_membership(
+                &consensus_state,
+                &cosmwasm_std::from_json::<NonMembershipProof>(&query.proof)?,
+                &query.key_path,
+            )?,
+        }
+
+        Ok(cosmwasm_std::to_json_binary(&ContractResult::success())?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn client_state(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ClientState {
+                latest_height: client_state.latest_height,
+                frozen_height: client_state.frozen_height,
+                chain_id: Some(client_state.chain_id),
+                trusting_period: None,
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_state(deps: Deps, _env: Env, height: Height) -> Result<Binary, ContractError> {
+        let consensus_state = state::CONSENSUS_STATES.load(
+            deps.storage,
+            (height.revision_number, height.revision_height),
+        )?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusState {
+                timestamp: consensus_state.timestamp,
+                root: consensus_state.root,
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn latest_height(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::LatestHeight {
+                height: client_state.latest_height,
+            },
+        )?)
+    }
+
+    /// Default limit for pagination
+    const DEFAULT_LIMIT: u32 = 10;
+    /// Maximum limit for pagination
+    const MAX_LIMIT: u32 = 100;
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_heights(
+        deps: Deps,
+        _env: Env,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after
+            .map(|height| Bound::exclusive((height.revision_number, height.revision_height)));
+
+        let heights = state::CONSENSUS_STATES
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|key| {
+                key.map(|(revision_number, revision_height)| Height {
+                    revision_number,
+                    revision_height,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusHeights { heights },
+        )?)
+    }
+}
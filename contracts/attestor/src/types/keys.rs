@@ -0,0 +1,18 @@
+//! # Keys
+//!
+//! Contains key constants definitions for the contract such as signature domains.
+
+/// `ATTESTATION_DOMAIN` is the domain separator of attestation sign bytes.
+pub const ATTESTATION_DOMAIN: &[u8] = b"ibc-lite/attestor/attestation";
+/// `KEY_ROTATION_DOMAIN` is the domain separator of key rotation sign bytes.
+pub const KEY_ROTATION_DOMAIN: &[u8] = b"ibc-lite/attestor/key-rotation";
+
+/// `LEAF_PREFIX` is the prefix of leaf hashes in the attested merkle tree.
+pub const LEAF_PREFIX: u8 = 0x00;
+/// `NODE_PREFIX` is the prefix of inner node hashes in the attested merkle tree.
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// `STATUS_ACTIVE` is the status of a client that is not frozen.
+pub const STATUS_ACTIVE: &str = "Active";
+/// `STATUS_FROZEN` is the status of a frozen client.
+pub const STATUS_FROZEN: &str = "Frozen";
@@ -0,0 +1,82 @@
+//! This module defines the binary merkle tree attested to by the attestors.
+
+use sha2::{Digest, Sha256};
+
+use super::keys;
+
+/// The maximum number of siblings in a proof, so that the index of every leaf fits in a `u64`.
+pub const MAX_DEPTH: usize = 63;
+
+/// Returns the key of the given merkle path. The leaves of an attested tree are sorted by their
+/// keys in strictly increasing order.
+#[must_use]
+pub fn leaf_key(key_path: &[String]) -> Vec<u8> {
+    key_path
+        .iter()
+        .flat_map(|key| length_prefixed(key.as_bytes()))
+        .collect()
+}
+
+/// Returns the leaf hash of the given merkle path and value.
+#[must_use]
+pub fn leaf_hash(key_path: &[String], value: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update([keys::LEAF_PREFIX]);
+    hasher.update(leaf_key(key_path));
+    hasher.update(length_prefixed(value));
+    hasher.finalize().to_vec()
+}
+
+/// Returns the hash of an inner node with the given children.
+#[must_use]
+pub fn node_hash(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update([keys::NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().to_vec()
+}
+
+/// Computes the root from a leaf hash, its index and its sibling hashes from the leaf to the
+/// root.
+#[must_use]
+pub fn compute_root(leaf: Vec<u8>, mut leaf_index: u64, siblings: &[impl AsRef<[u8]>]) -> Vec<u8> {
+    siblings.iter().fold(leaf, |node, sibling| {
+        let parent = if leaf_index & 1 == 0 {
+            node_hash(&node, sibling.as_ref())
+        } else {
+            node_hash(sibling.as_ref(), &node)
+        };
+        leaf_index >>= 1;
+        parent
+    })
+}
+
+/// Returns whether the leaf at `right_index` directly follows the leaf at `left_index`, given
+/// the number of siblings of each. A leaf at index `i` with `d` siblings covers the interval
+/// `[i, i + 1) / 2^d` of the tree, so the leaves are adjacent if the interval of the left leaf
+/// ends where that of the right leaf starts. Depths must not exceed [`MAX_DEPTH`].
+#[must_use]
+pub fn are_adjacent(
+    left_index: u64,
+    left_depth: usize,
+    right_index: u64,
+    right_depth: usize,
+) -> bool {
+    (u128::from(left_index) + 1) << right_depth == u128::from(right_index) << left_depth
+}
+
+/// Returns whether the leaf at `index` with `depth` siblings is the last leaf of the tree.
+/// The depth must not exceed [`MAX_DEPTH`].
+#[must_use]
+pub fn is_last(index: u64, depth: usize) -> bool {
+    u128::from(index) + 1 == 1 << depth
+}
+
+/// Returns the bytes prefixed with their length as a big-endian `u64`.
+#[must_use]
+pub fn length_prefixed(bz: &[u8]) -> Vec<u8> {
+    let mut prefixed = (bz.len() as u64).to_be_bytes().to_vec();
+    prefixed.extend_from_slice(bz);
+    prefixed
+}
@@ -0,0 +1,8 @@
+//! This module contains the types used by the contract's execution and state logic.
+
+pub mod keys;
+pub mod merkle;
+#[allow(clippy::module_name_repetitions)]
+pub mod msg;
+pub mod state;
+pub mod verification;
@@ -0,0 +1,98 @@
+//! # Messages
+//!
+//! This module defines the messages that this contract receives. The client messages and
+//! proofs are JSON encoded into the raw bytes of the shared light client messages.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Binary;
+
+use cw_ibc_lite_shared::types::ibc::Height;
+
+pub use cw_ibc_lite_shared::types::clients::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+use super::state::PublicKey;
+
+/// The client messages accepted by the contract.
+#[cw_serde]
+pub enum ClientMessage {
+    /// Attest a state root of the counterparty.
+    Attestation(Attestation),
+    /// Rotate the attestor set, authorized by the current set.
+    KeyRotation(KeyRotation),
+    /// Two conflicting attestations for the same height.
+    Misbehaviour(Misbehaviour),
+}
+
+/// A state root of the counterparty signed by the attestors.
+#[cw_serde]
+pub struct Attestation {
+    /// The attested height.
+    pub height: Height,
+    /// The timestamp of the counterparty at the attested height in nanoseconds since the unix
+    /// epoch.
+    pub timestamp: u64,
+    /// The attested merkle root.
+    pub root: Binary,
+    /// The signatures of the attestors.
+    pub signatures: Vec<Signature>,
+}
+
+/// A new attestor set signed by the current attestors.
+#[cw_serde]
+pub struct KeyRotation {
+    /// The public keys of the new attestors.
+    pub new_attestors: Vec<PublicKey>,
+    /// The new threshold.
+    pub new_threshold: u32,
+    /// The signatures of the current attestors.
+    pub signatures: Vec<Signature>,
+}
+
+/// Two valid attestations for the same height that attest different consensus states.
+#[cw_serde]
+pub struct Misbehaviour {
+    /// The first attestation.
+    pub attestation_one: Attestation,
+    /// The second attestation.
+    pub attestation_two: Attestation,
+}
+
+/// A signature by an attestor.
+#[cw_serde]
+pub struct Signature {
+    /// The index of the attestor in the current attestor set.
+    pub attestor_index: u32,
+    /// The signature of the sign bytes digest. 64 bytes for both key types.
+    pub signature: Binary,
+}
+
+/// A proof of membership of a key-value pair in an attested merkle tree.
+#[cw_serde]
+pub struct MembershipProof {
+    /// The index of the leaf in the tree.
+    pub leaf_index: u64,
+    /// The sibling hashes from the leaf to the root.
+    pub siblings: Vec<Binary>,
+}
+
+/// A proof of non-membership of a merkle path in an attested merkle tree. It proves the
+/// membership of the leaves directly before and after the position of the path, at least one of
+/// which must be given.
+#[cw_serde]
+pub struct NonMembershipProof {
+    /// The leaf before the path, if the path does not precede all leaves.
+    pub left: Option<AdjacentLeaf>,
+    /// The leaf after the path, if the path does not follow all leaves.
+    pub right: Option<AdjacentLeaf>,
+}
+
+/// A leaf adjacent to the position of a merkle path that is not in an attested merkle tree.
+#[cw_serde]
+pub struct AdjacentLeaf {
+    /// The merkle path of the leaf.
+    pub key_path: Vec<String>,
+    /// The value of the leaf.
+    pub value: Binary,
+    /// The membership proof of the leaf.
+    pub proof: MembershipProof,
+}
@@ -0,0 +1,98 @@
+//! This module defines the state storage of the Contract.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Binary;
+use cw_storage_plus::{Item, Map};
+
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, ibc::Height};
+
+/// `CLIENT_STATE` is the item that stores the [`ClientState`].
+pub const CLIENT_STATE: Item<ClientState> = Item::new("client_state");
+
+/// `CONSENSUS_STATES` is the map of all (revision number, revision height) pairs to their
+/// attested [`ConsensusState`].
+pub const CONSENSUS_STATES: Map<(u64, u64), ConsensusState> = Map::new("consensus_states");
+
+/// The client state of the multisig attestor light client.
+#[cw_serde]
+pub struct ClientState {
+    /// The chain id of the counterparty. It is signed over by the attestors.
+    pub chain_id: String,
+    /// The public keys of the current attestors.
+    pub attestors: Vec<PublicKey>,
+    /// The minimum number of distinct attestor signatures required.
+    pub threshold: u32,
+    /// The number of key rotations applied to this client.
+    #[serde(default)]
+    pub key_set_sequence: u64,
+    /// The latest attested height.
+    pub latest_height: Height,
+    /// The height at which misbehaviour was detected, if the client is frozen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frozen_height: Option<Height>,
+}
+
+/// An attested consensus state of the counterparty.
+#[cw_serde]
+pub struct ConsensusState {
+    /// The timestamp of the counterparty in nanoseconds since the unix epoch.
+    pub timestamp: u64,
+    /// The attested merkle root of the counterparty state.
+    pub root: Binary,
+}
+
+/// The public key of an attestor.
+#[cw_serde]
+pub enum PublicKey {
+    /// A compressed or uncompressed `secp256k1` public key.
+    Secp256k1(Binary),
+    /// An `ed25519` public key.
+    Ed25519(Binary),
+}
+
+impl ClientState {
+    /// Validates the attestor set and threshold of the client state.
+    ///
+    /// # Errors
+    /// Returns an error if the threshold is zero or larger than the number of attestors, or if
+    /// an attestor is listed more than once.
+    pub fn validate(&self) -> Result<(), LightClientError> {
+        validate_key_set(&self.attestors, self.threshold)?;
+        if self.chain_id.is_empty() {
+            return Err(LightClientError::InvalidClientState(
+                "chain id cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the client is frozen.
+    #[must_use]
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen_height.is_some()
+    }
+}
+
+/// Validates an attestor set and threshold.
+///
+/// # Errors
+/// Returns an error if the threshold is zero or larger than the number of attestors, or if
+/// an attestor is listed more than once.
+pub fn validate_key_set(attestors: &[PublicKey], threshold: u32) -> Result<(), LightClientError> {
+    if threshold == 0 || threshold as usize > attestors.len() {
+        return Err(LightClientError::InvalidClientState(format!(
+            "threshold must be between 1 and {}, got {threshold}",
+            attestors.len()
+        )));
+    }
+    if attestors
+        .iter()
+        .enumerate()
+        .any(|(i, key)| attestors[..i].contains(key))
+    {
+        return Err(LightClientError::InvalidClientState(
+            "attestors must be unique".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,369 @@
+//! This module contains the attestation and key rotation verification logic.
+
+use cosmwasm_std::Api;
+use sha2::{Digest, Sha256};
+
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+
+use super::{
+    keys, merkle,
+    msg::{Attestation, KeyRotation, MembershipProof, Misbehaviour, NonMembershipProof, Signature},
+    state::{ClientState, ConsensusState, PublicKey},
+};
+
+/// Returns the sign bytes of an attestation.
+#[must_use]
+pub fn attestation_sign_bytes(chain_id: &str, attestation: &Attestation) -> Vec<u8> {
+    let mut sign_bytes = keys::ATTESTATION_DOMAIN.to_vec();
+    sign_bytes.extend(merkle::length_prefixed(chain_id.as_bytes()));
+    sign_bytes.extend(attestation.height.revision_number.to_be_bytes());
+    sign_bytes.extend(attestation.height.revision_height.to_be_bytes());
+    sign_bytes.extend(attestation.timestamp.to_be_bytes());
+    sign_bytes.extend(merkle::length_prefixed(attestation.root.as_slice()));
+    sign_bytes
+}
+
+/// Returns the sign bytes of a key rotation.
+#[must_use]
+pub fn key_rotation_sign_bytes(
+    chain_id: &str,
+    key_set_sequence: u64,
+    key_rotation: &KeyRotation,
+) -> Vec<u8> {
+    let mut sign_bytes = keys::KEY_ROTATION_DOMAIN.to_vec();
+    sign_bytes.extend(merkle::length_prefixed(chain_id.as_bytes()));
+    sign_bytes.extend(key_set_sequence.to_be_bytes());
+    sign_bytes.extend(key_rotation.new_threshold.to_be_bytes());
+    for attestor in &key_rotation.new_attestors {
+        let (key_type, public_key) = match attestor {
+            PublicKey::Secp256k1(public_key) => (0_u8, public_key),
+            PublicKey::Ed25519(public_key) => (1_u8, public_key),
+        };
+        sign_bytes.push(key_type);
+        sign_bytes.extend(merkle::length_prefixed(public_key.as_slice()));
+    }
+    sign_bytes
+}
+
+/// Verifies that at least `threshold` distinct attestors of the current set signed the digest
+/// of the sign bytes.
+///
+/// # Errors
+/// Returns an error if a signature is invalid, if an attestor index is out of bounds, or if the
+/// threshold is not met.
+pub fn verify_signatures(
+    api: &dyn Api,
+    client_state: &ClientState,
+    sign_bytes: &[u8],
+    signatures: &[Signature],
+) -> Result<(), ContractError> {
+    let digest = Sha256::digest(sign_bytes);
+
+    let mut signers: Vec<u32> = Vec::with_capacity(signatures.len());
+    for signature in signatures {
+        if signers.contains(&signature.attestor_index) {
+            continue;
+        }
+        let attestor = client_state
+            .attestors
+            .get(signature.attestor_index as usize)
+            .ok_or(LightClientError::InvalidSignature)?;
+        let is_valid = match attestor {
+            PublicKey::Secp256k1(public_key) => {
+                api.secp256k1_verify(&digest, &signature.signature, public_key)?
+            }
+            PublicKey::Ed25519(public_key) => {
+                api.ed25519_verify(&digest, &signature.signature, public_key)?
+            }
+        };
+        if !is_valid {
+            return Err(LightClientError::InvalidSignature.into());
+        }
+        signers.push(signature.attestor_index);
+    }
+
+    if signers.len() < client_state.threshold as usize {
+        return Err(LightClientError::InvalidHeader(format!(
+            "not enough attestor signatures: required {}, got {}",
+            client_state.threshold,
+            signers.len()
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verifies an attestation against the current attestor set.
+///
+/// # Errors
+/// Returns an error if the attestation is malformed or not signed by the threshold.
+pub fn verify_attestation(
+    api: &dyn Api,
+    client_state: &ClientState,
+    attestation: &Attestation,
+) -> Result<(), ContractError> {
+    if attestation.timestamp == 0 {
+        return Err(LightClientError::InvalidHeader("timestamp cannot be 0".to_string()).into());
+    }
+    if attestation.height.revision_height == 0 {
+        return Err(
+            LightClientError::InvalidHeader("revision height cannot be 0".to_string()).into(),
+        );
+    }
+
+    let sign_bytes = attestation_sign_bytes(&client_state.chain_id, attestation);
+    verify_signatures(api, client_state, &sign_bytes, &attestation.signatures)
+}
+
+/// Verifies a key rotation against the current attestor set and validates the new set.
+///
+/// # Errors
+/// Returns an error if the new set is invalid or the rotation is not signed by the threshold.
+pub fn verify_key_rotation(
+    api: &dyn Api,
+    client_state: &ClientState,
+    key_rotation: &KeyRotation,
+) -> Result<(), ContractError> {
+    super::state::validate_key_set(&key_rotation.new_attestors, key_rotation.new_threshold)?;
+
+    let sign_bytes = key_rotation_sign_bytes(
+        &client_state.chain_id,
+        client_state.key_set_sequence,
+        key_rotation,
+    );
+    verify_signatures(api, client_state, &sign_bytes, &key_rotation.signatures)
+}
+
+/// Verifies that the misbehaviour consists of two valid attestations that attest different
+/// consensus states at the same height.
+///
+/// # Errors
+/// Returns an error if the misbehaviour is invalid.
+pub fn verify_misbehaviour(
+    api: &dyn Api,
+    client_state: &ClientState,
+    misbehaviour: &Misbehaviour,
+) -> Result<(), ContractError> {
+    let (one, two) = (&misbehaviour.attestation_one, &misbehaviour.attestation_two);
+    if one.height != two.height {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "attestations must be for the same height".to_string(),
+        )
+        .into());
+    }
+    if one.timestamp == two.timestamp && one.root == two.root {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "attestations must attest different consensus states".to_string(),
+        )
+        .into());
+    }
+
+    verify_attestation(api, client_state, one)?;
+    verify_attestation(api, client_state, two)
+}
+
+/// Verifies that the value is stored at the merkle path in the attested consensus state.
+///
+/// # Errors
+/// Returns an error if the proof does not match the attested root.
+pub fn verify_membership(
+    consensus_state: &ConsensusState,
+    proof: &MembershipProof,
+    key_path: &[String],
+    value: &[u8],
+) -> Result<(), ContractError> {
+    if key_path.is_empty() {
+        return Err(LightClientError::EmptyMerklePath.into());
+    }
+
+    verify_leaf(consensus_state, proof, key_path, value)
+}
+
+/// Verifies that nothing is stored at the merkle path in the attested consensus state, by
+/// verifying the leaves directly before and after the position of the path.
+///
+/// NOTE: This relies on the attestors only attesting trees whose leaves are sorted by
+/// [`merkle::leaf_key`] in strictly increasing order.
+///
+/// # Errors
+/// Returns an error if the leaves are not adjacent, do not surround the path, or do not match
+/// the attested root.
+pub fn verify_non_membership(
+    consensus_state: &ConsensusState,
+    proof: &NonMembershipProof,
+    key_path: &[String],
+) -> Result<(), ContractError> {
+    if key_path.is_empty() {
+        return Err(LightClientError::EmptyMerklePath.into());
+    }
+
+    let key = merkle::leaf_key(key_path);
+    if let Some(left) = &proof.left {
+        if merkle::leaf_key(&left.key_path) >= key {
+            return Err(LightClientError::InvalidProof(
+                "left leaf does not precede the path".to_string(),
+            )
+            .into());
+        }
+        verify_leaf(consensus_state, &left.proof, &left.key_path, &left.value)?;
+    }
+    if let Some(right) = &proof.right {
+        if merkle::leaf_key(&right.key_path) <= key {
+            return Err(LightClientError::InvalidProof(
+                "right leaf does not follow the path".to_string(),
+            )
+            .into());
+        }
+        verify_leaf(consensus_state, &right.proof, &right.key_path, &right.value)?;
+    }
+
+    let is_adjacent = match (&proof.left, &proof.right) {
+        (Some(left), Some(right)) => merkle::are_adjacent(
+            left.proof.leaf_index,
+            left.proof.siblings.len(),
+            right.proof.leaf_index,
+            right.proof.siblings.len(),
+        ),
+        (Some(left), None) => merkle::is_last(left.proof.leaf_index, left.proof.siblings.len()),
+        (None, Some(right)) => right.proof.leaf_index == 0,
+        (None, None) => false,
+    };
+    if !is_adjacent {
+        return Err(LightClientError::InvalidProof(
+            "leaves are not adjacent to the path".to_string(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verifies that the leaf with the merkle path and value is in the attested tree.
+fn verify_leaf(
+    consensus_state: &ConsensusState,
+    proof: &MembershipProof,
+    key_path: &[String],
+    value: &[u8],
+) -> Result<(), ContractError> {
+    let depth = proof.siblings.len();
+    if depth > merkle::MAX_DEPTH || proof.leaf_index >> depth != 0 {
+        return Err(
+            LightClientError::InvalidProof("leaf index is out of bounds".to_string()).into(),
+        );
+    }
+
+    let leaf = merkle::leaf_hash(key_path, value);
+    let root = merkle::compute_root(leaf, proof.leaf_index, proof.siblings.as_slice());
+    if root != consensus_state.root.as_slice() {
+        return Err(LightClientError::InvalidProof(
+            "computed root does not match the attested root".to_string(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Binary;
+
+    use super::{
+        merkle, verify_membership, verify_non_membership, ConsensusState, ContractError,
+        MembershipProof, NonMembershipProof,
+    };
+    use crate::types::msg::AdjacentLeaf;
+
+    fn path(key: &str) -> Vec<String> {
+        vec!["ibc".to_string(), key.to_string()]
+    }
+
+    /// Returns the leaf of the path with `key`, whose value is the key itself.
+    fn leaf(key: &str) -> Vec<u8> {
+        merkle::leaf_hash(&path(key), key.as_bytes())
+    }
+
+    /// The attested tree is `node(node(b, d), f)`, with its leaves sorted by their keys.
+    fn consensus_state() -> ConsensusState {
+        let root = merkle::node_hash(&merkle::node_hash(&leaf("b"), &leaf("d")), &leaf("f"));
+        ConsensusState {
+            timestamp: 1,
+            root: root.into(),
+        }
+    }
+
+    fn proof(key: &str) -> MembershipProof {
+        let (leaf_index, siblings) = match key {
+            "b" => (0, vec![leaf("d"), leaf("f")]),
+            "d" => (1, vec![leaf("b"), leaf("f")]),
+            "f" => (1, vec![merkle::node_hash(&leaf("b"), &leaf("d"))]),
+            _ => unreachable!("{key} is not in the tree"),
+        };
+        MembershipProof {
+            leaf_index,
+            siblings: siblings.into_iter().map(Binary::from).collect(),
+        }
+    }
+
+    fn non_membership(
+        key: &str,
+        left: Option<&str>,
+        right: Option<&str>,
+    ) -> Result<(), ContractError> {
+        let adjacent = |key: &str| AdjacentLeaf {
+            key_path: path(key),
+            value: key.as_bytes().into(),
+            proof: proof(key),
+        };
+        let proof = NonMembershipProof {
+            left: left.map(adjacent),
+            right: right.map(adjacent),
+        };
+        verify_non_membership(&consensus_state(), &proof, &path(key))
+    }
+
+    #[test]
+    fn membership_is_verified() {
+        for key in ["b", "d", "f"] {
+            verify_membership(&consensus_state(), &proof(key), &path(key), key.as_bytes()).unwrap();
+        }
+        verify_membership(&consensus_state(), &proof("b"), &path("b"), b"").unwrap_err();
+        verify_membership(&consensus_state(), &proof("b"), &path("d"), b"b").unwrap_err();
+        verify_membership(&consensus_state(), &proof("b"), &[], b"b").unwrap_err();
+
+        // the index of `f` is aliased by higher bits that are not covered by its siblings
+        let aliased = MembershipProof {
+            leaf_index: 3,
+            ..proof("f")
+        };
+        verify_membership(&consensus_state(), &aliased, &path("f"), b"f").unwrap_err();
+    }
+
+    #[test]
+    fn non_membership_is_proven_by_adjacent_leaves() {
+        non_membership("c", Some("b"), Some("d")).unwrap();
+        non_membership("e", Some("d"), Some("f")).unwrap();
+        non_membership("a", None, Some("b")).unwrap();
+        non_membership("g", Some("f"), None).unwrap();
+        // keys are ordered by their length prefixed encoding
+        non_membership("bb", Some("f"), None).unwrap();
+    }
+
+    #[test]
+    fn non_membership_rejects_invalid_proofs() {
+        // stored paths
+        non_membership("d", Some("b"), Some("f")).unwrap_err();
+        non_membership("d", Some("b"), Some("d")).unwrap_err();
+        non_membership("d", Some("d"), Some("f")).unwrap_err();
+        // leaves that are not adjacent
+        non_membership("c", Some("b"), Some("f")).unwrap_err();
+        non_membership("e", Some("b"), Some("f")).unwrap_err();
+        non_membership("c", None, Some("d")).unwrap_err();
+        non_membership("e", Some("d"), None).unwrap_err();
+        non_membership("c", None, None).unwrap_err();
+        // leaves that do not surround the path
+        non_membership("c", Some("d"), Some("b")).unwrap_err();
+        non_membership("a", Some("b"), Some("d")).unwrap_err();
+    }
+}
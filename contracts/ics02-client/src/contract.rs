@@ -6,7 +6,7 @@ use cw_ibc_lite_shared::types::error::ContractError;
 
 use crate::types::{
     keys,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state,
 };
 
@@ -20,11 +20,14 @@ pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, keys::CONTRACT_VERSION)?;
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(info.sender.as_str()))?;
 
+    let creation_policy = msg.creation_policy.unwrap_or_default().validate(deps.api)?;
+    state::CREATION_POLICY.save(deps.storage, &creation_policy)?;
+
     Ok(Response::default())
 }
 
@@ -56,6 +59,9 @@ pub fn execute(
             client_id,
             counterparty_info,
         } => execute::provide_counterparty(deps, env, info, client_id, counterparty_info),
+        ExecuteMsg::UpdateCreationPolicy { creation_policy } => {
+            execute::update_creation_policy(deps, env, info, creation_policy)
+        }
     }
 }
 
@@ -67,10 +73,32 @@ pub fn execute(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::ClientInfo { client_id } => query::client_info(deps, env, client_id),
+        QueryMsg::CreationPolicy {} => query::creation_policy(deps, env),
     }
 }
 
+/// Migrates the contract to this version. Refuses to migrate from another contract or from a
+/// newer version of this one.
+///
+/// # Errors
+/// Will return an error if the recorded contract name or version does not allow the migration.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // NOTE: State migrations from the returned previous version are to be run here once the
+    // storage layout changes.
+    cw_ibc_lite_shared::utils::migrate::ensure_from_older_version(
+        deps.storage,
+        keys::CONTRACT_NAME,
+        keys::CONTRACT_VERSION,
+    )?;
+
+    Ok(Response::default())
+}
+
 mod execute {
+    use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Storage, Uint128};
+
     use super::{state, ContractError, DepsMut, Env, MessageInfo, Response};
 
     use crate::types::events;
@@ -83,11 +111,16 @@ mod execute {
         env: Env,
         info: MessageInfo,
         code_id: u64,
-        instantiate_msg: cw_ibc_lite_shared::types::clients::msg::InstantiateMsg,
+        mut instantiate_msg: cw_ibc_lite_shared::types::clients::msg::InstantiateMsg,
         counterparty_info: Option<state::CounterpartyInfo>,
     ) -> Result<Response, ContractError> {
+        let fee_msgs = enforce_creation_policy(deps.storage, &info)?;
         let client_id = state::helpers::new_client_id(deps.storage)?;
 
+        let checksum = deps.querier.query_wasm_code_info(code_id)?.checksum;
+        instantiate_msg.checksum = Some(checksum.as_slice().into());
+        state::CHECKSUMS.save(deps.storage, &client_id, &checksum)?;
+
         state::CREATORS.save(deps.storage, &client_id, &info.sender)?;
         if let Some(counterparty_info) = &counterparty_info {
             state::COUNTERPARTY.save(deps.storage, &client_id, counterparty_info)?;
@@ -100,7 +133,6 @@ mod execute {
             &deps.querier,
             &env,
             instantiate_msg,
-            // TODO: Make sure there is no DOS vector here.
             &client_id,
             Some(&env.contract.address),
             &client_id,
@@ -109,6 +141,7 @@ mod execute {
         state::CLIENTS.save(deps.storage, &client_id, &address)?;
 
         Ok(Response::new()
+            .add_messages(fee_msgs)
             .add_message(instantiate2)
             .add_event(events::create_client::success(
                 &client_id,
@@ -118,6 +151,121 @@ mod execute {
             )))
     }
 
+    /// Enforces the [`state::CreationPolicy`] on the sender of a
+    /// [`crate::types::msg::ExecuteMsg::CreateClient`] message. Returns the messages
+    /// forwarding the creation fee to the collector and refunding the excess funds to the
+    /// sender, if any.
+    fn enforce_creation_policy(
+        storage: &mut dyn Storage,
+        info: &MessageInfo,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
+        let creation_policy = state::CREATION_POLICY
+            .may_load(storage)?
+            .unwrap_or_default();
+        let created_clients = state::helpers::increment_created_clients(storage, &info.sender)?;
+
+        if !matches!(creation_policy, state::CreationPolicy::Fee { .. }) && !info.funds.is_empty() {
+            return Err(ContractError::UnexpectedFunds);
+        }
+
+        match creation_policy {
+            state::CreationPolicy::Permissionless => Ok(vec![]),
+            state::CreationPolicy::Fee { amount, collector } => {
+                let required = |denom: &str| -> Uint128 {
+                    amount
+                        .iter()
+                        .filter(|coin| coin.denom == denom)
+                        .map(|coin| coin.amount)
+                        .sum()
+                };
+                let provided = |denom: &str| -> Uint128 {
+                    info.funds
+                        .iter()
+                        .filter(|coin| coin.denom == denom)
+                        .map(|coin| coin.amount)
+                        .sum()
+                };
+                if amount
+                    .iter()
+                    .any(|coin| provided(&coin.denom) < required(&coin.denom))
+                {
+                    return Err(ContractError::insufficient_creation_fee(&amount));
+                }
+                let refund: Vec<_> = info
+                    .funds
+                    .iter()
+                    .filter_map(|coin| {
+                        let excess = coin.amount.saturating_sub(required(&coin.denom));
+                        (!excess.is_zero()).then(|| Coin::new(excess, &coin.denom))
+                    })
+                    .collect();
+
+                // NOTE: Only the fee is collected, the funds in excess of it are refunded.
+                let fee: Vec<_> = amount
+                    .into_iter()
+                    .filter(|coin| !coin.amount.is_zero())
+                    .collect();
+                let mut msgs = vec![];
+                if !fee.is_empty() {
+                    msgs.push(
+                        BankMsg::Send {
+                            to_address: collector,
+                            amount: fee,
+                        }
+                        .into(),
+                    );
+                }
+                if !refund.is_empty() {
+                    msgs.push(
+                        BankMsg::Send {
+                            to_address: info.sender.to_string(),
+                            amount: refund,
+                        }
+                        .into(),
+                    );
+                }
+                Ok(msgs)
+            }
+            state::CreationPolicy::Quota {
+                max_clients_per_creator,
+            } => {
+                if created_clients > max_clients_per_creator {
+                    return Err(ContractError::ClientQuotaExceeded {
+                        max: max_clients_per_creator,
+                    });
+                }
+                Ok(vec![])
+            }
+            state::CreationPolicy::Allowlist { creators } => {
+                if !creators
+                    .iter()
+                    .any(|creator| creator == info.sender.as_str())
+                {
+                    return Err(ContractError::Unauthorized);
+                }
+                Ok(vec![])
+            }
+        }
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_creation_policy(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        creation_policy: state::CreationPolicy,
+    ) -> Result<Response, ContractError> {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        let creation_policy = creation_policy.validate(deps.api)?;
+        state::CREATION_POLICY.save(deps.storage, &creation_policy)?;
+
+        Ok(
+            Response::new().add_event(events::update_creation_policy::success(
+                &cosmwasm_std::to_json_string(&creation_policy)?,
+            )),
+        )
+    }
+
     #[allow(clippy::needless_pass_by_value, clippy::module_name_repetitions)]
     pub fn execute_client(
         deps: DepsMut,
@@ -150,6 +298,10 @@ mod execute {
 
         let substitute_client_address = state::CLIENTS.load(deps.storage, &substitute_client_id)?;
         state::CLIENTS.save(deps.storage, &subject_client_id, &substitute_client_address)?;
+        match state::CHECKSUMS.may_load(deps.storage, &substitute_client_id)? {
+            Some(checksum) => state::CHECKSUMS.save(deps.storage, &subject_client_id, &checksum)?,
+            None => state::CHECKSUMS.remove(deps.storage, &subject_client_id),
+        }
 
         Ok(Response::new().add_event(events::migrate_client::success(
             &subject_client_id,
@@ -192,6 +344,7 @@ mod query {
         let address = state::CLIENTS.load(deps.storage, &client_id)?;
         let counterparty_info = state::COUNTERPARTY.may_load(deps.storage, &client_id)?;
         let creator = state::CREATORS.load(deps.storage, &client_id)?;
+        let checksum = state::CHECKSUMS.may_load(deps.storage, &client_id)?;
 
         Ok(cosmwasm_std::to_json_binary(
             &query_responses::ClientInfo {
@@ -199,7 +352,89 @@ mod query {
                 address: address.into_string(),
                 counterparty_info,
                 creator: creator.into_string(),
+                checksum,
             },
         )?)
     }
+
+    /// Returns the [`state::CreationPolicy`] encoded as a JSON binary.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn creation_policy(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let creation_policy = state::CREATION_POLICY
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+
+        Ok(cosmwasm_std::to_json_binary(&creation_policy)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    use crate::types::{
+        msg::query_responses,
+        state::{CounterpartyInfo, CreationPolicy},
+    };
+
+    use super::{keys, migrate, query, state, MigrateMsg, QueryMsg};
+
+    /// The last released version, which stores no creation policy, checksums or created clients.
+    const PREVIOUS_VERSION: &str = "0.1.0";
+    const CLIENT_ID: &str = "08-wasm-0";
+
+    #[test]
+    fn migrate_keeps_the_clients_of_the_previous_version() {
+        let mut deps = mock_dependencies();
+        let router = deps.api.addr_make("router");
+        let creator = deps.api.addr_make("creator");
+        let client = deps.api.addr_make("client");
+        let counterparty_info = CounterpartyInfo {
+            client_id: "08-wasm-1".to_string(),
+            merkle_path_prefix: None,
+        };
+        {
+            let deps = deps.as_mut();
+            cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, PREVIOUS_VERSION).unwrap();
+            cw_ownable::initialize_owner(deps.storage, deps.api, Some(router.as_str())).unwrap();
+            state::NEXT_CLIENT_NUMBER.save(deps.storage, &1).unwrap();
+            state::CLIENTS
+                .save(deps.storage, CLIENT_ID, &client)
+                .unwrap();
+            state::CREATORS
+                .save(deps.storage, CLIENT_ID, &creator)
+                .unwrap();
+            state::COUNTERPARTY
+                .save(deps.storage, CLIENT_ID, &counterparty_info)
+                .unwrap();
+        }
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, keys::CONTRACT_VERSION);
+        let ownership = cw_ownable::get_ownership(&deps.storage).unwrap();
+        assert_eq!(ownership.owner, Some(router));
+
+        let msg = QueryMsg::ClientInfo {
+            client_id: CLIENT_ID.to_string(),
+        };
+        let client_info: query_responses::ClientInfo =
+            cosmwasm_std::from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(
+            client_info,
+            query_responses::ClientInfo {
+                client_id: CLIENT_ID.to_string(),
+                address: client.into_string(),
+                counterparty_info: Some(counterparty_info),
+                creator: creator.into_string(),
+                checksum: None,
+            }
+        );
+
+        let msg = QueryMsg::CreationPolicy {};
+        let creation_policy: CreationPolicy =
+            cosmwasm_std::from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(creation_policy, CreationPolicy::Permissionless);
+    }
 }
@@ -206,4 +206,15 @@ impl<'a> Ics02ClientContractQuerier<'a> {
             },
         )
     }
+
+    /// `creation_policy` sends a [`msg::QueryMsg::CreationPolicy`] query to this contract.
+    /// It returns the policy enforced on client creation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the query fails
+    pub fn creation_policy(&self) -> StdResult<crate::types::state::CreationPolicy> {
+        self.querier
+            .query_wasm_smart(&self.addr, &msg::QueryMsg::CreationPolicy {})
+    }
 }
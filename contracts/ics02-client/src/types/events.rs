@@ -6,6 +6,8 @@ pub const EVENT_TYPE_CREATE_CLIENT: &str = "create_client";
 pub const EVENT_TYPE_PROVIDE_COUNTERPARTY: &str = "provide_counterparty";
 /// `EVENT_TYPE_MIGRATE_CLIENT` is the event type for a migrate client event
 pub const EVENT_TYPE_MIGRATE_CLIENT: &str = "migrate_client";
+/// `EVENT_TYPE_UPDATE_CREATION_POLICY` is the event type for an update creation policy event
+pub const EVENT_TYPE_UPDATE_CREATION_POLICY: &str = "update_creation_policy";
 
 /// `ATTRIBUTE_KEY_CLIENT_ID` is the attribute key for the client id
 pub const ATTRIBUTE_KEY_CLIENT_ID: &str = "client_id";
@@ -26,6 +28,8 @@ pub const ATTRIBUTE_KEY_SUBSTITUTE_CLIENT_ID: &str = "substitute_client_id";
 /// `ATTRIBUTE_KEY_SUBSTITUTE_CLIENT_ADDRESS` is the attribute key for the substitute client's
 /// contract address
 pub const ATTRIBUTE_KEY_SUBSTITUTE_CLIENT_ADDRESS: &str = "substitute_client_address";
+/// `ATTRIBUTE_KEY_CREATION_POLICY` is the attribute key for the JSON encoded creation policy
+pub const ATTRIBUTE_KEY_CREATION_POLICY: &str = "creation_policy";
 
 /// Contains event messages emitted during [`super::super::msg::ExecuteMsg::CreateClient`] execution.
 pub mod create_client {
@@ -113,3 +117,18 @@ pub mod migrate_client {
         ])
     }
 }
+
+/// Contains event messages emitted during [`super::super::msg::ExecuteMsg::UpdateCreationPolicy`]
+/// execution.
+pub mod update_creation_policy {
+    use cosmwasm_std::{Attribute, Event};
+
+    /// `update_creation_policy` is the event message for an update creation policy event
+    #[must_use]
+    pub fn success(creation_policy: &str) -> Event {
+        Event::new(super::EVENT_TYPE_UPDATE_CREATION_POLICY).add_attributes(vec![Attribute::new(
+            super::ATTRIBUTE_KEY_CREATION_POLICY,
+            creation_policy,
+        )])
+    }
+}
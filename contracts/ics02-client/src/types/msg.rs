@@ -7,7 +7,17 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 /// The message to instantiate the contract.
 /// Sender is assumed to be cw-ibc-lite-router, and becomes the owner of the contract.
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// The policy enforced on client creation.
+    /// Defaults to [`super::state::CreationPolicy::Permissionless`] if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_policy: Option<super::state::CreationPolicy>,
+}
+
+/// The message to migrate the contract.
+#[cw_serde]
+pub struct MigrateMsg {}
 
 /// The execute messages supported by the contract.
 #[cw_serde]
@@ -44,6 +54,12 @@ pub enum ExecuteMsg {
         /// Counterparty client information.
         counterparty_info: super::state::CounterpartyInfo,
     },
+    /// Update the policy enforced on client creation.
+    /// Only the owner of this contract, cw-ibc-lite-router, can update the policy.
+    UpdateCreationPolicy {
+        /// The new policy enforced on client creation.
+        creation_policy: super::state::CreationPolicy,
+    },
 }
 
 /// The query messages supported by the contract.
@@ -56,6 +72,9 @@ pub enum QueryMsg {
         /// The client id of the client to get the address of.
         client_id: String,
     },
+    /// Get the policy enforced on client creation.
+    #[returns(super::state::CreationPolicy)]
+    CreationPolicy {},
 }
 
 /// Contains the query responses supported by the contract.
@@ -93,5 +112,9 @@ pub mod query_responses {
         pub counterparty_info: Option<super::super::state::CounterpartyInfo>,
         /// The creator address of the client.
         pub creator: String,
+        /// The checksum of the client's contract code.
+        /// None if the client was created before checksums were recorded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub checksum: Option<cosmwasm_std::Checksum>,
     }
 }
@@ -1,6 +1,6 @@
 //! This module defines the state storage of the Contract.
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Api, Checksum, Coin, StdResult};
 use cw_storage_plus::{Item, Map};
 use ibc_client_cw::types::MerklePath;
 
@@ -17,6 +17,16 @@ pub const COUNTERPARTY: Map<&str, CounterpartyInfo> = Map::new("counterparty");
 /// `CREATORS` is the map of all client ids to their creator address.
 pub const CREATORS: Map<&str, Addr> = Map::new("creators");
 
+/// `CHECKSUMS` is the map of all client ids to the checksum of their contract code.
+pub const CHECKSUMS: Map<&str, Checksum> = Map::new("checksums");
+
+/// `CREATION_POLICY` is the item that stores the [`CreationPolicy`] enforced on
+/// [`super::msg::ExecuteMsg::CreateClient`].
+pub const CREATION_POLICY: Item<CreationPolicy> = Item::new("creation_policy");
+
+/// `CREATED_CLIENTS` is the map of all creator addresses to the number of clients they created.
+pub const CREATED_CLIENTS: Map<&Addr, u64> = Map::new("created_clients");
+
 /// Counterparty client information.
 #[cosmwasm_schema::cw_serde]
 pub struct CounterpartyInfo {
@@ -27,6 +37,58 @@ pub struct CounterpartyInfo {
     pub merkle_path_prefix: Option<MerklePath>,
 }
 
+/// The policy that controls who may create clients, and at what cost.
+/// Each client instantiates a new light client contract, so chains with permissionless
+/// contract instantiation should pick a restrictive policy.
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub enum CreationPolicy {
+    /// Anyone can create any number of clients for free.
+    #[default]
+    Permissionless,
+    /// Anyone can create clients by paying a fee in native funds.
+    /// The fee is forwarded to the collector, and any funds attached to
+    /// [`super::msg::ExecuteMsg::CreateClient`] in excess of it are refunded to the creator.
+    Fee {
+        /// The fee required to create a client.
+        amount: Vec<Coin>,
+        /// The address that receives the fees.
+        collector: String,
+    },
+    /// Anyone can create clients, up to a fixed number of clients per creator.
+    Quota {
+        /// The maximum number of clients a single creator can create.
+        max_clients_per_creator: u64,
+    },
+    /// Only the listed addresses can create clients.
+    Allowlist {
+        /// The addresses allowed to create clients.
+        creators: Vec<String>,
+    },
+}
+
+impl CreationPolicy {
+    /// Validates the addresses in the policy and returns the policy with normalized addresses.
+    ///
+    /// # Errors
+    /// Returns an error if any of the addresses in the policy is invalid.
+    pub fn validate(self, api: &dyn Api) -> StdResult<Self> {
+        Ok(match self {
+            Self::Fee { amount, collector } => Self::Fee {
+                amount,
+                collector: api.addr_validate(&collector)?.into_string(),
+            },
+            Self::Allowlist { creators } => Self::Allowlist {
+                creators: creators
+                    .iter()
+                    .map(|creator| Ok(api.addr_validate(creator)?.into_string()))
+                    .collect::<StdResult<_>>()?,
+            },
+            policy @ (Self::Permissionless | Self::Quota { .. }) => policy,
+        })
+    }
+}
+
 /// Contains state storage helpers.
 pub mod helpers {
     use cosmwasm_std::{StdResult, Storage};
@@ -47,6 +109,23 @@ pub mod helpers {
         Ok(format!("{}{}", keys::CLIENT_ID_PREFIX, client_number))
     }
 
+    /// Increments the number of clients created by the given creator and returns the new count.
+    ///
+    /// # Errors
+    /// Returns an error if the count cannot be loaded or saved.
+    pub fn increment_created_clients(
+        storage: &mut dyn Storage,
+        creator: &cosmwasm_std::Addr,
+    ) -> StdResult<u64> {
+        let count = super::CREATED_CLIENTS
+            .may_load(storage, creator)?
+            .unwrap_or_default()
+            + 1;
+        super::CREATED_CLIENTS.save(storage, creator, &count)?;
+
+        Ok(count)
+    }
+
     /// Asserts that the given creator is the creator of the client.
     ///
     /// # Errors
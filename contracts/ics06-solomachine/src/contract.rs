@@ -0,0 +1,786 @@
+//! This module handles the execution logic of the contract.
+
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+use ibc_proto::ibc::lightclients::solomachine::v3::{ClientState, ConsensusState};
+
+use crate::types::{
+    keys,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state,
+};
+
+/// Instantiates a new contract.
+///
+/// # Errors
+/// Will return an error if the instantiation fails.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let client_state: ClientState =
+        state::decode_any(msg.client_state.as_slice(), keys::CLIENT_STATE_TYPE_URL)?;
+    let consensus_state: ConsensusState = state::decode_any(
+        msg.consensus_state.as_slice(),
+        keys::CONSENSUS_STATE_TYPE_URL,
+    )?;
+
+    state::helpers::validate(&client_state)?;
+    if *state::helpers::consensus_state(&client_state)? != consensus_state {
+        return Err(LightClientError::InvalidConsensusState(
+            "consensus state in the client state does not match the initial consensus state"
+                .to_string(),
+        )
+        .into());
+    }
+    state::save_client_state(deps.storage, &client_state);
+
+    Ok(Response::default())
+}
+
+/// Handles the execution of the contract by routing the messages to the respective handlers.
+/// Can be called by anyone.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateState(msg) => {
+            execute::update_state(deps, env, msg.client_message.as_slice())
+        }
+        ExecuteMsg::UpdateStateOnMisbehaviour(msg) => {
+            execute::update_state_on_misbehaviour(deps, env, msg.client_message.as_slice())
+        }
+        ExecuteMsg::VerifyUpgradeAndUpdateState(_) => {
+            Err(LightClientError::UpgradeNotSupported.into())
+        }
+    }
+}
+
+/// Handles the query messages by routing them to the respective handlers.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Status(_) => query::status(deps, env),
+        QueryMsg::ExportMetadata(_) => query::export_metadata(),
+        QueryMsg::TimestampAtHeight(_) => query::timestamp_at_height(deps, env),
+        QueryMsg::VerifyClientMessage(msg) => {
+            query::verify_client_message(deps, env, msg.client_message.as_slice())
+        }
+        QueryMsg::CheckForMisbehaviour(msg) => {
+            query::check_for_misbehaviour(deps, env, msg.client_message.as_slice())
+        }
+        QueryMsg::VerifyMembership(_) | QueryMsg::VerifyNonMembership(_) => {
+            query::verify_membership(deps, env, msg.try_into()?)
+        }
+        QueryMsg::ClientState {} => query::client_state(deps, env),
+        QueryMsg::ConsensusState { height } => query::consensus_state(deps, env, height),
+        QueryMsg::LatestHeight {} => query::latest_height(deps, env),
+        QueryMsg::ConsensusHeights { start_after, limit } => {
+            query::consensus_heights(deps, env, start_after, limit)
+        }
+    }
+}
+
+mod execute {
+    use cw_ibc_lite_shared::types::clients::error::LightClientError;
+    use ibc_client_cw::types::ContractResult;
+    use ibc_proto::ibc::lightclients::solomachine::v3::ConsensusState;
+
+    use crate::types::{state, verification};
+
+    use super::{ContractError, DepsMut, Env, Response};
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state(
+        deps: DepsMut,
+        _env: Env,
+        client_message: &[u8],
+    ) -> Result<Response, ContractError> {
+        let mut client_state = state::load_client_state(deps.storage)?;
+        if client_state.is_frozen {
+            return Err(LightClientError::ClientFrozen.into());
+        }
+
+        let verification::ClientMessage::Header(header) =
+            verification::ClientMessage::decode(client_message)?
+        else {
+            return Err(LightClientError::InvalidHeader(
+                "update state only accepts headers, use update state on misbehaviour".to_string(),
+            )
+            .into());
+        };
+        verification::verify_header(deps.api, &client_state, &header)?;
+
+        client_state.sequence += 1;
+        client_state.consensus_state = Some(ConsensusState {
+            public_key: header.new_public_key,
+            diversifier: header.new_diversifier,
+            timestamp: header.timestamp,
+        });
+        state::save_client_state(deps.storage, &client_state);
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state_on_misbehaviour(
+        deps: DepsMut,
+        _env: Env,
+        client_message: &[u8],
+    ) -> Result<Response, ContractError> {
+        let mut client_state = state::load_client_state(deps.storage)?;
+
+        let verification::ClientMessage::Misbehaviour(misbehaviour) =
+            verification::ClientMessage::decode(client_message)?
+        else {
+            return Err(LightClientError::InvalidMisbehaviour(
+                "update state on misbehaviour only accepts misbehaviour".to_string(),
+            )
+            .into());
+        };
+        verification::verify_misbehaviour(deps.api, &client_state, &misbehaviour)?;
+
+        client_state.is_frozen = true;
+        state::save_client_state(deps.storage, &client_state);
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+}
+
+mod query {
+    use cw_ibc_lite_shared::types::{
+        clients::{
+            error::LightClientError,
+            msg::{query_responses, VerifyMembershipQuery},
+        },
+        ibc::Height,
+    };
+    use ibc_client_cw::types::ContractResult;
+    use ibc_proto::ibc::lightclients::solomachine::v3::TimestampedSignatureData;
+    use prost::Message;
+
+    use crate::types::{keys, state, verification};
+
+    use super::{Binary, ContractError, Deps, Env};
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn status(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        let status = if client_state.is_frozen {
+            keys::STATUS_FROZEN
+        } else {
+            keys::STATUS_ACTIVE
+        };
+
+        Ok(cosmwasm_std::to_json_binary(&query_responses::Status {
+            status: status.to_string(),
+        })?)
+    }
+
+    pub fn export_metadata() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ExportMetadata { metadata: vec![] },
+        )?)
+    }
+
+    /// Returns the timestamp of the current consensus state. A solo machine only has a single
+    /// consensus state, so the height is ignored.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn timestamp_at_height(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        let consensus_state = state::helpers::consensus_state(&client_state)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::TimestampAtHeight {
+                timestamp: consensus_state.timestamp,
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_client_message(
+        deps: Deps,
+        _env: Env,
+        client_message: &[u8],
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        match verification::ClientMessage::decode(client_message)? {
+            verification::ClientMessage::Header(header) => {
+                verification::verify_header(deps.api, &client_state, &header)?;
+            }
+            verification::ClientMessage::Misbehaviour(misbehaviour) => {
+                verification::verify_misbehaviour(deps.api, &client_state, &misbehaviour)?;
+            }
+        }
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::VerifyClientMessage { is_valid: true },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn check_for_misbehaviour(
+        deps: Deps,
+        _env: Env,
+        client_message: &[u8],
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        let found_misbehaviour = match verification::ClientMessage::decode(client_message)? {
+            verification::ClientMessage::Header(_) => false,
+            verification::ClientMessage::Misbehaviour(misbehaviour) => {
+                verification::verify_misbehaviour(deps.api, &client_state, &misbehaviour).is_ok()
+            }
+        };
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::CheckForMisbehaviour { found_misbehaviour },
+        )?)
+    }
+
+    /// Verifies a signature based (non-)membership proof. An empty value proves
+    /// non-membership.
+    ///
+    /// NOTE: Verification is read-only, so the sequence is not incremented here. The proof
+    /// height must match the current sequence, which the solo machine advances with headers.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_membership(
+        deps: Deps,
+        _env: Env,
+        query: VerifyMembershipQuery,
+    ) -> Result<Binary, ContractError> {
+        let VerifyMembershipQuery {
+            height,
+            proof,
+            key_path,
+            value,
+            ..
+        } = query;
+        let client_state = state::load_client_state(deps.storage)?;
+        if client_state.is_frozen {
+            return Err(LightClientError::ClientFrozen.into());
+        }
+
+        let latest_height = state::helpers::latest_height(&client_state);
+        if height != latest_height {
+            return Err(LightClientError::invalid_proof_height(
+                format!(
+                    "{}-{}",
+                    latest_height.revision_number, latest_height.revision_height
+                ),
+                format!("{}-{}", height.revision_number, height.revision_height),
+            )
+            .into());
+        }
+
+        if proof.is_empty() {
+            return Err(LightClientError::EmptyProof.into());
+        }
+        let timestamped_signature = TimestampedSignatureData::decode(proof.as_slice())?;
+        if timestamped_signature.signature_data.is_empty() {
+            return Err(LightClientError::EmptyProof.into());
+        }
+
+        let consensus_state = state::helpers::consensus_state(&client_state)?;
+        if consensus_state.timestamp > timestamped_signature.timestamp {
+            return Err(LightClientError::InvalidProofTimestamp {
+                consensus: consensus_state.timestamp,
+                proof: timestamped_signature.timestamp,
+            }
+            .into());
+        }
+
+        if key_path.is_empty() {
+            return Err(LightClientError::EmptyMerklePath.into());
+        }
+
+        let sign_bytes = verification::sign_bytes(
+            client_state.sequence,
+            timestamped_signature.timestamp,
+            consensus_state.diversifier.clone(),
+            verification::merkle_path_bytes(&key_path),
+            value.map(Vec::from).unwrap_or_default(),
+        );
+        let public_key = consensus_state.public_key.as_ref().ok_or_else(|| {
+            LightClientError::InvalidConsensusState("public key cannot be empty".to_string())
+        })?;
+        verification::verify_signature(
+            deps.api,
+            public_key,
+            &sign_bytes,
+            &timestamped_signature.signature_data,
+        )?;
+
+        Ok(cosmwasm_std::to_json_binary(&ContractResult::success())?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn client_state(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        let latest_height = state::helpers::latest_height(&client_state);
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ClientState {
+                frozen_height: client_state.is_frozen.then(|| latest_height.clone()),
+                latest_height,
+                chain_id: None,
+                trusting_period: None,
+            },
+        )?)
+    }
+
+    /// Returns the consensus state at the given height. A solo machine only stores the
+    /// consensus state at its latest height.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_state(deps: Deps, _env: Env, height: Height) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        if height != state::helpers::latest_height(&client_state) {
+            return Err(ContractError::not_found::<Height>(
+                format!("{}-{}", height.revision_number, height.revision_height).into_bytes(),
+            ));
+        }
+        let consensus_state = state::helpers::consensus_state(&client_state)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusState {
+                timestamp: consensus_state.timestamp,
+                root: Binary::default(),
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn latest_height(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::LatestHeight {
+                height: state::helpers::latest_height(&client_state),
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_heights(
+        deps: Deps,
+        _env: Env,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::load_client_state(deps.storage)?;
+        let latest_height = state::helpers::latest_height(&client_state);
+
+        let is_after_start = start_after.map_or(true, |start_after| {
+            (latest_height.revision_number, latest_height.revision_height)
+                > (start_after.revision_number, start_after.revision_height)
+        });
+        let heights = if is_after_start && limit != Some(0) {
+            vec![latest_height]
+        } else {
+            vec![]
+        };
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusHeights { heights },
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Binary, Deps, DepsMut, Response,
+    };
+    use ed25519_zebra::{SigningKey, VerificationKey};
+    use ibc_client_cw::types::{
+        CheckForMisbehaviourMsgRaw, MerklePath, StatusMsg, UpdateStateMsgRaw,
+        UpdateStateOnMisbehaviourMsgRaw, VerifyMembershipMsgRaw, VerifyNonMembershipMsgRaw,
+    };
+    use ibc_proto::{
+        cosmos::{
+            crypto::ed25519,
+            tx::signing::v1beta1::signature_descriptor::{
+                data::{Single, Sum},
+                Data as SignatureData,
+            },
+        },
+        google::protobuf::Any,
+        ibc::lightclients::solomachine::v3::{
+            Header, HeaderData, Misbehaviour, SignatureAndData, TimestampedSignatureData,
+        },
+    };
+    use prost::Message;
+
+    use cw_ibc_lite_shared::types::{clients::msg::query_responses, ibc::Height};
+
+    use crate::types::verification;
+
+    use super::{
+        execute, instantiate, keys, query, ClientState, ConsensusState, ContractError, ExecuteMsg,
+        InstantiateMsg, QueryMsg,
+    };
+
+    const SEED: [u8; 32] = [7; 32];
+    const NEW_SEED: [u8; 32] = [8; 32];
+    const DIVERSIFIER: &str = "diversifier";
+    const TIMESTAMP: u64 = 100;
+    const KEY: &str = "commitments/ports/transfer/channels/channel-0/sequences/1";
+
+    const fn height(sequence: u64) -> Height {
+        Height {
+            revision_number: 0,
+            revision_height: sequence,
+        }
+    }
+
+    fn any(type_url: &str, msg: &impl Message) -> Binary {
+        Any {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec(),
+        }
+        .encode_to_vec()
+        .into()
+    }
+
+    fn public_key(seed: [u8; 32]) -> Any {
+        let key = <[u8; 32]>::from(VerificationKey::from(&SigningKey::from(seed)));
+        Any {
+            type_url: keys::ED25519_PUBKEY_TYPE_URL.to_string(),
+            value: ed25519::PubKey { key: key.to_vec() }.encode_to_vec(),
+        }
+    }
+
+    /// Signs the message with the key of `seed`, as single signature data.
+    fn sign(seed: [u8; 32], message: &[u8]) -> Vec<u8> {
+        let signature = <[u8; 64]>::from(SigningKey::from(seed).sign(message));
+        SignatureData {
+            sum: Some(Sum::Single(Single {
+                mode: 0,
+                signature: signature.to_vec(),
+            })),
+        }
+        .encode_to_vec()
+    }
+
+    fn consensus_state() -> ConsensusState {
+        ConsensusState {
+            public_key: Some(public_key(SEED)),
+            diversifier: DIVERSIFIER.to_string(),
+            timestamp: TIMESTAMP,
+        }
+    }
+
+    fn instantiate_msg(
+        client_state: &ClientState,
+        consensus_state: &ConsensusState,
+    ) -> InstantiateMsg {
+        InstantiateMsg {
+            client_state: any(keys::CLIENT_STATE_TYPE_URL, client_state),
+            consensus_state: any(keys::CONSENSUS_STATE_TYPE_URL, consensus_state),
+            checksum: None,
+        }
+    }
+
+    /// Instantiates a client at sequence `1`, signed for by the key of [`SEED`].
+    fn instantiate_client(deps: DepsMut) {
+        let client_state = ClientState {
+            sequence: 1,
+            is_frozen: false,
+            consensus_state: Some(consensus_state()),
+        };
+        let msg = instantiate_msg(&client_state, &consensus_state());
+        instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
+
+    /// Returns the sign bytes of a proof of `value` at [`KEY`].
+    fn proof_sign_bytes(sequence: u64, timestamp: u64, diversifier: &str, value: &[u8]) -> Vec<u8> {
+        verification::sign_bytes(
+            sequence,
+            timestamp,
+            diversifier.to_string(),
+            verification::merkle_path_bytes(&["ibc".to_string(), KEY.to_string()]),
+            value.to_vec(),
+        )
+    }
+
+    /// Returns a proof of `value` at [`KEY`] signed with the key of `seed`.
+    fn proof(seed: [u8; 32], sequence: u64, diversifier: &str, value: &[u8]) -> Vec<u8> {
+        let timestamp = TIMESTAMP + 1;
+        TimestampedSignatureData {
+            signature_data: sign(
+                seed,
+                &proof_sign_bytes(sequence, timestamp, diversifier, value),
+            ),
+            timestamp,
+        }
+        .encode_to_vec()
+    }
+
+    fn merkle_path() -> MerklePath {
+        MerklePath {
+            key_path: vec!["ibc".to_string(), KEY.to_string()],
+        }
+    }
+
+    fn verify_membership(
+        deps: Deps,
+        sequence: u64,
+        proof: Vec<u8>,
+        value: &[u8],
+    ) -> Result<Binary, ContractError> {
+        let msg = VerifyMembershipMsgRaw {
+            proof,
+            path: merkle_path(),
+            value: value.to_vec(),
+            height: height(sequence).into(),
+            delay_time_period: 0,
+            delay_block_period: 0,
+        };
+        query(deps, mock_env(), QueryMsg::VerifyMembership(msg))
+    }
+
+    fn verify_non_membership(
+        deps: Deps,
+        sequence: u64,
+        proof: Vec<u8>,
+    ) -> Result<Binary, ContractError> {
+        let msg = VerifyNonMembershipMsgRaw {
+            proof,
+            path: merkle_path(),
+            height: height(sequence).into(),
+            delay_time_period: 0,
+            delay_block_period: 0,
+        };
+        query(deps, mock_env(), QueryMsg::VerifyNonMembership(msg))
+    }
+
+    /// Returns a header rotating to the key of [`NEW_SEED`], signed with the key of `seed`.
+    fn header(seed: [u8; 32], sequence: u64) -> Binary {
+        let header_data = HeaderData {
+            new_pub_key: Some(public_key(NEW_SEED)),
+            new_diversifier: "new-diversifier".to_string(),
+        };
+        let sign_bytes = verification::sign_bytes(
+            sequence,
+            TIMESTAMP + 1,
+            DIVERSIFIER.to_string(),
+            keys::SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+            header_data.encode_to_vec(),
+        );
+        let header = Header {
+            timestamp: TIMESTAMP + 1,
+            signature: sign(seed, &sign_bytes),
+            new_public_key: header_data.new_pub_key,
+            new_diversifier: header_data.new_diversifier,
+        };
+        any(keys::HEADER_TYPE_URL, &header)
+    }
+
+    /// Returns a misbehaviour at sequence `1` of two proofs at [`KEY`], of `one` and `two`.
+    fn misbehaviour(one: &[u8], two: &[u8]) -> Binary {
+        let signature_and_data = |value: &[u8]| SignatureAndData {
+            signature: sign(
+                SEED,
+                &proof_sign_bytes(1, TIMESTAMP + 1, DIVERSIFIER, value),
+            ),
+            path: verification::merkle_path_bytes(&["ibc".to_string(), KEY.to_string()]),
+            data: value.to_vec(),
+            timestamp: TIMESTAMP + 1,
+        };
+        let misbehaviour = Misbehaviour {
+            sequence: 1,
+            signature_one: Some(signature_and_data(one)),
+            signature_two: Some(signature_and_data(two)),
+        };
+        any(keys::MISBEHAVIOUR_TYPE_URL, &misbehaviour)
+    }
+
+    fn update_state(deps: DepsMut, client_message: Binary) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::UpdateState(UpdateStateMsgRaw { client_message });
+        execute(deps, mock_env(), mock_info("anyone", &[]), msg)
+    }
+
+    fn update_state_on_misbehaviour(
+        deps: DepsMut,
+        client_message: Binary,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::UpdateStateOnMisbehaviour(UpdateStateOnMisbehaviourMsgRaw {
+            client_message,
+        });
+        execute(deps, mock_env(), mock_info("anyone", &[]), msg)
+    }
+
+    fn status(deps: Deps) -> String {
+        let response = query(deps, mock_env(), QueryMsg::Status(StatusMsg {})).unwrap();
+        cosmwasm_std::from_json::<query_responses::Status>(response)
+            .unwrap()
+            .status
+    }
+
+    fn latest_height(deps: Deps) -> Height {
+        let response = query(deps, mock_env(), QueryMsg::LatestHeight {}).unwrap();
+        cosmwasm_std::from_json::<query_responses::LatestHeight>(response)
+            .unwrap()
+            .height
+    }
+
+    #[test]
+    fn instantiate_validates_the_client_state() {
+        let mut deps = mock_dependencies();
+        let client_state = ClientState {
+            sequence: 1,
+            is_frozen: false,
+            consensus_state: Some(consensus_state()),
+        };
+
+        // the consensus state must match the one in the client state
+        let other = ConsensusState {
+            timestamp: TIMESTAMP + 1,
+            ..consensus_state()
+        };
+        let msg = instantiate_msg(&client_state, &other);
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+
+        // the sequence cannot be 0
+        let msg = instantiate_msg(
+            &ClientState {
+                sequence: 0,
+                ..client_state.clone()
+            },
+            &consensus_state(),
+        );
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+
+        // the type urls must match
+        let msg = InstantiateMsg {
+            client_state: any(keys::CONSENSUS_STATE_TYPE_URL, &client_state),
+            ..instantiate_msg(&client_state, &consensus_state())
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap_err();
+
+        instantiate_client(deps.as_mut());
+        assert_eq!(status(deps.as_ref()), keys::STATUS_ACTIVE);
+        assert_eq!(latest_height(deps.as_ref()), height(1));
+    }
+
+    #[test]
+    fn membership_is_signed_at_the_current_sequence_and_diversifier() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+
+        let valid = proof(SEED, 1, DIVERSIFIER, b"value");
+        verify_membership(deps.as_ref(), 1, valid.clone(), b"value").unwrap();
+        // the proof is of another value
+        verify_membership(deps.as_ref(), 1, valid.clone(), b"other").unwrap_err();
+        // the proof height must be the current sequence
+        verify_membership(deps.as_ref(), 2, valid, b"value").unwrap_err();
+        // the proof is signed at another sequence
+        let other_sequence = proof(SEED, 2, DIVERSIFIER, b"value");
+        verify_membership(deps.as_ref(), 1, other_sequence, b"value").unwrap_err();
+        // the proof is signed with another diversifier
+        let other_diversifier = proof(SEED, 1, "other", b"value");
+        verify_membership(deps.as_ref(), 1, other_diversifier, b"value").unwrap_err();
+        // the proof is signed by another key
+        let other_key = proof(NEW_SEED, 1, DIVERSIFIER, b"value");
+        verify_membership(deps.as_ref(), 1, other_key, b"value").unwrap_err();
+        // the proof is older than the consensus state
+        let stale = TimestampedSignatureData {
+            signature_data: sign(
+                SEED,
+                &proof_sign_bytes(1, TIMESTAMP - 1, DIVERSIFIER, b"value"),
+            ),
+            timestamp: TIMESTAMP - 1,
+        };
+        verify_membership(deps.as_ref(), 1, stale.encode_to_vec(), b"value").unwrap_err();
+        verify_membership(deps.as_ref(), 1, vec![], b"value").unwrap_err();
+    }
+
+    #[test]
+    fn non_membership_is_signed_over_an_empty_value() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+
+        verify_non_membership(deps.as_ref(), 1, proof(SEED, 1, DIVERSIFIER, b"")).unwrap();
+        // a proof of membership does not prove non-membership
+        verify_non_membership(deps.as_ref(), 1, proof(SEED, 1, DIVERSIFIER, b"value")).unwrap_err();
+        verify_non_membership(deps.as_ref(), 1, proof(SEED, 1, "other", b"")).unwrap_err();
+        verify_non_membership(deps.as_ref(), 1, proof(SEED, 2, DIVERSIFIER, b"")).unwrap_err();
+    }
+
+    #[test]
+    fn update_state_rotates_the_key_and_advances_the_sequence() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+
+        // the header must be signed by the current key at the current sequence
+        update_state(deps.as_mut(), header(NEW_SEED, 1)).unwrap_err();
+        update_state(deps.as_mut(), header(SEED, 2)).unwrap_err();
+        // misbehaviour is only accepted by update state on misbehaviour
+        update_state(deps.as_mut(), misbehaviour(b"one", b"two")).unwrap_err();
+
+        update_state(deps.as_mut(), header(SEED, 1)).unwrap();
+        assert_eq!(latest_height(deps.as_ref()), height(2));
+        // the header cannot be replayed
+        update_state(deps.as_mut(), header(SEED, 1)).unwrap_err();
+
+        // proofs are signed by the new key with the new diversifier at the new sequence
+        let old = proof(SEED, 1, DIVERSIFIER, b"value");
+        verify_membership(deps.as_ref(), 1, old, b"value").unwrap_err();
+        let new = proof(NEW_SEED, 2, "new-diversifier", b"value");
+        verify_membership(deps.as_ref(), 2, new, b"value").unwrap();
+    }
+
+    #[test]
+    fn update_state_on_misbehaviour_freezes_the_client() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+
+        let check_for_misbehaviour = |deps: Deps, client_message: Binary| {
+            let msg = QueryMsg::CheckForMisbehaviour(CheckForMisbehaviourMsgRaw { client_message });
+            cosmwasm_std::from_json::<query_responses::CheckForMisbehaviour>(
+                query(deps, mock_env(), msg).unwrap(),
+            )
+            .unwrap()
+            .found_misbehaviour
+        };
+        assert!(!check_for_misbehaviour(deps.as_ref(), header(SEED, 1)));
+        assert!(!check_for_misbehaviour(
+            deps.as_ref(),
+            misbehaviour(b"one", b"one")
+        ));
+        assert!(check_for_misbehaviour(
+            deps.as_ref(),
+            misbehaviour(b"one", b"two")
+        ));
+
+        // headers are only accepted by update state
+        update_state_on_misbehaviour(deps.as_mut(), header(SEED, 1)).unwrap_err();
+        // the same value signed twice is not misbehaviour
+        update_state_on_misbehaviour(deps.as_mut(), misbehaviour(b"one", b"one")).unwrap_err();
+        assert_eq!(status(deps.as_ref()), keys::STATUS_ACTIVE);
+
+        // membership and non-membership of the same path
+        update_state_on_misbehaviour(deps.as_mut(), misbehaviour(b"one", b"")).unwrap();
+        assert_eq!(status(deps.as_ref()), keys::STATUS_FROZEN);
+
+        let valid = proof(SEED, 1, DIVERSIFIER, b"value");
+        verify_membership(deps.as_ref(), 1, valid, b"value").unwrap_err();
+        update_state(deps.as_mut(), header(SEED, 1)).unwrap_err();
+    }
+}
@@ -0,0 +1,28 @@
+//! # Keys
+//!
+//! Contains key constants definitions for the contract such as protobuf type urls.
+
+/// `CLIENT_STATE_KEY` is the storage key of the client state.
+pub const CLIENT_STATE_KEY: &str = "clientState";
+
+/// `CLIENT_STATE_TYPE_URL` is the type url of the solo machine client state.
+pub const CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
+/// `CONSENSUS_STATE_TYPE_URL` is the type url of the solo machine consensus state.
+pub const CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ConsensusState";
+/// `HEADER_TYPE_URL` is the type url of the solo machine header.
+pub const HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
+/// `MISBEHAVIOUR_TYPE_URL` is the type url of the solo machine misbehaviour.
+pub const MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Misbehaviour";
+
+/// `SECP256K1_PUBKEY_TYPE_URL` is the type url of a cosmos-sdk secp256k1 public key.
+pub const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+/// `ED25519_PUBKEY_TYPE_URL` is the type url of a cosmos-sdk ed25519 public key.
+pub const ED25519_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// `SENTINEL_HEADER_PATH` is the path signed over in a solo machine header.
+pub const SENTINEL_HEADER_PATH: &str = "solomachine:header";
+
+/// `STATUS_ACTIVE` is the status of a client that is not frozen.
+pub const STATUS_ACTIVE: &str = "Active";
+/// `STATUS_FROZEN` is the status of a frozen client.
+pub const STATUS_FROZEN: &str = "Frozen";
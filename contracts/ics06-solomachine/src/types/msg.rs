@@ -0,0 +1,5 @@
+//! # Messages
+//!
+//! This module defines the messages that this contract receives.
+
+pub use cw_ibc_lite_shared::types::clients::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -0,0 +1,112 @@
+//! This module defines the state storage of the Contract.
+
+use cosmwasm_std::Storage;
+use ibc_proto::{
+    google::protobuf::Any,
+    ibc::lightclients::solomachine::v3::{ClientState, ConsensusState},
+};
+use prost::Message;
+
+use cw_ibc_lite_shared::types::{
+    clients::error::LightClientError, error::ContractError, ibc::Height, storage::PureItem,
+};
+
+use super::keys;
+
+/// Loads the client state from the store.
+///
+/// # Errors
+/// Returns an error if the client state is not found or cannot be decoded.
+pub fn load_client_state(storage: &dyn Storage) -> Result<ClientState, ContractError> {
+    let client_state_any = PureItem::new(keys::CLIENT_STATE_KEY).load(storage)?;
+    decode_any(&client_state_any, keys::CLIENT_STATE_TYPE_URL)
+}
+
+/// Saves the client state to the store as a protobuf encoded [`Any`].
+pub fn save_client_state(storage: &mut dyn Storage, client_state: &ClientState) {
+    let client_state_any = Any {
+        type_url: keys::CLIENT_STATE_TYPE_URL.to_string(),
+        value: client_state.encode_to_vec(),
+    };
+    PureItem::new(keys::CLIENT_STATE_KEY).save(storage, &client_state_any.encode_to_vec());
+}
+
+/// Decodes a protobuf encoded [`Any`] with the given type url into `M`.
+///
+/// # Errors
+/// Returns an error if the bytes cannot be decoded or if the type url does not match.
+pub fn decode_any<M: Message + Default>(bz: &[u8], type_url: &str) -> Result<M, ContractError> {
+    let any = Any::decode(bz)?;
+    if any.type_url != type_url {
+        return Err(LightClientError::unexpected_type_url(type_url, any.type_url).into());
+    }
+
+    Ok(M::decode(any.value.as_slice())?)
+}
+
+/// Contains helpers for the solo machine client state.
+pub mod helpers {
+    use super::{ClientState, ConsensusState, ContractError, Height, LightClientError};
+
+    /// Returns the latest height of the client. The revision height is the client sequence.
+    #[must_use]
+    pub const fn latest_height(client_state: &ClientState) -> Height {
+        Height {
+            revision_number: 0,
+            revision_height: client_state.sequence,
+        }
+    }
+
+    /// Returns the consensus state of the client.
+    ///
+    /// # Errors
+    /// Returns an error if the client state has no consensus state.
+    pub fn consensus_state(client_state: &ClientState) -> Result<&ConsensusState, ContractError> {
+        client_state.consensus_state.as_ref().ok_or_else(|| {
+            LightClientError::InvalidClientState("consensus state cannot be empty".to_string())
+                .into()
+        })
+    }
+
+    /// Validates the client state and its consensus state.
+    ///
+    /// # Errors
+    /// Returns an error if the client state or the consensus state is invalid.
+    pub fn validate(client_state: &ClientState) -> Result<(), ContractError> {
+        if client_state.sequence == 0 {
+            return Err(
+                LightClientError::InvalidClientState("sequence cannot be 0".to_string()).into(),
+            );
+        }
+
+        validate_consensus_state(consensus_state(client_state)?)
+    }
+
+    /// Validates the consensus state.
+    ///
+    /// # Errors
+    /// Returns an error if the consensus state is invalid.
+    pub fn validate_consensus_state(consensus_state: &ConsensusState) -> Result<(), ContractError> {
+        if consensus_state.timestamp == 0 {
+            return Err(LightClientError::InvalidConsensusState(
+                "timestamp cannot be 0".to_string(),
+            )
+            .into());
+        }
+        if !consensus_state.diversifier.is_empty() && consensus_state.diversifier.trim().is_empty()
+        {
+            return Err(LightClientError::InvalidConsensusState(
+                "diversifier cannot contain only spaces".to_string(),
+            )
+            .into());
+        }
+        if consensus_state.public_key.is_none() {
+            return Err(LightClientError::InvalidConsensusState(
+                "public key cannot be empty".to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
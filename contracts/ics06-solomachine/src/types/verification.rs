@@ -0,0 +1,476 @@
+//! This module contains the solo machine signature verification logic.
+
+use cosmwasm_std::Api;
+use ibc_proto::{
+    cosmos::{
+        crypto::{ed25519, secp256k1},
+        tx::signing::v1beta1::signature_descriptor::{data, Data as SignatureData},
+    },
+    google::protobuf::Any,
+    ibc::lightclients::solomachine::v3::{
+        ClientState, Header, HeaderData, Misbehaviour, SignBytes, SignatureAndData,
+    },
+};
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+
+use super::{keys, state};
+
+/// A decoded solo machine client message.
+pub enum ClientMessage {
+    /// A header updating the public key and diversifier of the solo machine.
+    Header(Header),
+    /// Two conflicting signatures at the same sequence.
+    Misbehaviour(Misbehaviour),
+}
+
+impl ClientMessage {
+    /// Decodes a protobuf encoded [`Any`] into a [`ClientMessage`].
+    ///
+    /// # Errors
+    /// Returns an error if the bytes cannot be decoded or if the type url is unknown.
+    pub fn decode(bz: &[u8]) -> Result<Self, ContractError> {
+        let any = Any::decode(bz)?;
+        match any.type_url.as_str() {
+            keys::HEADER_TYPE_URL => Ok(Self::Header(Header::decode(any.value.as_slice())?)),
+            keys::MISBEHAVIOUR_TYPE_URL => Ok(Self::Misbehaviour(Misbehaviour::decode(
+                any.value.as_slice(),
+            )?)),
+            _ => Err(LightClientError::unexpected_type_url(
+                format!(
+                    "{} or {}",
+                    keys::HEADER_TYPE_URL,
+                    keys::MISBEHAVIOUR_TYPE_URL
+                ),
+                any.type_url,
+            )
+            .into()),
+        }
+    }
+}
+
+/// Returns the protobuf encoded [`SignBytes`] signed by the solo machine.
+#[must_use]
+pub fn sign_bytes(
+    sequence: u64,
+    timestamp: u64,
+    diversifier: String,
+    path: Vec<u8>,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    SignBytes {
+        sequence,
+        timestamp,
+        diversifier,
+        path,
+        data,
+    }
+    .encode_to_vec()
+}
+
+/// Returns the bytes of the merkle path as signed by the solo machine. This matches the string
+/// representation of a merkle path in ibc-go, i.e. each key is url path escaped and prefixed
+/// with a `/`.
+#[must_use]
+pub fn merkle_path_bytes(key_path: &[String]) -> Vec<u8> {
+    key_path
+        .iter()
+        .fold(String::new(), |mut path, key| {
+            path.push('/');
+            path.push_str(&path_escape(key));
+            path
+        })
+        .into_bytes()
+}
+
+/// Escapes a string so that it can be used as a single url path segment.
+fn path_escape(segment: &str) -> String {
+    segment.bytes().fold(String::new(), |mut escaped, byte| {
+        if byte.is_ascii_alphanumeric() || b"-_.~$&+:=@".contains(&byte) {
+            escaped.push(char::from(byte));
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+        escaped
+    })
+}
+
+/// Verifies that `signature` is a valid signature of `sign_bytes` by `public_key`.
+/// The signature must be a protobuf encoded single signature data.
+///
+/// # Errors
+/// Returns an error if the public key or signature is unsupported, or if the signature is
+/// invalid.
+pub fn verify_signature(
+    api: &dyn Api,
+    public_key: &Any,
+    sign_bytes: &[u8],
+    signature: &[u8],
+) -> Result<(), ContractError> {
+    let signature = match SignatureData::decode(signature)?.sum {
+        Some(data::Sum::Single(single)) => single.signature,
+        _ => return Err(LightClientError::UnsupportedSignatureData.into()),
+    };
+
+    let is_valid = match public_key.type_url.as_str() {
+        keys::SECP256K1_PUBKEY_TYPE_URL => {
+            let public_key = secp256k1::PubKey::decode(public_key.value.as_slice())?;
+            let message_hash = Sha256::digest(sign_bytes);
+            api.secp256k1_verify(&message_hash, &signature, &public_key.key)?
+        }
+        keys::ED25519_PUBKEY_TYPE_URL => {
+            let public_key = ed25519::PubKey::decode(public_key.value.as_slice())?;
+            api.ed25519_verify(sign_bytes, &signature, &public_key.key)?
+        }
+        type_url => return Err(LightClientError::UnsupportedPublicKey(type_url.to_string()).into()),
+    };
+
+    if !is_valid {
+        return Err(LightClientError::InvalidSignature.into());
+    }
+
+    Ok(())
+}
+
+/// Verifies that the header is signed by the current public key over the new public key and
+/// diversifier at the current sequence.
+///
+/// # Errors
+/// Returns an error if the header is invalid.
+pub fn verify_header(
+    api: &dyn Api,
+    client_state: &ClientState,
+    header: &Header,
+) -> Result<(), ContractError> {
+    if header.timestamp == 0 {
+        return Err(LightClientError::InvalidHeader("timestamp cannot be 0".to_string()).into());
+    }
+    if header.signature.is_empty() {
+        return Err(
+            LightClientError::InvalidHeader("signature cannot be empty".to_string()).into(),
+        );
+    }
+    if !header.new_diversifier.is_empty() && header.new_diversifier.trim().is_empty() {
+        return Err(LightClientError::InvalidHeader(
+            "diversifier cannot contain only spaces".to_string(),
+        )
+        .into());
+    }
+    let Some(new_public_key) = &header.new_public_key else {
+        return Err(
+            LightClientError::InvalidHeader("new public key cannot be empty".to_string()).into(),
+        );
+    };
+
+    let consensus_state = state::helpers::consensus_state(client_state)?;
+    if header.timestamp < consensus_state.timestamp {
+        return Err(LightClientError::InvalidHeader(format!(
+            "header timestamp {} is less than the consensus state timestamp {}",
+            header.timestamp, consensus_state.timestamp
+        ))
+        .into());
+    }
+
+    let header_data = HeaderData {
+        new_pub_key: Some(new_public_key.clone()),
+        new_diversifier: header.new_diversifier.clone(),
+    };
+    let sign_bytes = sign_bytes(
+        client_state.sequence,
+        header.timestamp,
+        consensus_state.diversifier.clone(),
+        keys::SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+        header_data.encode_to_vec(),
+    );
+
+    verify_signature(
+        api,
+        public_key(client_state)?,
+        &sign_bytes,
+        &header.signature,
+    )
+}
+
+/// Verifies that both signatures of the misbehaviour are valid proof signatures by the current
+/// public key at the misbehaviour sequence, and that they sign conflicting data over the same
+/// path, i.e. different values, or a value and non-membership.
+///
+/// NOTE: Membership proofs are verified without incrementing the sequence, so many valid proofs
+/// share a sequence. Proofs over different paths do not conflict, so they cannot be used to
+/// freeze the client. The solo machine must advance its sequence with a header before it signs
+/// a new value at a path it has already signed over.
+///
+/// # Errors
+/// Returns an error if the misbehaviour is invalid.
+pub fn verify_misbehaviour(
+    api: &dyn Api,
+    client_state: &ClientState,
+    misbehaviour: &Misbehaviour,
+) -> Result<(), ContractError> {
+    if misbehaviour.sequence == 0 {
+        return Err(
+            LightClientError::InvalidMisbehaviour("sequence cannot be 0".to_string()).into(),
+        );
+    }
+    let (Some(signature_one), Some(signature_two)) =
+        (&misbehaviour.signature_one, &misbehaviour.signature_two)
+    else {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "signatures cannot be empty".to_string(),
+        )
+        .into());
+    };
+    if signature_one.signature == signature_two.signature {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "signatures cannot be equal".to_string(),
+        )
+        .into());
+    }
+    if signature_one.path != signature_two.path {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "signatures must be over the same path".to_string(),
+        )
+        .into());
+    }
+    if signature_one.data == signature_two.data {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "signed data cannot be equal".to_string(),
+        )
+        .into());
+    }
+
+    // NOTE: The misbehaviour timestamp is not checked to allow processing past misbehaviour.
+    verify_signature_and_data(api, client_state, misbehaviour.sequence, signature_one)?;
+    verify_signature_and_data(api, client_state, misbehaviour.sequence, signature_two)
+}
+
+/// Verifies a single signature of a misbehaviour, which signs the same bytes as a membership
+/// proof of its data at its path.
+fn verify_signature_and_data(
+    api: &dyn Api,
+    client_state: &ClientState,
+    sequence: u64,
+    signature_and_data: &SignatureAndData,
+) -> Result<(), ContractError> {
+    if signature_and_data.signature.is_empty() || signature_and_data.path.is_empty() {
+        return Err(LightClientError::InvalidMisbehaviour(
+            "signature and path cannot be empty".to_string(),
+        )
+        .into());
+    }
+    if signature_and_data.timestamp == 0 {
+        return Err(
+            LightClientError::InvalidMisbehaviour("timestamp cannot be 0".to_string()).into(),
+        );
+    }
+
+    let consensus_state = state::helpers::consensus_state(client_state)?;
+    let sign_bytes = sign_bytes(
+        sequence,
+        signature_and_data.timestamp,
+        consensus_state.diversifier.clone(),
+        signature_and_data.path.clone(),
+        signature_and_data.data.clone(),
+    );
+
+    verify_signature(
+        api,
+        public_key(client_state)?,
+        &sign_bytes,
+        &signature_and_data.signature,
+    )
+}
+
+/// Returns the current public key of the solo machine.
+fn public_key(client_state: &ClientState) -> Result<&Any, ContractError> {
+    state::helpers::consensus_state(client_state)?
+        .public_key
+        .as_ref()
+        .ok_or_else(|| {
+            LightClientError::InvalidConsensusState("public key cannot be empty".to_string()).into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockApi;
+    use ed25519_zebra::{SigningKey, VerificationKey};
+    use ibc_proto::{
+        cosmos::tx::signing::v1beta1::signature_descriptor::data::{Multi, Single},
+        ibc::lightclients::solomachine::v3::ConsensusState,
+    };
+
+    use super::{
+        data, ed25519, keys, merkle_path_bytes, path_escape, sign_bytes, verify_misbehaviour,
+        verify_signature, Any, ClientState, Message, Misbehaviour, SignBytes, SignatureAndData,
+        SignatureData,
+    };
+
+    const SEED: [u8; 32] = [7; 32];
+    const SEQUENCE: u64 = 5;
+    const DIVERSIFIER: &str = "diversifier";
+
+    fn public_key() -> Any {
+        let key = <[u8; 32]>::from(VerificationKey::from(&SigningKey::from(SEED)));
+        Any {
+            type_url: keys::ED25519_PUBKEY_TYPE_URL.to_string(),
+            value: ed25519::PubKey { key: key.to_vec() }.encode_to_vec(),
+        }
+    }
+
+    /// Signs the message with the key of [`public_key`], as single signature data.
+    fn sign(message: &[u8]) -> Vec<u8> {
+        let signature = <[u8; 64]>::from(SigningKey::from(SEED).sign(message));
+        SignatureData {
+            sum: Some(data::Sum::Single(Single {
+                mode: 0,
+                signature: signature.to_vec(),
+            })),
+        }
+        .encode_to_vec()
+    }
+
+    fn client_state() -> ClientState {
+        ClientState {
+            sequence: SEQUENCE,
+            is_frozen: false,
+            consensus_state: Some(ConsensusState {
+                public_key: Some(public_key()),
+                diversifier: DIVERSIFIER.to_string(),
+                timestamp: 100,
+            }),
+        }
+    }
+
+    /// Signs the data at the path, with the sign bytes of a membership proof.
+    fn signature_and_data(path: &[u8], data: &[u8]) -> SignatureAndData {
+        let bytes = sign_bytes(
+            SEQUENCE,
+            200,
+            DIVERSIFIER.to_string(),
+            path.to_vec(),
+            data.to_vec(),
+        );
+        SignatureAndData {
+            signature: sign(&bytes),
+            path: path.to_vec(),
+            data: data.to_vec(),
+            timestamp: 200,
+        }
+    }
+
+    #[test]
+    fn sign_bytes_are_protobuf_encoded() {
+        let bytes = sign_bytes(
+            1,
+            2,
+            DIVERSIFIER.to_string(),
+            b"/path".to_vec(),
+            b"value".to_vec(),
+        );
+        assert_eq!(
+            SignBytes::decode(bytes.as_slice()).unwrap(),
+            SignBytes {
+                sequence: 1,
+                timestamp: 2,
+                diversifier: DIVERSIFIER.to_string(),
+                path: b"/path".to_vec(),
+                data: b"value".to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn merkle_path_keys_are_escaped() {
+        let key_path = [
+            "ibc".to_string(),
+            "commitments/ports/transfer/channels/channel-0/sequences/1".to_string(),
+        ];
+        assert_eq!(
+            merkle_path_bytes(&key_path),
+            b"/ibc/commitments%2Fports%2Ftransfer%2Fchannels%2Fchannel-0%2Fsequences%2F1"
+        );
+        assert!(merkle_path_bytes(&[]).is_empty());
+
+        assert_eq!(path_escape("aZ09-_.~$&+:=@"), "aZ09-_.~$&+:=@");
+        assert_eq!(path_escape("a b?#%é"), "a%20b%3F%23%25%C3%A9");
+    }
+
+    #[test]
+    fn signatures_are_verified() {
+        let api = MockApi::default();
+        verify_signature(&api, &public_key(), b"message", &sign(b"message")).unwrap();
+        verify_signature(&api, &public_key(), b"other", &sign(b"message")).unwrap_err();
+
+        let multi = SignatureData {
+            sum: Some(data::Sum::Multi(Multi::default())),
+        };
+        verify_signature(&api, &public_key(), b"message", &multi.encode_to_vec()).unwrap_err();
+
+        let unsupported = Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            ..public_key()
+        };
+        verify_signature(&api, &unsupported, b"message", &sign(b"message")).unwrap_err();
+    }
+
+    #[test]
+    fn conflicting_proofs_are_misbehaviour() {
+        let api = MockApi::default();
+        let path = |key: &str| merkle_path_bytes(&["ibc".to_string(), key.to_string()]);
+        let misbehaviour = |one: SignatureAndData, two: SignatureAndData| Misbehaviour {
+            sequence: SEQUENCE,
+            signature_one: Some(one),
+            signature_two: Some(two),
+        };
+
+        // different values at the same path
+        let equivocation = misbehaviour(
+            signature_and_data(&path("a"), b"one"),
+            signature_and_data(&path("a"), b"two"),
+        );
+        verify_misbehaviour(&api, &client_state(), &equivocation).unwrap();
+
+        // membership and non-membership at the same path
+        let membership = misbehaviour(
+            signature_and_data(&path("a"), b"one"),
+            signature_and_data(&path("a"), b""),
+        );
+        verify_misbehaviour(&api, &client_state(), &membership).unwrap();
+
+        // valid proofs at different paths do not conflict
+        let different_paths = misbehaviour(
+            signature_and_data(&path("a"), b"one"),
+            signature_and_data(&path("b"), b"two"),
+        );
+        verify_misbehaviour(&api, &client_state(), &different_paths).unwrap_err();
+
+        // the same value signed at different timestamps does not conflict
+        let mut later = signature_and_data(&path("a"), b"one");
+        later.timestamp = 300;
+        later.signature = sign(&sign_bytes(
+            SEQUENCE,
+            300,
+            DIVERSIFIER.to_string(),
+            path("a"),
+            b"one".to_vec(),
+        ));
+        let same_data = misbehaviour(signature_and_data(&path("a"), b"one"), later);
+        verify_misbehaviour(&api, &client_state(), &same_data).unwrap_err();
+
+        // a signature at another sequence is not valid
+        let stale = Misbehaviour {
+            sequence: SEQUENCE + 1,
+            ..equivocation.clone()
+        };
+        verify_misbehaviour(&api, &client_state(), &stale).unwrap_err();
+
+        // a forged signature is not valid
+        let mut forged = signature_and_data(&path("a"), b"two");
+        forged.data = b"three".to_vec();
+        let forged = misbehaviour(signature_and_data(&path("a"), b"one"), forged);
+        verify_misbehaviour(&api, &client_state(), &forged).unwrap_err();
+    }
+}
@@ -4,7 +4,8 @@ use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
 use cw_ibc_lite_shared::types::error::ContractError;
 
 use crate::types::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    keys,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state,
 };
 
@@ -23,6 +24,7 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, keys::CONTRACT_VERSION)?;
     let mut ctx = TendermintContext::new_mut(deps, env)?;
     let data = ctx.instantiate(msg.into())?;
 
@@ -42,10 +44,18 @@ pub fn execute(
     _info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    let mut ctx = TendermintContext::new_mut(deps, env)?;
-    let data = ctx.sudo(msg.into())?;
+    match msg {
+        ExecuteMsg::PruneExpiredConsensusStates { limit } => {
+            execute::prune_expired_consensus_states(deps, env, limit)
+        }
+        ExecuteMsg::UpdateState(_) => execute::update_state(deps, env, msg.try_into()?),
+        ExecuteMsg::UpdateStateOnMisbehaviour(_) | ExecuteMsg::VerifyUpgradeAndUpdateState(_) => {
+            let mut ctx = TendermintContext::new_mut(deps, env)?;
+            let data = ctx.sudo(msg.try_into()?)?;
 
-    Ok(Response::new().set_data(data))
+            Ok(Response::new().set_data(data))
+        }
+    }
 }
 
 /// Handles the query messages by routing them to the respective handlers.
@@ -57,8 +67,8 @@ pub fn execute(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     // NOTE: [`QueryMsg::VerifyMembership`] and [`QueryMsg::VerifyNonMembership`] are handled by
     // the sudo handler in ibc_client_cw. This is beacuse initial ibc specifications required this.
-    // In cw-ibc-lite, we moved these messages to the query handler, this is why we need to handle
-    // verify membership and non-membership queries in a different way.
+    // In cw-ibc-lite, we moved these messages to the query handler, so they are verified here
+    // against a read-only context instead.
     match msg {
         QueryMsg::Status(_) => query::status(deps, env, msg.try_into()?),
         QueryMsg::ExportMetadata(_) => query::export_metadata(deps, env, msg.try_into()?),
@@ -70,19 +80,149 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             query::check_for_misbehaviour(deps, env, msg.try_into()?)
         }
         QueryMsg::VerifyMembership(_) | QueryMsg::VerifyNonMembership(_) => {
-            query::sudo_query(deps, env, msg.try_into()?)
+            query::verify_membership(deps, env, msg.try_into()?)
+        }
+        QueryMsg::ClientState {} => query::client_state(deps, env),
+        QueryMsg::ConsensusState { height } => query::consensus_state(deps, env, height),
+        QueryMsg::LatestHeight {} => query::latest_height(deps, env),
+        QueryMsg::ConsensusHeights { start_after, limit } => {
+            query::consensus_heights(deps, start_after, limit)
         }
     }
 }
 
+/// Migrates the contract to this version. Refuses to migrate from another contract or from a
+/// newer version of this one. Clients instantiated before the version was recorded are assumed
+/// to be older versions of this contract.
+///
+/// # Errors
+/// Will return an error if the recorded contract name or version does not allow the migration.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    if cw2::CONTRACT.may_load(deps.storage)?.is_none() {
+        cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, keys::CONTRACT_VERSION)?;
+        return Ok(Response::default());
+    }
+
+    // NOTE: State migrations from the returned previous version are to be run here once the
+    // storage layout changes.
+    cw_ibc_lite_shared::utils::migrate::ensure_from_older_version(
+        deps.storage,
+        keys::CONTRACT_NAME,
+        keys::CONTRACT_VERSION,
+    )?;
+
+    Ok(Response::default())
+}
+
+mod execute {
+    use ibc_client_cw::types::{ContractError as TendermintContractError, SudoMsg};
+    use ibc_core_client::context::{ClientExecutionContext, ClientValidationContext};
+    use ibc_core_client::types::Height;
+    use ibc_core_host::types::path::ClientConsensusStatePath;
+
+    use super::{state, ContractError, DepsMut, Env, Response, TendermintContext};
+
+    /// The maximum number of expired consensus states pruned after each update.
+    const MAX_PRUNE_ON_UPDATE: usize = 5;
+    /// Default limit for [`crate::types::msg::ExecuteMsg::PruneExpiredConsensusStates`]
+    const DEFAULT_PRUNE_LIMIT: u32 = 10;
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state(
+        mut deps: DepsMut,
+        env: Env,
+        msg: SudoMsg,
+    ) -> Result<Response, ContractError> {
+        let data = TendermintContext::new_mut(deps.branch(), env.clone())?.sudo(msg)?;
+        let pruned = prune(deps, env, MAX_PRUNE_ON_UPDATE)?;
+
+        Ok(Response::new()
+            .set_data(data)
+            .add_attribute("pruned_consensus_states", pruned.to_string()))
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn prune_expired_consensus_states(
+        deps: DepsMut,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT) as usize;
+        let pruned = prune(deps, env, limit)?;
+
+        Ok(Response::new().add_attribute("pruned_consensus_states", pruned.to_string()))
+    }
+
+    /// Deletes up to `limit` consensus states, oldest first, whose trusting period has passed
+    /// at the block time. Walks the consensus states in ascending order, reading at most
+    /// `limit + 1` of them since the latest one is skipped, and stops at the first consensus
+    /// state that is still within the trusting period. Returns the number of pruned consensus
+    /// states.
+    fn prune(deps: DepsMut, env: Env, limit: usize) -> Result<usize, ContractError> {
+        let now = i128::from(env.block.time.nanos());
+        let heights = state::consensus_heights(deps.storage, None, limit.saturating_add(1))?;
+
+        let mut ctx = TendermintContext::new_mut(deps, env)?;
+        let client_id = ctx.client_id();
+        let client_state = ctx
+            .client_state(&client_id)
+            .map_err(TendermintContractError::from)?;
+        let latest_height = client_state.inner().latest_height;
+        let trusting_period =
+            i128::try_from(client_state.inner().trusting_period.as_nanos()).unwrap_or(i128::MAX);
+
+        let mut pruned = 0;
+        for height in heights
+            .into_iter()
+            .map(Height::from)
+            .filter(|height| *height != latest_height)
+            .take(limit)
+        {
+            let path = ClientConsensusStatePath::new(
+                client_id.clone(),
+                height.revision_number(),
+                height.revision_height(),
+            );
+            let consensus_state = ctx
+                .consensus_state(&path)
+                .map_err(TendermintContractError::from)?;
+            let expires_at = consensus_state
+                .inner()
+                .timestamp
+                .unix_timestamp_nanos()
+                .saturating_add(trusting_period);
+            if expires_at > now {
+                break;
+            }
+
+            ctx.delete_consensus_state(path)
+                .map_err(TendermintContractError::from)?;
+            ctx.delete_update_meta(client_id.clone(), height)
+                .map_err(TendermintContractError::from)?;
+            pruned += 1;
+        }
+
+        Ok(pruned)
+    }
+}
+
 mod query {
     use cw_ibc_lite_shared::types::{
-        clients::msg::query_responses, storage::mock_mut::MockMutStorage,
+        clients::msg::{query_responses, VerifyMembershipQuery},
+        ibc::Height,
     };
 
-    use ibc_client_cw::types::{QueryMsg as TendermintQueryMsg, QueryResponse};
+    use ibc_client_cw::types::{
+        ContractError as TendermintContractError, ContractResult, QueryMsg as TendermintQueryMsg,
+        QueryResponse, VerifyMembershipMsg, VerifyMembershipMsgRaw, VerifyNonMembershipMsg,
+        VerifyNonMembershipMsgRaw,
+    };
+    use ibc_core_client::context::{client_state::ClientStateCommon, ClientValidationContext};
+    use ibc_core_host::types::path::ClientConsensusStatePath;
 
-    use super::{Binary, ContractError, Deps, Env, TendermintContext};
+    use super::{state, Binary, ContractError, Deps, Env, TendermintContext};
 
     pub fn status(deps: Deps, env: Env, msg: TendermintQueryMsg) -> Result<Binary, ContractError> {
         tendermint_query(deps, env, msg)
@@ -130,6 +270,79 @@ mod query {
             .and_then(|qr| cosmwasm_std::to_json_binary(&qr).map_err(ContractError::from))
     }
 
+    pub fn client_state(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        let ctx = TendermintContext::new_ref(deps, env)?;
+        let client_state = ctx
+            .client_state(&ctx.client_id())
+            .map_err(TendermintContractError::from)?;
+        let client_state = client_state.inner();
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ClientState {
+                latest_height: client_state.latest_height.into(),
+                frozen_height: client_state.frozen_height.map(Into::into),
+                chain_id: Some(client_state.chain_id.to_string()),
+                trusting_period: Some(client_state.trusting_period.as_secs()),
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_state(deps: Deps, env: Env, height: Height) -> Result<Binary, ContractError> {
+        let ctx = TendermintContext::new_ref(deps, env)?;
+        let path = ClientConsensusStatePath::new(
+            ctx.client_id(),
+            height.revision_number,
+            height.revision_height,
+        );
+        let consensus_state = ctx
+            .consensus_state(&path)
+            .map_err(TendermintContractError::from)?;
+        let consensus_state = consensus_state.inner();
+
+        let timestamp = u64::try_from(consensus_state.timestamp.unix_timestamp_nanos())
+            .map_err(|_| ContractError::try_from::<i128, u64>())?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusState {
+                timestamp,
+                root: consensus_state.root.as_bytes().into(),
+            },
+        )?)
+    }
+
+    pub fn latest_height(deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        let ctx = TendermintContext::new_ref(deps, env)?;
+        let client_state = ctx
+            .client_state(&ctx.client_id())
+            .map_err(TendermintContractError::from)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::LatestHeight {
+                height: client_state.inner().latest_height.into(),
+            },
+        )?)
+    }
+
+    /// Default limit for pagination
+    const DEFAULT_LIMIT: u32 = 10;
+    /// Maximum limit for pagination
+    const MAX_LIMIT: u32 = 100;
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_heights(
+        deps: Deps,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let heights = state::consensus_heights(deps.storage, start_after.as_ref(), limit)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusHeights { heights },
+        )?)
+    }
+
     fn tendermint_query(
         deps: Deps,
         env: Env,
@@ -139,16 +352,388 @@ mod query {
         cosmwasm_std::from_json(ctx.query(msg)?).map_err(ContractError::from)
     }
 
-    #[allow(clippy::needless_pass_by_value, clippy::module_name_repetitions)]
-    pub fn sudo_query(
+    /// Verifies (non-)membership against a read-only context. Any storage write attempted during
+    /// verification fails since the context has no mutable storage.
+    pub fn verify_membership(
         deps: Deps,
         env: Env,
-        msg: ibc_client_cw::types::SudoMsg,
+        query: VerifyMembershipQuery,
     ) -> Result<Binary, ContractError> {
-        let mut storage_mut = MockMutStorage::new(deps.storage);
-        let deps_mut = storage_mut.to_deps_mut(deps.api, &deps.querier);
-        let mut ctx = TendermintContext::new_mut(deps_mut, env)?;
+        let ctx = TendermintContext::new_ref(deps, env)?;
+        let client_state = ctx
+            .client_state(&ctx.client_id())
+            .map_err(TendermintContractError::from)?;
+        let consensus_state = ctx
+            .consensus_state(&ClientConsensusStatePath::new(
+                ctx.client_id(),
+                query.height.revision_number,
+                query.height.revision_height,
+            ))
+            .map_err(TendermintContractError::from)?;
+        let root = &consensus_state.inner().root;
+
+        let verified = if query.value.is_some() {
+            let msg = VerifyMembershipMsg::try_from(VerifyMembershipMsgRaw::try_from(query)?)?;
+            client_state.verify_membership(&msg.prefix, &msg.proof, root, msg.path, msg.value)
+        } else {
+            let msg =
+                VerifyNonMembershipMsg::try_from(VerifyNonMembershipMsgRaw::try_from(query)?)?;
+            client_state.verify_non_membership(&msg.prefix, &msg.proof, root, msg.path)
+        };
+        verified.map_err(TendermintContractError::from)?;
+
+        Ok(cosmwasm_std::to_json_binary(&ContractResult::success())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Binary, Deps, DepsMut, Storage, Timestamp,
+    };
+    use ibc_client_cw::types::{
+        MerklePath, StatusMsg, VerifyMembershipMsgRaw, VerifyNonMembershipMsgRaw,
+    };
+    use ibc_client_tendermint::{
+        consensus_state::ConsensusState,
+        types::{TENDERMINT_CLIENT_STATE_TYPE_URL, TENDERMINT_CONSENSUS_STATE_TYPE_URL},
+    };
+    use ibc_core_client::context::ClientExecutionContext;
+    use ibc_core_commitment_types::specs::ProofSpecs;
+    use ibc_core_host::types::path::{iteration_key, ClientConsensusStatePath};
+    use ibc_proto::{
+        google::protobuf::{Any, Duration, Timestamp as RawTimestamp},
+        ibc::{
+            core::{client::v1::Height as RawHeight, commitment::v1::MerkleRoot},
+            lightclients::tendermint::v1::{
+                ClientState as RawClientState, ConsensusState as RawConsensusState, Fraction,
+            },
+        },
+    };
+    use prost::Message;
+
+    use cw_ibc_lite_shared::types::{clients::msg::query_responses, ibc::Height};
+
+    use super::{
+        execute, instantiate, keys, migrate, query, ExecuteMsg, InstantiateMsg, MigrateMsg,
+        QueryMsg, TendermintContext,
+    };
+
+    const CHAIN_ID: &str = "testchain-1";
+    /// The trusting period of the test client in seconds.
+    const TRUSTING_PERIOD: u64 = 1_000;
+    const ROOT: &[u8] = b"root";
+
+    const fn height(revision_height: u64) -> Height {
+        Height {
+            revision_number: 1,
+            revision_height,
+        }
+    }
+
+    fn any(type_url: &str, msg: &impl Message) -> Binary {
+        Any {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec(),
+        }
+        .encode_to_vec()
+        .into()
+    }
+
+    fn consensus_state(timestamp: Timestamp) -> Binary {
+        let consensus_state = RawConsensusState {
+            timestamp: Some(RawTimestamp {
+                seconds: i64::try_from(timestamp.seconds()).unwrap(),
+                nanos: 0,
+            }),
+            root: Some(MerkleRoot {
+                hash: ROOT.to_vec(),
+            }),
+            next_validators_hash: vec![0; 32],
+        };
+        any(TENDERMINT_CONSENSUS_STATE_TYPE_URL, &consensus_state)
+    }
+
+    /// Instantiates a client whose only consensus state is at `latest_height`, produced at
+    /// `timestamp`.
+    fn instantiate_client(deps: DepsMut, latest_height: u64, timestamp: Timestamp) {
+        let duration = |seconds: u64| Duration {
+            seconds: i64::try_from(seconds).unwrap(),
+            nanos: 0,
+        };
+        let client_state = RawClientState {
+            chain_id: CHAIN_ID.to_string(),
+            trust_level: Some(Fraction {
+                numerator: 1,
+                denominator: 3,
+            }),
+            trusting_period: Some(duration(TRUSTING_PERIOD)),
+            unbonding_period: Some(duration(2 * TRUSTING_PERIOD)),
+            max_clock_drift: Some(duration(10)),
+            latest_height: Some(height(latest_height).into()),
+            proof_specs: ProofSpecs::cosmos().into(),
+            ..Default::default()
+        };
+        let msg = InstantiateMsg {
+            client_state: any(TENDERMINT_CLIENT_STATE_TYPE_URL, &client_state),
+            consensus_state: consensus_state(timestamp),
+            checksum: Some(Binary::from(vec![1; 32])),
+        };
+
+        instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
+
+    /// Stores the iteration key that ibc-rs keeps for each consensus state.
+    fn store_iteration_key(deps: DepsMut, height: Height) {
+        let key = iteration_key(height.revision_number, height.revision_height);
+        deps.storage
+            .set(&key, &RawHeight::from(height).encode_to_vec());
+    }
+
+    /// Stores a consensus state at the height, produced at `timestamp`.
+    fn store_consensus_state(mut deps: DepsMut, revision_height: u64, timestamp: Timestamp) {
+        let any = Any::decode(consensus_state(timestamp).as_slice()).unwrap();
+        let mut ctx = TendermintContext::new_mut(deps.branch(), mock_env()).unwrap();
+        let path = ClientConsensusStatePath::new(ctx.client_id(), 1, revision_height);
+        ctx.store_consensus_state(path, ConsensusState::try_from(any).unwrap())
+            .unwrap();
+
+        store_iteration_key(deps, height(revision_height));
+    }
+
+    /// Prunes the expired consensus states at the block time of [`mock_env`].
+    fn prune(deps: DepsMut, limit: Option<u32>) -> String {
+        let msg = ExecuteMsg::PruneExpiredConsensusStates { limit };
+        let response = execute(deps, mock_env(), mock_info("anyone", &[]), msg).unwrap();
+        response.attributes[0].value.clone()
+    }
+
+    fn consensus_heights(
+        deps: Deps,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> Vec<Height> {
+        let response = query(
+            deps,
+            mock_env(),
+            QueryMsg::ConsensusHeights { start_after, limit },
+        )
+        .unwrap();
+        cosmwasm_std::from_json::<query_responses::ConsensusHeights>(response)
+            .unwrap()
+            .heights
+    }
+
+    #[test]
+    fn client_and_consensus_state_queries() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time;
+        instantiate_client(deps.as_mut(), 10, now);
+
+        let client_state: query_responses::ClientState = cosmwasm_std::from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::ClientState {}).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            client_state,
+            query_responses::ClientState {
+                latest_height: height(10),
+                frozen_height: None,
+                chain_id: Some(CHAIN_ID.to_string()),
+                trusting_period: Some(TRUSTING_PERIOD),
+            }
+        );
+
+        let latest_height: query_responses::LatestHeight = cosmwasm_std::from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::LatestHeight {}).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(latest_height.height, height(10));
+
+        let consensus_state: query_responses::ConsensusState = cosmwasm_std::from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ConsensusState { height: height(10) },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            consensus_state,
+            query_responses::ConsensusState {
+                timestamp: now.nanos(),
+                root: Binary::from(ROOT),
+            }
+        );
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ConsensusState { height: height(11) },
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn consensus_heights_are_paginated() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut(), 10, mock_env().block.time);
+        for revision_height in [20, 30, 40] {
+            store_iteration_key(deps.as_mut(), height(revision_height));
+        }
+        // keys just outside of the iteration key range are not read
+        deps.storage.set(b"iterateConsensusState", b"");
+        deps.storage.set(b"iterateConsensusStatet", b"");
+
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, None),
+            vec![height(10), height(20), height(30), height(40)]
+        );
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, Some(2)),
+            vec![height(10), height(20)]
+        );
+        assert_eq!(
+            consensus_heights(deps.as_ref(), Some(height(20)), None),
+            vec![height(30), height(40)]
+        );
+        // the start need not be a stored height
+        assert_eq!(
+            consensus_heights(deps.as_ref(), Some(height(25)), Some(1)),
+            vec![height(30)]
+        );
+        assert!(consensus_heights(deps.as_ref(), Some(height(40)), None).is_empty());
+    }
+
+    #[test]
+    fn consensus_heights_limit_is_capped() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut(), 1, mock_env().block.time);
+        for revision_height in 2..=150 {
+            store_iteration_key(deps.as_mut(), height(revision_height));
+        }
+
+        assert_eq!(consensus_heights(deps.as_ref(), None, None).len(), 10);
+        let heights = consensus_heights(deps.as_ref(), None, Some(u32::MAX));
+        assert_eq!(heights.len(), 100);
+        assert_eq!(heights.last(), Some(&height(100)));
+    }
+
+    #[test]
+    fn expired_consensus_states_are_pruned_oldest_first() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time;
+        instantiate_client(deps.as_mut(), 50, now);
+        // the trusting period has passed for all but the consensus state at height 40
+        for (revision_height, age) in [(10, 3_000), (20, 2_500), (30, 2_000), (40, 500)] {
+            store_consensus_state(deps.as_mut(), revision_height, now.minus_seconds(age));
+        }
+
+        assert_eq!(prune(deps.as_mut(), Some(2)), "2");
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, None),
+            vec![height(30), height(40), height(50)]
+        );
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ConsensusState { height: height(10) },
+        )
+        .unwrap_err();
+
+        assert_eq!(prune(deps.as_mut(), None), "1");
+        assert_eq!(prune(deps.as_mut(), None), "0");
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, None),
+            vec![height(40), height(50)]
+        );
+    }
+
+    #[test]
+    fn latest_consensus_state_is_never_pruned() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time;
+        instantiate_client(deps.as_mut(), 10, now.minus_seconds(5_000));
+        store_consensus_state(deps.as_mut(), 5, now.minus_seconds(6_000));
+
+        assert_eq!(prune(deps.as_mut(), Some(0)), "0");
+        assert_eq!(prune(deps.as_mut(), Some(1)), "1");
+        assert_eq!(prune(deps.as_mut(), None), "0");
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, None),
+            vec![height(10)]
+        );
+    }
+
+    #[test]
+    fn invalid_proofs_are_rejected() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut(), 10, mock_env().block.time);
+        let path = MerklePath {
+            key_path: vec![
+                "ibc".to_string(),
+                "commitments/ports/transfer/channels/channel-0/sequences/1".to_string(),
+            ],
+        };
+
+        let membership = VerifyMembershipMsgRaw {
+            proof: b"proof".to_vec(),
+            path: path.clone(),
+            value: b"value".to_vec(),
+            height: height(10).into(),
+            delay_time_period: 0,
+            delay_block_period: 0,
+        };
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyMembership(membership),
+        )
+        .unwrap_err();
+
+        let non_membership = VerifyNonMembershipMsgRaw {
+            proof: b"proof".to_vec(),
+            path,
+            height: height(10).into(),
+            delay_time_period: 0,
+            delay_block_period: 0,
+        };
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyNonMembership(non_membership),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn migrate_keeps_the_states_of_the_previous_version() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time;
+        instantiate_client(deps.as_mut(), 20, now);
+        store_consensus_state(deps.as_mut(), 10, now.minus_seconds(100));
+        // the previous version did not record its version
+        cw2::CONTRACT.remove(&mut deps.storage);
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.contract, keys::CONTRACT_NAME);
+        assert_eq!(version.version, keys::CONTRACT_VERSION);
+        assert_eq!(
+            consensus_heights(deps.as_ref(), None, None),
+            vec![height(10), height(20)]
+        );
+        let status: query_responses::Status = cosmwasm_std::from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Status(StatusMsg {})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(status.status, "Active");
 
-        ctx.sudo(msg).map_err(ContractError::from)
+        // the recorded version is checked from now on
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        cw2::set_contract_version(&mut deps.storage, keys::CONTRACT_NAME, "999.0.0").unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
     }
 }
@@ -2,4 +2,68 @@
 //!
 //! This module defines the messages that this contract receives.
 
-pub use cw_ibc_lite_shared::types::clients::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cosmwasm_schema::cw_serde;
+use ibc_client_cw::types::{
+    SudoMsg, UpdateStateMsgRaw, UpdateStateOnMisbehaviourMsgRaw, VerifyUpgradeAndUpdateStateMsgRaw,
+};
+
+use cw_ibc_lite_shared::types::error::ContractError;
+
+pub use cw_ibc_lite_shared::types::clients::msg::{InstantiateMsg, QueryMsg};
+
+/// The message to migrate the contract.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// The execute messages supported by the contract.
+/// This is a superset of [`cw_ibc_lite_shared::types::clients::msg::ExecuteMsg`].
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Update the client state.
+    /// Also prunes a bounded number of expired consensus states.
+    UpdateState(UpdateStateMsgRaw),
+    /// Update the client state on misbehaviour
+    UpdateStateOnMisbehaviour(UpdateStateOnMisbehaviourMsgRaw),
+    /// Verify upgrade and update the client state
+    VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsgRaw),
+    /// Prune the consensus states that are past the trusting period, starting from the oldest.
+    /// The consensus state at the latest height is never pruned. Can be called by anyone.
+    PruneExpiredConsensusStates {
+        /// The maximum number of consensus states to prune.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+}
+
+impl From<cw_ibc_lite_shared::types::clients::msg::ExecuteMsg> for ExecuteMsg {
+    fn from(msg: cw_ibc_lite_shared::types::clients::msg::ExecuteMsg) -> Self {
+        use cw_ibc_lite_shared::types::clients::msg::ExecuteMsg as SharedExecuteMsg;
+
+        match msg {
+            SharedExecuteMsg::UpdateState(msg) => Self::UpdateState(msg),
+            SharedExecuteMsg::UpdateStateOnMisbehaviour(msg) => {
+                Self::UpdateStateOnMisbehaviour(msg)
+            }
+            SharedExecuteMsg::VerifyUpgradeAndUpdateState(msg) => {
+                Self::VerifyUpgradeAndUpdateState(msg)
+            }
+        }
+    }
+}
+
+impl TryFrom<ExecuteMsg> for SudoMsg {
+    type Error = ContractError;
+
+    fn try_from(msg: ExecuteMsg) -> Result<Self, Self::Error> {
+        match msg {
+            ExecuteMsg::UpdateState(msg) => Ok(Self::UpdateState(msg)),
+            ExecuteMsg::UpdateStateOnMisbehaviour(msg) => Ok(Self::UpdateStateOnMisbehaviour(msg)),
+            ExecuteMsg::VerifyUpgradeAndUpdateState(msg) => {
+                Ok(Self::VerifyUpgradeAndUpdateState(msg))
+            }
+            ExecuteMsg::PruneExpiredConsensusStates { .. } => {
+                Err(ContractError::try_from::<ExecuteMsg, Self>())
+            }
+        }
+    }
+}
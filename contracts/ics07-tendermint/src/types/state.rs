@@ -1,8 +1,12 @@
 //! Exposes the Tendermint client and consensus states.
 
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
 use ibc_client_cw::api::ClientType;
 use ibc_client_tendermint::client_state::ClientState;
 use ibc_client_tendermint::consensus_state::ConsensusState;
+use ibc_core_host::types::path::{iteration_key, ITERATE_CONSENSUS_STATE_PREFIX};
+
+use cw_ibc_lite_shared::types::ibc::Height;
 
 /// Tendermint client type as defined in ibc-rs.
 #[derive(Clone, Debug)]
@@ -12,3 +16,49 @@ impl<'a> ClientType<'a> for TendermintClient {
     type ClientState = ClientState;
     type ConsensusState = ConsensusState;
 }
+
+/// Returns up to `limit` heights of the stored consensus states in ascending order, starting
+/// after `start_after`. The heights are read from the iteration keys that ibc-rs stores with
+/// each consensus state, whose big-endian encoding sorts them by height.
+///
+/// # Errors
+/// Will return an error if an iteration key is malformed.
+pub fn consensus_heights(
+    storage: &dyn Storage,
+    start_after: Option<&Height>,
+    limit: usize,
+) -> StdResult<Vec<Height>> {
+    let prefix = ITERATE_CONSENSUS_STATE_PREFIX.as_bytes();
+    // appending a byte makes the bound exclusive of `start_after`
+    let start = start_after.map_or_else(
+        || prefix.to_vec(),
+        |height| {
+            let mut key = iteration_key(height.revision_number, height.revision_height);
+            key.push(0);
+            key
+        },
+    );
+    let mut end = prefix.to_vec();
+    if let Some(last) = end.last_mut() {
+        *last += 1;
+    }
+
+    storage
+        .range(Some(&start), Some(&end), Order::Ascending)
+        .take(limit)
+        .map(|(key, _)| parse_iteration_key(&key[prefix.len()..]))
+        .collect()
+}
+
+/// Parses the big-endian revision number and height following the iteration key prefix.
+fn parse_iteration_key(suffix: &[u8]) -> StdResult<Height> {
+    let malformed = |_| StdError::generic_err("malformed consensus state iteration key");
+    let bytes = <[u8; 16]>::try_from(suffix).map_err(malformed)?;
+    let (revision_number, revision_height) = bytes.split_at(8);
+    let parse = |bytes: &[u8]| bytes.try_into().map(u64::from_be_bytes).map_err(malformed);
+
+    Ok(Height {
+        revision_number: parse(revision_number)?,
+        revision_height: parse(revision_height)?,
+    })
+}
@@ -0,0 +1,233 @@
+//! This module handles the execution logic of the contract.
+
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+
+use crate::types::{
+    keys,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state,
+};
+
+/// Instantiates a new contract. The sender must be the `cw-ibc-lite-ics02-client` contract,
+/// whose owner is the router that this client reads from.
+///
+/// # Errors
+/// Will return an error if the router address cannot be read from the sender.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let ownership: cw_ownable::Ownership<cosmwasm_std::Addr> =
+        cw_storage_plus::Item::new(keys::ICS02_OWNERSHIP_KEY).query(&deps.querier, info.sender)?;
+    let router_address = ownership.owner.ok_or_else(|| {
+        LightClientError::InvalidClientState("ics02-client has no owner".to_string())
+    })?;
+
+    state::CLIENT_STATE.save(deps.storage, &state::ClientState { router_address })?;
+
+    Ok(Response::default())
+}
+
+/// Handles the execution of the contract by routing the messages to the respective handlers.
+/// Can be called by anyone.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        // NOTE: The localhost client always tracks the current block, there is nothing to update.
+        ExecuteMsg::UpdateState(_) => Ok(Response::new().set_data(cosmwasm_std::to_json_binary(
+            &ibc_client_cw::types::ContractResult::success(),
+        )?)),
+        ExecuteMsg::UpdateStateOnMisbehaviour(_) => Err(LightClientError::InvalidMisbehaviour(
+            "localhost client has no misbehaviour".to_string(),
+        )
+        .into()),
+        ExecuteMsg::VerifyUpgradeAndUpdateState(_) => {
+            Err(LightClientError::UpgradeNotSupported.into())
+        }
+    }
+}
+
+/// Handles the query messages by routing them to the respective handlers.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Status(_) => query::status(),
+        QueryMsg::ExportMetadata(_) => query::export_metadata(),
+        QueryMsg::TimestampAtHeight(msg) => query::timestamp_at_height(env, msg.height.into()),
+        QueryMsg::VerifyClientMessage(_) => query::verify_client_message(),
+        QueryMsg::CheckForMisbehaviour(_) => query::check_for_misbehaviour(),
+        QueryMsg::VerifyMembership(_) | QueryMsg::VerifyNonMembership(_) => {
+            query::verify_membership(deps, env, msg.try_into()?)
+        }
+        QueryMsg::ClientState {} => query::client_state(env),
+        QueryMsg::ConsensusState { height } => query::consensus_state(env, height),
+        QueryMsg::LatestHeight {} => query::latest_height(env),
+        QueryMsg::ConsensusHeights { .. } => query::consensus_heights(env),
+    }
+}
+
+mod query {
+    use cw_ibc_lite_shared::types::{
+        clients::{
+            error::LightClientError,
+            msg::{query_responses, VerifyMembershipQuery},
+        },
+        ibc::Height,
+        storage::PureItem,
+    };
+    use ibc_client_cw::types::ContractResult;
+
+    use crate::types::{keys, state};
+
+    use super::{Binary, ContractError, Deps, Env};
+
+    pub fn status() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(&query_responses::Status {
+            status: keys::STATUS_ACTIVE.to_string(),
+        })?)
+    }
+
+    pub fn export_metadata() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ExportMetadata { metadata: vec![] },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn timestamp_at_height(env: Env, height: Height) -> Result<Binary, ContractError> {
+        validate_height(&env, &height)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::TimestampAtHeight {
+                timestamp: env.block.time.nanos(),
+            },
+        )?)
+    }
+
+    pub fn verify_client_message() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::VerifyClientMessage { is_valid: true },
+        )?)
+    }
+
+    pub fn check_for_misbehaviour() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::CheckForMisbehaviour {
+                found_misbehaviour: false,
+            },
+        )?)
+    }
+
+    /// Verifies that the router stores the value at the ICS-24 path by reading its storage.
+    /// If the value is [`None`], verifies that nothing is stored at the path.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_membership(
+        deps: Deps,
+        env: Env,
+        query: VerifyMembershipQuery,
+    ) -> Result<Binary, ContractError> {
+        validate_height(&env, &query.height)?;
+
+        let key = match query.key_path.as_slice() {
+            [key] => key,
+            [] => return Err(LightClientError::EmptyMerklePath.into()),
+            _ => {
+                return Err(LightClientError::InvalidProof(
+                    "localhost merkle path must not be prefixed".to_string(),
+                )
+                .into())
+            }
+        };
+
+        let router_address = state::CLIENT_STATE.load(deps.storage)?.router_address;
+        let stored = PureItem::new(key).query(&deps.querier, router_address)?;
+        if stored.as_deref() != query.value.as_deref() {
+            return Err(LightClientError::InvalidProof(format!(
+                "router storage does not match the expected value at {key}"
+            ))
+            .into());
+        }
+
+        Ok(cosmwasm_std::to_json_binary(&ContractResult::success())?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn client_state(env: Env) -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ClientState {
+                latest_height: state::helpers::self_height(&env),
+                frozen_height: None,
+                chain_id: Some(env.block.chain_id),
+                trusting_period: None,
+            },
+        )?)
+    }
+
+    /// The localhost client does not store consensus states, the consensus state is always that
+    /// of the current block. Its root is empty since proofs are not used.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_state(env: Env, height: Height) -> Result<Binary, ContractError> {
+        validate_height(&env, &height)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusState {
+                timestamp: env.block.time.nanos(),
+                root: Binary::default(),
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn latest_height(env: Env) -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::LatestHeight {
+                height: state::helpers::self_height(&env),
+            },
+        )?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn consensus_heights(env: Env) -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusHeights {
+                heights: vec![state::helpers::self_height(&env)],
+            },
+        )?)
+    }
+
+    /// Ensures that the height is not past the current block.
+    fn validate_height(env: &Env, height: &Height) -> Result<(), LightClientError> {
+        let self_height = state::helpers::self_height(env);
+        if height.revision_number != self_height.revision_number
+            || height.revision_height > self_height.revision_height
+        {
+            return Err(LightClientError::invalid_proof_height(
+                format!(
+                    "{}-{}",
+                    self_height.revision_number, self_height.revision_height
+                ),
+                format!("{}-{}", height.revision_number, height.revision_height),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+//! # Keys
+//!
+//! Contains key constants definitions for the contract.
+
+/// `ICS02_OWNERSHIP_KEY` is the storage key of the `cw-ownable` ownership in
+/// `cw-ibc-lite-ics02-client`. Its owner is the `cw-ibc-lite-ics26-router` contract.
+pub const ICS02_OWNERSHIP_KEY: &str = "ownership";
+
+/// `STATUS_ACTIVE` is the status of the client, which is always active.
+pub const STATUS_ACTIVE: &str = "Active";
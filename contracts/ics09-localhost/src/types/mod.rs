@@ -0,0 +1,6 @@
+//! This module contains the types used by the contract's execution and state logic.
+
+pub mod keys;
+#[allow(clippy::module_name_repetitions)]
+pub mod msg;
+pub mod state;
@@ -0,0 +1,6 @@
+//! # Messages
+//!
+//! This module defines the messages that this contract receives. The localhost client accepts
+//! the messages shared by all light clients, and ignores the client messages and proofs.
+
+pub use cw_ibc_lite_shared::types::clients::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -0,0 +1,37 @@
+//! This module defines the state storage of the Contract.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+
+use cw_storage_plus::Item;
+
+/// The item for storing the [`ClientState`].
+pub const CLIENT_STATE: Item<ClientState> = Item::new("client_state");
+
+/// The client state of the localhost client.
+#[cw_serde]
+pub struct ClientState {
+    /// The address of the `cw-ibc-lite-ics26-router` contract whose provable store is read.
+    pub router_address: Addr,
+}
+
+/// Contains state storage helpers.
+pub mod helpers {
+    use cosmwasm_std::Env;
+    use cw_ibc_lite_shared::types::ibc::Height;
+    use ibc_core_host::types::identifiers::ChainId;
+
+    /// Returns the height of the current block. The revision number is parsed from the chain id,
+    /// and is `0` if the chain id is not in the `{chain_name}-{revision_number}` format.
+    #[must_use]
+    pub fn self_height(env: &Env) -> Height {
+        let revision_number = ChainId::new(&env.block.chain_id)
+            .map(|chain_id| chain_id.revision_number())
+            .unwrap_or_default();
+
+        Height {
+            revision_number,
+            revision_height: env.block.height,
+        }
+    }
+}
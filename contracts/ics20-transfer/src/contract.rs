@@ -6,7 +6,8 @@ use cw_ibc_lite_shared::types::error::ContractError;
 
 use crate::types::{
     keys,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    state,
 };
 
 /// Instantiates a new contract.
@@ -25,6 +26,9 @@ pub fn instantiate(
     // NOTE: Admin is assumed to be gov module address.
     cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, keys::CONTRACT_VERSION)?;
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.ics26_router_address))?;
+    if let Some(voucher_code_id) = msg.voucher_code_id {
+        state::VOUCHER_CODE_ID.save(deps.storage, &voucher_code_id)?;
+    }
 
     Ok(Response::default())
 }
@@ -43,6 +47,39 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(receive_msg) => execute::receive(deps, env, info, receive_msg),
+        ExecuteMsg::Transfer(transfer_msg) => execute::transfer(deps, env, info, transfer_msg),
+        ExecuteMsg::BatchTransfer(batch_msg) => execute::batch_transfer(deps, env, info, batch_msg),
+        ExecuteMsg::ReceivePacketTokens { packet } => {
+            execute::receive_packet_tokens(deps, env, info, packet)
+        }
+        ExecuteMsg::SetRateLimits {
+            channel,
+            denom,
+            quotas,
+        } => execute::set_rate_limits(deps, env, info, channel, denom, quotas),
+        ExecuteMsg::SetChannelEncoding { channel, encoding } => {
+            execute::set_channel_encoding(deps, env, info, channel, encoding)
+        }
+        ExecuteMsg::SetChannelBatchSupport { channel, supported } => {
+            execute::set_channel_batch_support(deps, env, info, channel, supported)
+        }
+        ExecuteMsg::SetDefaultTimeout {
+            channel,
+            timeout_seconds,
+        } => execute::set_default_timeout(deps, env, info, channel, timeout_seconds),
+        ExecuteMsg::SetCallbackGasLimit { gas_limit } => {
+            execute::set_callback_gas_limit(deps, env, info, gas_limit)
+        }
+        ExecuteMsg::SetAllowlists(allowlists) => {
+            execute::set_allowlists(deps, env, info, allowlists)
+        }
+        ExecuteMsg::SetTokenPolicy { denom, policy } => {
+            execute::set_token_policy(deps, env, info, denom, policy)
+        }
+        ExecuteMsg::SetChannelPolicy { channel, policy } => {
+            execute::set_channel_policy(deps, env, info, channel, policy)
+        }
+        ExecuteMsg::Recover(recovery) => execute::recover(deps, env, info, recovery),
         ExecuteMsg::ReceiveIbcAppCallback(callback_msg) => {
             execute::receive_ibc_callback(deps, env, info, callback_msg)
         }
@@ -57,9 +94,10 @@ pub fn execute(
 #[allow(clippy::needless_pass_by_value)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
-        keys::reply::ON_RECV_PACKET_CW20_TRANSFER => {
-            reply::on_recv_packet_cw20_transfer(deps, env, msg.result, msg.payload)
+        keys::reply::ON_RECV_PACKET_TRANSFER => {
+            reply::on_recv_packet_transfer(deps, env, msg.result)
         }
+        keys::reply::ON_SOURCE_CALLBACK => reply::on_source_callback(deps, env, msg.result),
         _ => Err(ContractError::UnknownReplyId(msg.id)),
     }
 }
@@ -76,31 +114,82 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             channel,
             cw20_address,
         } => query::escrow_amount(deps, env, channel, cw20_address),
+        QueryMsg::EscrowInvariant { denom } => query::escrow_invariant(deps, env, denom),
         QueryMsg::ListEscrows {
             channel,
             start_after,
             limit,
         } => query::list_escrow(deps, env, channel, start_after, limit),
+        QueryMsg::Voucher { denom_trace } => query::voucher(deps, env, denom_trace),
+        QueryMsg::DenomTrace { hash } => query::denom_trace(deps, env, hash),
+        QueryMsg::ListDenomTraces { start_after, limit } => {
+            query::list_denom_traces(deps, env, start_after, limit)
+        }
+        QueryMsg::DenomHash { trace } => query::denom_hash(deps, env, trace),
+        QueryMsg::RateLimits { channel, denom } => query::rate_limits(deps, env, channel, denom),
+        QueryMsg::InFlightTransfers {
+            sender,
+            start_after,
+            limit,
+        } => query::in_flight_transfers(deps, env, sender, start_after, limit),
+        QueryMsg::DefaultTimeout { channel } => query::default_timeout(deps, env, channel),
+        QueryMsg::ChannelEncoding { channel } => query::channel_encoding(deps, env, channel),
+        QueryMsg::ChannelBatchSupport { channel } => {
+            query::channel_batch_support(deps, env, channel)
+        }
+        QueryMsg::CallbackGasLimit {} => query::callback_gas_limit(deps, env),
+        QueryMsg::Allowlists {} => query::allowlists(deps, env),
+        QueryMsg::ListTokenPolicies { start_after, limit } => {
+            query::list_token_policies(deps, env, start_after, limit)
+        }
+        QueryMsg::ListChannelPolicies { start_after, limit } => {
+            query::list_channel_policies(deps, env, start_after, limit)
+        }
         QueryMsg::Ownership {} => query::ownership(deps),
     }
 }
 
+/// Migrates the contract to this version. Refuses to migrate from another contract or from a
+/// newer version of this one.
+///
+/// # Errors
+/// Will return an error if the recorded contract name or version does not allow the migration.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // NOTE: State migrations from the returned previous version are to be run here once the
+    // storage layout changes.
+    cw_ibc_lite_shared::utils::migrate::ensure_from_older_version(
+        deps.storage,
+        keys::CONTRACT_NAME,
+        keys::CONTRACT_VERSION,
+    )?;
+
+    Ok(Response::default())
+}
+
 mod execute {
-    use cosmwasm_std::IbcTimeout;
-    use cw_ibc_lite_ics26_router::{
-        helpers::IbcLiteRouterContract, types::msg::execute::SendPacketMsg,
+    use cosmwasm_std::{Empty, Uint128};
+    use cw_ibc_lite_shared::types::{
+        apps::callbacks::IbcAppCallbackMsg,
+        transfer::{
+            encoding::PacketEncoding,
+            error::TransferError,
+            packet::Token,
+            rate_limit::{FlowDirection, Quota, RateLimit},
+        },
     };
-    use cw_ibc_lite_shared::{
+
+    use crate::{
+        ibc,
         types::{
-            apps::callbacks::IbcAppCallbackMsg,
-            transfer::{error::TransferError, packet::Ics20Packet},
+            events,
+            msg::{BatchTransferMsg, ReceiveMsg, Recovery, TransferMsg},
+            state::{Allowlists, ChannelPolicy, TokenPolicy},
         },
-        utils,
     };
 
-    use crate::{ibc, types::msg::TransferMsg};
-
-    use super::{keys, ContractError, DepsMut, Env, MessageInfo, Response};
+    use super::{state, ContractError, Deps, DepsMut, Env, MessageInfo, Response};
 
     #[allow(clippy::needless_pass_by_value)]
     pub fn receive(
@@ -113,42 +202,439 @@ mod execute {
             return Err(TransferError::UnexpectedNativeToken.into());
         }
 
-        let ics26_address = cw_ownable::get_ownership(deps.storage)?
-            .owner
-            .ok_or(ContractError::Unauthorized)?;
-        let ics26_contract = IbcLiteRouterContract::new(ics26_address);
+        let receive_msg: ReceiveMsg = cosmwasm_std::from_json(msg.msg)?;
+        // NOTE: We use the sender contract address as the denom, unless it is a voucher, in
+        // which case we use its denom trace.
+        let denom = state::VOUCHER_TRACES
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_else(|| info.sender.to_string());
+        let token = Token {
+            denom,
+            amount: msg.amount,
+        };
 
-        let transfer_msg: TransferMsg = cosmwasm_std::from_json(msg.msg)?;
-        let denom = info.sender.to_string(); // NOTE: We use the sender contract address as the denom.
-        let source_port = utils::apps::contract_port_id(&env.contract.address)?.into();
-        let timeout_seconds = transfer_msg
-            .timeout
-            .unwrap_or(keys::DEFAULT_TIMEOUT_SECONDS);
-        let timeout = IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout_seconds));
+        match receive_msg {
+            ReceiveMsg::Transfer(transfer_msg) => {
+                send_transfer_packet(deps, env, vec![token], msg.sender, transfer_msg)
+            }
+            ReceiveMsg::BatchTransfer(batch_msg) => {
+                send_batch_transfer_packets(deps, env, token, msg.sender, batch_msg)
+            }
+        }
+    }
 
-        let packet = Ics20Packet::try_new(
-            msg.amount,
-            denom,
-            transfer_msg.receiver,
-            msg.sender,
-            transfer_msg.memo,
-        )?;
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        transfer_msg: TransferMsg,
+    ) -> Result<Response, ContractError> {
+        if info.funds.is_empty() {
+            return Err(TransferError::InvalidNativeFunds.into());
+        }
+        let tokens = info
+            .funds
+            .into_iter()
+            .map(|coin| {
+                // NOTE: A native denom must not be mistaken for a cw20 contract address.
+                if deps.api.addr_validate(&coin.denom).is_ok() {
+                    return Err(TransferError::InvalidNativeDenom(coin.denom));
+                }
+                Ok(Token {
+                    denom: coin.denom,
+                    amount: coin.amount,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let send_packet_msg = SendPacketMsg {
-            source_port,
-            source_channel: transfer_msg.source_channel,
-            dest_port: keys::DEFAULT_PORT_ID.to_string(),
-            dest_channel: None, // NOTE: Router will determine the dest channel.
-            data: cosmwasm_std::to_json_binary(&packet)?,
-            timeout,
-            version: keys::ICS20_VERSION.to_string(),
+        send_transfer_packet(deps, env, tokens, info.sender.into(), transfer_msg)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn batch_transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        batch_msg: BatchTransferMsg,
+    ) -> Result<Response, ContractError> {
+        let coin = match info.funds.as_slice() {
+            [] => return Err(TransferError::InvalidNativeFunds.into()),
+            [coin] => coin.clone(),
+            _ => {
+                return Err(TransferError::InvalidBatch(
+                    "exactly one coin must be sent".to_string(),
+                )
+                .into())
+            }
+        };
+        // NOTE: A native denom must not be mistaken for a cw20 contract address.
+        if deps.api.addr_validate(&coin.denom).is_ok() {
+            return Err(TransferError::InvalidNativeDenom(coin.denom).into());
+        }
+        let token = Token {
+            denom: coin.denom,
+            amount: coin.amount,
         };
-        let ics26_msg = ics26_contract.call(send_packet_msg)?;
 
-        // TODO: Add events, maybe
+        send_batch_transfer_packets(deps, env, token, info.sender.into(), batch_msg)
+    }
+
+    /// Sends an ICS-20 packet for tokens already received by this contract. The tokens are
+    /// escrowed in [`crate::ibc::relay::on_send_packet`].
+    #[allow(clippy::needless_pass_by_value)]
+    fn send_transfer_packet(
+        deps: DepsMut,
+        env: Env,
+        tokens: Vec<Token>,
+        sender: String,
+        transfer_msg: TransferMsg,
+    ) -> Result<Response, ContractError> {
+        // NOTE: The policies are checked again in `OnSendPacket`, this fails early.
+        ibc::policy::assert_allowed(
+            deps.storage,
+            &transfer_msg.source_channel,
+            tokens.iter().map(|token| token.denom.as_str()),
+            FlowDirection::Send,
+        )?;
+
+        // NOTE: Only the sender may register itself to be notified of the outcome.
+        if let Some(callback) = transfer_msg.callback.as_deref() {
+            if callback != sender {
+                return Err(TransferError::InvalidCallback(format!(
+                    "callback {callback} is not the sender {sender}"
+                ))
+                .into());
+            }
+            let callback = deps.api.addr_validate(callback)?;
+            ibc::callbacks::set_pending(deps.storage, &callback)?;
+        }
+
+        let ics26_msg =
+            ibc::relay::send_packet_msg(deps.storage, &env, tokens, sender, transfer_msg)?;
+
+        // NOTE: The transfer events are emitted in `OnSendPacket`, once the sequence is known.
         Ok(Response::new().add_message(ics26_msg))
     }
 
+    /// Sends the batch for tokens already received by this contract, in a single `ics20-batch-1`
+    /// packet if the counterparty app accepts it and packet data is JSON encoded on the channel.
+    /// Otherwise, one packet is sent per receiver, which is escrowed, acknowledged and refunded
+    /// on its own.
+    #[allow(clippy::needless_pass_by_value)]
+    fn send_batch_transfer_packets(
+        deps: DepsMut,
+        env: Env,
+        token: Token,
+        sender: String,
+        batch_msg: BatchTransferMsg,
+    ) -> Result<Response, ContractError> {
+        if batch_msg.recipients.is_empty() {
+            return Err(TransferError::InvalidBatch("no recipients".to_string()).into());
+        }
+        let total = batch_msg
+            .recipients
+            .iter()
+            .try_fold(Uint128::zero(), |total, recipient| {
+                total.checked_add(recipient.amount)
+            })
+            .map_err(|_| TransferError::AmountOverflow)?;
+        if total != token.amount {
+            return Err(TransferError::InvalidBatch(format!(
+                "recipients sum to {total}, but {} was sent",
+                token.amount
+            ))
+            .into());
+        }
+
+        // NOTE: The policies are checked again in `OnSendPacket`, this fails early.
+        ibc::policy::assert_allowed(
+            deps.storage,
+            &batch_msg.source_channel,
+            [token.denom.as_str()],
+            FlowDirection::Send,
+        )?;
+
+        let encoding = ibc::encoding::channel_encoding(deps.storage, &batch_msg.source_channel)?;
+        if batch_msg.recipients.len() > 1
+            && encoding == PacketEncoding::Json
+            && state::BATCH_CHANNELS.has(deps.storage, &batch_msg.source_channel)
+        {
+            let ics26_msg = ibc::relay::send_batch_packet_msg(
+                deps.storage,
+                &env,
+                token.denom,
+                sender,
+                batch_msg,
+            )?;
+            return Ok(Response::new().add_message(ics26_msg));
+        }
+
+        let ics26_msgs = batch_msg
+            .recipients
+            .into_iter()
+            .map(|recipient| {
+                let transfer_msg = TransferMsg {
+                    source_channel: batch_msg.source_channel.clone(),
+                    receiver: recipient.receiver,
+                    timeout: batch_msg.timeout.clone(),
+                    memo: batch_msg.memo.clone(),
+                    forwarding: None,
+                    callback: None,
+                };
+                let token = Token {
+                    denom: token.denom.clone(),
+                    amount: recipient.amount,
+                };
+                ibc::relay::send_packet_msg(
+                    deps.storage,
+                    &env,
+                    vec![token],
+                    sender.clone(),
+                    transfer_msg,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Response::new().add_messages(ics26_msgs))
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn receive_packet_tokens(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        packet: cw_ibc_lite_shared::types::ibc::Packet,
+    ) -> Result<Response, ContractError> {
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized);
+        }
+
+        ibc::relay::receive_packet_tokens(deps, &env, packet)
+    }
+
+    /// Sets the quotas on the transfers of a denom over a channel. Only the contract admin may
+    /// set them.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_rate_limits(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        denom: String,
+        quotas: Vec<Quota>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+
+        if quotas.is_empty() {
+            state::RATE_LIMITS.remove(deps.storage, (&channel, &denom));
+            return Ok(Response::default());
+        }
+
+        let mut rate_limits: Vec<RateLimit> = Vec::with_capacity(quotas.len());
+        for quota in quotas {
+            quota.validate()?;
+            if rate_limits.iter().any(|r| r.quota.name == quota.name) {
+                return Err(TransferError::DuplicateQuota(quota.name).into());
+            }
+            let channel_value =
+                ibc::rate_limit::channel_value(deps.as_ref(), &channel, &denom, quota.base)?;
+            rate_limits.push(RateLimit::new(quota, env.block.time, channel_value));
+        }
+        state::RATE_LIMITS.save(deps.storage, (&channel, &denom), &rate_limits)?;
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_channel_encoding(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        encoding: Option<PacketEncoding>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match encoding {
+            Some(encoding) => state::CHANNEL_ENCODINGS.save(deps.storage, &channel, &encoding)?,
+            None => state::CHANNEL_ENCODINGS.remove(deps.storage, &channel),
+        }
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_channel_batch_support(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        supported: bool,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        if supported {
+            state::BATCH_CHANNELS.save(deps.storage, &channel, &Empty {})?;
+        } else {
+            state::BATCH_CHANNELS.remove(deps.storage, &channel);
+        }
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_default_timeout(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        timeout_seconds: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match timeout_seconds {
+            Some(0) => return Err(ContractError::EmptyTimeout),
+            Some(seconds) => state::DEFAULT_TIMEOUTS.save(deps.storage, &channel, &seconds)?,
+            None => state::DEFAULT_TIMEOUTS.remove(deps.storage, &channel),
+        }
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_callback_gas_limit(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        gas_limit: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match gas_limit {
+            Some(0) => {
+                return Err(TransferError::InvalidCallback("zero gas limit".to_string()).into())
+            }
+            Some(gas_limit) => state::CALLBACK_GAS_LIMIT.save(deps.storage, &gas_limit)?,
+            None => state::CALLBACK_GAS_LIMIT.remove(deps.storage),
+        }
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_allowlists(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        allowlists: Allowlists,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        state::ALLOWLISTS.save(deps.storage, &allowlists)?;
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_token_policy(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        policy: Option<TokenPolicy>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match policy {
+            Some(policy) => state::TOKEN_POLICIES.save(deps.storage, &denom, &policy)?,
+            None => state::TOKEN_POLICIES.remove(deps.storage, &denom),
+        }
+
+        Ok(Response::default())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn set_channel_policy(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        policy: Option<ChannelPolicy>,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match policy {
+            Some(policy) => state::CHANNEL_POLICIES.save(deps.storage, &channel, &policy)?,
+            None => state::CHANNEL_POLICIES.remove(deps.storage, &channel),
+        }
+
+        Ok(Response::default())
+    }
+
+    /// Executes a recovery action. Setting an escrow does not check the invariant, since it may
+    /// take several actions to reconcile a discrepancy, while funds are only returned if they
+    /// are not escrowed.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn recover(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recovery: Recovery,
+    ) -> Result<Response, ContractError> {
+        assert_admin(deps.as_ref(), &env, &info)?;
+        match recovery {
+            Recovery::SetEscrow {
+                channel,
+                denom,
+                amount,
+            } => {
+                let previous = state::ESCROW
+                    .may_load(deps.storage, (&channel, &denom))?
+                    .unwrap_or_default();
+                if amount.is_zero() {
+                    state::ESCROW.remove(deps.storage, (&channel, &denom));
+                } else {
+                    state::ESCROW.save(deps.storage, (&channel, &denom), &amount)?;
+                }
+
+                Ok(Response::new().add_event(events::recovery::set_escrow(
+                    &channel, &denom, previous, amount,
+                )))
+            }
+            Recovery::ReturnFunds {
+                denom,
+                amount,
+                recipient,
+            } => {
+                let recipient = deps.api.addr_validate(&recipient)?.into_string();
+                let stranded = ibc::escrow::stranded(deps.as_ref(), &env, &denom)?;
+                if amount > stranded {
+                    return Err(TransferError::InsufficientStrandedFunds {
+                        stranded: stranded.to_string(),
+                        requested: amount.to_string(),
+                    }
+                    .into());
+                }
+
+                let event = events::recovery::return_funds(&denom, amount, &recipient);
+                let transfer_msg = ibc::relay::transfer_tokens_msg(
+                    deps.storage,
+                    deps.api,
+                    &denom,
+                    recipient,
+                    amount,
+                )?;
+                Ok(Response::new().add_message(transfer_msg).add_event(event))
+            }
+        }
+    }
+
+    /// Asserts that the sender is the contract admin, which is assumed to be the gov module.
+    /// The owner of this contract is the router, so it cannot manage the policies.
+    fn assert_admin(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+        let contract_info = deps
+            .querier
+            .query_wasm_contract_info(env.contract.address.as_str())?;
+        match contract_info.admin {
+            Some(admin) if admin.as_str() == info.sender.as_str() => Ok(()),
+            _ => Err(ContractError::Unauthorized),
+        }
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     pub fn receive_ibc_callback(
         deps: DepsMut,
@@ -186,51 +672,52 @@ mod execute {
 }
 
 mod reply {
-    use crate::types::state::{self, ESCROW};
-
     use super::{ContractError, DepsMut, Env, Response};
 
-    use cosmwasm_std::{Binary, SubMsgResult};
+    use cosmwasm_std::SubMsgResult;
     use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
 
-    /// Handles the reply to
-    /// [`cw_ibc_lite_shared::types::apps::callbacks::IbcAppCallbackMsg::OnRecvPacket`].
-    /// It writes the acknowledgement and emits the write acknowledgement events.
+    /// Handles the reply to [`crate::types::msg::ExecuteMsg::ReceivePacketTokens`].
+    /// It writes an error acknowledgement, since none of the tokens were received.
     #[allow(clippy::needless_pass_by_value)]
-    pub fn on_recv_packet_cw20_transfer(
-        deps: DepsMut,
+    pub fn on_recv_packet_transfer(
+        _deps: DepsMut,
         _env: Env,
         result: SubMsgResult,
-        payload: Binary,
     ) -> Result<Response, ContractError> {
         match result {
             SubMsgResult::Ok(_) => {
                 unreachable!("unexpected response on `SubMsg::reply_on_err`")
             }
-            SubMsgResult::Err(err) => {
-                let reply_args: state::RecvPacketReplyPayload = cosmwasm_std::from_json(payload)?;
-                // undo the escrow reduction
-                ESCROW.update(
-                    deps.storage,
-                    (&reply_args.channel_id, &reply_args.denom),
-                    |bal| -> Result<_, ContractError> {
-                        let mut bal = bal.unwrap();
-                        bal += reply_args.amount;
-                        Ok(bal)
-                    },
-                )?;
+            SubMsgResult::Err(err) => Ok(Response::new().set_data(Ics20Ack::error(err).to_vec())),
+        }
+    }
 
-                Ok(Response::new().set_data(Ics20Ack::error(err).to_vec()))
+    /// Handles the reply to the source callback of a transfer. The error of the callback
+    /// contract is only recorded, since the acknowledgement or timeout must not fail because of
+    /// it.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn on_source_callback(
+        _deps: DepsMut,
+        _env: Env,
+        result: SubMsgResult,
+    ) -> Result<Response, ContractError> {
+        match result {
+            SubMsgResult::Ok(_) => {
+                unreachable!("unexpected response on `SubMsg::reply_on_err`")
             }
+            SubMsgResult::Err(err) => Ok(Response::new().add_attribute("callback_error", err)),
         }
     }
 }
 
 mod query {
     use cosmwasm_std::{Order, StdResult};
+    use cw_ibc_lite_shared::types::transfer::denom::{self, DenomTrace};
     use cw_storage_plus::Bound;
 
     use crate::types::{
+        keys,
         msg::responses,
         state::{self, ESCROW},
     };
@@ -253,6 +740,157 @@ mod query {
         Ok(cosmwasm_std::to_json_binary(&amount)?)
     }
 
+    /// Queries the voucher contract of the given denom trace.
+    ///
+    /// # Errors
+    /// Will return an error if no voucher exists for the denom trace.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn voucher(deps: Deps, _env: Env, denom_trace: String) -> Result<Binary, ContractError> {
+        let address = state::VOUCHERS.load(deps.storage, &denom_trace)?;
+        Ok(cosmwasm_std::to_json_binary(&responses::VoucherInfo {
+            denom_trace,
+            address: address.into_string(),
+        })?)
+    }
+
+    /// Queries the denom trace of the given hash.
+    ///
+    /// # Errors
+    /// Will return an error if the hash is invalid or no denom trace is registered for it.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn denom_trace(deps: Deps, _env: Env, hash: String) -> Result<Binary, ContractError> {
+        let hash = denom::parse_denom_hash(&hash)?;
+        let denom_trace = state::DENOM_TRACES.load(deps.storage, &hash)?;
+        Ok(cosmwasm_std::to_json_binary(&responses::DenomTraceInfo {
+            hash,
+            denom_trace,
+        })?)
+    }
+
+    /// Computes the hash of the given denom trace.
+    ///
+    /// # Errors
+    /// Will return an error if the denom trace is invalid.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn denom_hash(_deps: Deps, _env: Env, trace: String) -> Result<Binary, ContractError> {
+        let trace: DenomTrace = trace.parse()?;
+        Ok(cosmwasm_std::to_json_binary(&responses::DenomHash {
+            hash: trace.hash(),
+        })?)
+    }
+
+    /// Queries the rate limits on the transfers of the given denom over the given channel.
+    ///
+    /// # Errors
+    /// Will return an error if the rate limits cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn rate_limits(
+        deps: Deps,
+        _env: Env,
+        channel: String,
+        denom: String,
+    ) -> Result<Binary, ContractError> {
+        let list = state::RATE_LIMITS
+            .may_load(deps.storage, (&channel, &denom))?
+            .unwrap_or_default();
+        Ok(cosmwasm_std::to_json_binary(&responses::RateLimitList {
+            list,
+        })?)
+    }
+
+    /// Queries whether the tokens held by this contract cover the total escrowed amount of the
+    /// given denom.
+    ///
+    /// # Errors
+    /// Will return an error if the escrows cannot be loaded, or the balance cannot be queried.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn escrow_invariant(deps: Deps, env: Env, denom: String) -> Result<Binary, ContractError> {
+        let escrowed = crate::ibc::escrow::total_escrowed(deps.storage, &denom)?;
+        let balance = crate::ibc::escrow::balance(deps, &env, &denom)?;
+        Ok(cosmwasm_std::to_json_binary(&responses::EscrowInvariant {
+            denom,
+            escrowed,
+            balance,
+            holds: balance >= escrowed,
+        })?)
+    }
+
+    /// Queries the default timeout of the transfers sent on the given channel.
+    ///
+    /// # Errors
+    /// Will return an error if the default timeout cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn default_timeout(
+        deps: Deps,
+        _env: Env,
+        channel: String,
+    ) -> Result<Binary, ContractError> {
+        let timeout_seconds = state::DEFAULT_TIMEOUTS
+            .may_load(deps.storage, &channel)?
+            .unwrap_or(keys::DEFAULT_TIMEOUT_SECONDS);
+        Ok(cosmwasm_std::to_json_binary(&responses::DefaultTimeout {
+            channel,
+            timeout_seconds,
+        })?)
+    }
+
+    /// Queries the encoding of the packet data of the counterparty app on the given channel.
+    ///
+    /// # Errors
+    /// Will return an error if the encoding cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn channel_encoding(
+        deps: Deps,
+        _env: Env,
+        channel: String,
+    ) -> Result<Binary, ContractError> {
+        let encoding = crate::ibc::encoding::channel_encoding(deps.storage, &channel)?;
+        Ok(cosmwasm_std::to_json_binary(&responses::ChannelEncoding {
+            channel,
+            encoding,
+        })?)
+    }
+
+    /// Queries whether the counterparty app on the given channel accepts `ics20-batch-1` packets.
+    ///
+    /// # Errors
+    /// Will return an error if the batch support cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn channel_batch_support(
+        deps: Deps,
+        _env: Env,
+        channel: String,
+    ) -> Result<Binary, ContractError> {
+        let supported = state::BATCH_CHANNELS.has(deps.storage, &channel);
+        Ok(cosmwasm_std::to_json_binary(
+            &responses::ChannelBatchSupport { channel, supported },
+        )?)
+    }
+
+    /// Queries the gas limit of source callbacks.
+    ///
+    /// # Errors
+    /// Will return an error if the gas limit cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn callback_gas_limit(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let gas_limit = crate::ibc::callbacks::gas_limit(deps.storage)?;
+        Ok(cosmwasm_std::to_json_binary(
+            &responses::CallbackGasLimit { gas_limit },
+        )?)
+    }
+
+    /// Queries whether the token and channel allowlists are enabled.
+    ///
+    /// # Errors
+    /// Will return an error if the allowlists cannot be loaded or serialized.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn allowlists(deps: Deps, _env: Env) -> Result<Binary, ContractError> {
+        let allowlists = state::ALLOWLISTS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        Ok(cosmwasm_std::to_json_binary(&allowlists)?)
+    }
+
     pub fn ownership(deps: Deps) -> Result<Binary, ContractError> {
         Ok(cosmwasm_std::to_json_binary(&cw_ownable::get_ownership(
             deps.storage,
@@ -289,4 +927,163 @@ mod query {
 
         Ok(cosmwasm_std::to_json_binary(&response)?)
     }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn list_denom_traces(
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        let start_after = start_after
+            .as_deref()
+            .map(denom::parse_denom_hash)
+            .transpose()?;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let denom_traces = state::DENOM_TRACES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(hash, denom_trace)| responses::DenomTraceInfo { hash, denom_trace })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let response = responses::DenomTraceList { list: denom_traces };
+
+        Ok(cosmwasm_std::to_json_binary(&response)?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn list_token_policies(
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let policies = state::TOKEN_POLICIES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(denom, policy)| responses::TokenPolicyInfo { denom, policy }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let response = responses::TokenPolicyList { list: policies };
+
+        Ok(cosmwasm_std::to_json_binary(&response)?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn list_channel_policies(
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let policies = state::CHANNEL_POLICIES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(channel, policy)| responses::ChannelPolicyInfo { channel, policy })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let response = responses::ChannelPolicyList { list: policies };
+
+        Ok(cosmwasm_std::to_json_binary(&response)?)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn in_flight_transfers(
+        deps: Deps,
+        _env: Env,
+        sender: String,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        let start = start_after
+            .as_ref()
+            .map(|(channel, sequence)| Bound::exclusive((channel.as_str(), *sequence)));
+
+        let transfers = state::IN_FLIGHT
+            .sub_prefix(sender.as_str())
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, transfer)| transfer))
+            .collect::<StdResult<Vec<_>>>()?;
+        let response = responses::InFlightTransferList { list: transfers };
+
+        Ok(cosmwasm_std::to_json_binary(&response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, Uint128,
+    };
+
+    use crate::types::msg::responses;
+
+    use super::{instantiate, keys, migrate, query, state, InstantiateMsg, MigrateMsg, QueryMsg};
+
+    /// The last released version, which only stores the escrowed amounts.
+    const PREVIOUS_VERSION: &str = "0.1.0";
+
+    #[test]
+    fn migrate_keeps_the_escrows_of_the_previous_version() {
+        let mut deps = mock_dependencies();
+        let router = deps.api.addr_make("router");
+        let msg = InstantiateMsg {
+            ics26_router_address: router.to_string(),
+            voucher_code_id: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(router.as_str(), &[]),
+            msg,
+        )
+        .unwrap();
+        cw2::set_contract_version(&mut deps.storage, keys::CONTRACT_NAME, PREVIOUS_VERSION)
+            .unwrap();
+        for (channel, amount) in [("08-wasm-0", 100_u128), ("08-wasm-1", 50)] {
+            state::ESCROW
+                .save(&mut deps.storage, (channel, "uatom"), &Uint128::new(amount))
+                .unwrap();
+        }
+        deps.querier
+            .bank
+            .update_balance(mock_env().contract.address, vec![coin(150, "uatom")]);
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, keys::CONTRACT_VERSION);
+        let msg = QueryMsg::EscrowAmount {
+            channel: "08-wasm-0".to_string(),
+            cw20_address: "uatom".to_string(),
+        };
+        let escrowed: Uint128 =
+            cosmwasm_std::from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(escrowed.u128(), 100);
+        let msg = QueryMsg::EscrowInvariant {
+            denom: "uatom".to_string(),
+        };
+        let invariant: responses::EscrowInvariant =
+            cosmwasm_std::from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(invariant.escrowed.u128(), 150);
+        assert!(invariant.holds);
+        let ownership: cw_ownable::Ownership<Addr> = cosmwasm_std::from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Ownership {}).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(ownership.owner, Some(router));
+    }
 }
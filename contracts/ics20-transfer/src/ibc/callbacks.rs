@@ -0,0 +1,81 @@
+//! This module contains the helpers for the source callbacks registered by transfer senders.
+
+use cosmwasm_std::{Addr, Storage, SubMsg};
+use cw_ibc_lite_shared::types::{
+    error::ContractError,
+    ibc,
+    transfer::callbacks::{IbcTransferCallback, TransferOutcome},
+};
+
+use crate::types::{keys, state};
+
+/// Registers the callback contract of the transfer being sent. It is moved under the sequence
+/// of the packet by [`register_pending`].
+///
+/// # Errors
+/// Will return an error if the state cannot be saved.
+pub fn set_pending(storage: &mut dyn Storage, callback: &Addr) -> Result<(), ContractError> {
+    Ok(state::PENDING_CALLBACK.save(storage, callback)?)
+}
+
+/// Registers the pending callback contract, if any, under the sequence of the packet being
+/// sent.
+///
+/// # Errors
+/// Will return an error if the state cannot be loaded or saved.
+pub fn register_pending(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+) -> Result<(), ContractError> {
+    if let Some(callback) = state::PENDING_CALLBACK.may_load(storage)? {
+        state::PENDING_CALLBACK.remove(storage);
+        state::SOURCE_CALLBACKS.save(
+            storage,
+            (packet.source_channel.as_str(), packet.sequence.value()),
+            &callback,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns the gas limit of source callbacks.
+///
+/// # Errors
+/// Will return an error if the gas limit cannot be loaded.
+pub fn gas_limit(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(state::CALLBACK_GAS_LIMIT
+        .may_load(storage)?
+        .unwrap_or(keys::DEFAULT_CALLBACK_GAS_LIMIT))
+}
+
+/// Removes the callback contract of the given packet, and returns the submessage notifying it
+/// of the outcome, if any. Errors of the callback contract, including running out of its gas
+/// limit, are ignored in the reply, so that they cannot block the acknowledgement or timeout of
+/// the packet.
+///
+/// # Errors
+/// Will return an error if the state cannot be loaded or the message cannot be serialized.
+pub fn take_msg(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+    outcome: TransferOutcome,
+) -> Result<Option<SubMsg>, ContractError> {
+    let key = (packet.source_channel.as_str(), packet.sequence.value());
+    let Some(callback) = state::SOURCE_CALLBACKS.may_load(storage, key)? else {
+        return Ok(None);
+    };
+    state::SOURCE_CALLBACKS.remove(storage, key);
+
+    let callback_msg = IbcTransferCallback {
+        packet: packet.clone(),
+        sequence: packet.sequence.value(),
+        outcome,
+    }
+    .into_cosmos_msg(callback)?;
+
+    Ok(Some(
+        SubMsg::reply_on_error(callback_msg, keys::reply::ON_SOURCE_CALLBACK)
+            .with_gas_limit(gas_limit(storage)?),
+    ))
+}
@@ -0,0 +1,52 @@
+//! This module contains the helpers for the encoding of the packet data on each channel, which
+//! is set by the contract admin since `cw-ibc-lite` packets carry no encoding.
+
+use cosmwasm_std::Storage;
+use cw_ibc_lite_shared::types::{error::ContractError, ibc, transfer::encoding::PacketEncoding};
+
+use crate::types::state;
+
+/// Returns the encoding of the packet data of the counterparty app on the channel.
+///
+/// # Errors
+/// Will return an error if the encoding cannot be loaded.
+pub fn channel_encoding(
+    storage: &dyn Storage,
+    channel: &str,
+) -> Result<PacketEncoding, ContractError> {
+    Ok(state::CHANNEL_ENCODINGS
+        .may_load(storage, channel)?
+        .unwrap_or_default())
+}
+
+/// Records the encoding of a packet being sent, see [`take_sent`].
+///
+/// # Errors
+/// Will return an error if the encoding cannot be saved.
+pub fn register_sent(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+    encoding: PacketEncoding,
+) -> Result<(), ContractError> {
+    Ok(state::SENT_ENCODINGS.save(
+        storage,
+        (packet.source_channel.as_str(), packet.sequence.value()),
+        &encoding,
+    )?)
+}
+
+/// Removes and returns the encoding of a packet sent by this contract. Packets sent before the
+/// encoding was recorded are JSON encoded.
+///
+/// # Errors
+/// Will return an error if the encoding cannot be loaded.
+pub fn take_sent(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+) -> Result<PacketEncoding, ContractError> {
+    let key = (packet.source_channel.as_str(), packet.sequence.value());
+    let encoding = state::SENT_ENCODINGS.may_load(storage, key)?;
+    state::SENT_ENCODINGS.remove(storage, key);
+
+    Ok(encoding.unwrap_or_default())
+}
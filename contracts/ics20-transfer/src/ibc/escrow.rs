@@ -0,0 +1,67 @@
+//! This module contains the checks of the escrow invariant: the tokens held by this contract
+//! must cover the escrowed amounts of all channels.
+
+use cosmwasm_std::{Deps, Env, Order, Storage, Uint128};
+use cw_ibc_lite_shared::types::{error::ContractError, transfer::error::TransferError};
+
+use crate::types::state;
+
+/// Returns the total escrowed amount of the denom over all channels.
+///
+/// # Errors
+/// Will return an error if the escrows cannot be loaded, or their total overflows.
+pub fn total_escrowed(storage: &dyn Storage, denom: &str) -> Result<Uint128, ContractError> {
+    let mut total = Uint128::zero();
+    for item in state::ESCROW.range(storage, None, None, Order::Ascending) {
+        let ((_, escrowed_denom), amount) = item?;
+        if escrowed_denom == denom {
+            total = total
+                .checked_add(amount)
+                .map_err(|_| TransferError::AmountOverflow)?;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Returns the balance of the denom held by this contract. The denom is a cw20 contract address
+/// if it is a valid address, the denom trace of an escrowed voucher, or a native denom
+/// otherwise.
+///
+/// # Errors
+/// Will return an error if the balance cannot be queried.
+pub fn balance(deps: Deps, env: &Env, denom: &str) -> Result<Uint128, ContractError> {
+    let Some(cw20_contract) = crate::ibc::voucher::cw20_contract(deps.storage, deps.api, denom)?
+    else {
+        return Ok(deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount);
+    };
+
+    let response: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+        cw20_contract,
+        &cw20::Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+    Ok(response.balance)
+}
+
+/// Returns the amount of the denom held by this contract that is not escrowed, which can be
+/// returned without breaking the escrow invariant.
+///
+/// # Errors
+/// Will return an error if the invariant does not hold, or the balance cannot be queried.
+pub fn stranded(deps: Deps, env: &Env, denom: &str) -> Result<Uint128, ContractError> {
+    let escrowed = total_escrowed(deps.storage, denom)?;
+    let balance = balance(deps, env, denom)?;
+    balance.checked_sub(escrowed).map_err(|_| {
+        TransferError::EscrowInvariantBroken {
+            denom: denom.to_string(),
+            escrowed: escrowed.to_string(),
+            balance: balance.to_string(),
+        }
+        .into()
+    })
+}
@@ -0,0 +1,209 @@
+//! This module contains the helpers for forwarding the tokens of incoming packets to another
+//! chain. The acknowledgement of the incoming packet is held until the forwarded packet is
+//! acknowledged or times out.
+
+use cosmwasm_std::{CosmosMsg, Env, Storage};
+use cw_ibc_lite_ics26_router::{
+    helpers::IbcLiteRouterContract, types::msg::execute::WriteAcknowledgementMsg,
+};
+use cw_ibc_lite_shared::{
+    types::{
+        error::ContractError,
+        ibc,
+        transfer::{
+            error::TransferError,
+            forward::NextHop,
+            packet::{Ics20Ack, Token},
+            rate_limit::FlowDirection,
+        },
+    },
+    utils,
+};
+
+use crate::{
+    ibc::{rate_limit, voucher},
+    types::{
+        keys,
+        msg::{TransferMsg, TransferTimeout},
+        state,
+    },
+};
+
+/// Returns the message forwarding the tokens of an incoming packet to the next hop. The tokens
+/// must already be held by this contract.
+///
+/// # Errors
+/// Will return an error if the next hop is not on this contract's port, or the packet cannot be
+/// sent.
+pub fn forward_tokens_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    packet: ibc::Packet,
+    next_hop: NextHop,
+    tokens: Vec<Token>,
+) -> Result<CosmosMsg, ContractError> {
+    let port_id = utils::apps::contract_port_id(&env.contract.address)?;
+    if next_hop.port_id != port_id.as_str() && next_hop.port_id != keys::DEFAULT_PORT_ID {
+        return Err(TransferError::unexpected_port_id(port_id, next_hop.port_id).into());
+    }
+
+    let transfer_msg = TransferMsg {
+        source_channel: next_hop.channel_id,
+        receiver: next_hop.receiver,
+        timeout: next_hop.timeout.map(TransferTimeout::Relative),
+        memo: next_hop.memo,
+        forwarding: next_hop.forwarding,
+        callback: None,
+    };
+    let send_msg = crate::ibc::relay::send_packet_msg(
+        storage,
+        env,
+        tokens.clone(),
+        env.contract.address.to_string(),
+        transfer_msg,
+    )?;
+
+    state::PENDING_FORWARD.save(storage, &state::ForwardedPacket { packet, tokens })?;
+    Ok(send_msg)
+}
+
+/// Registers the pending forward, if any, under the sequence of the packet being sent.
+///
+/// # Errors
+/// Will return an error if the state cannot be loaded or saved.
+pub fn register_pending(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+) -> Result<(), ContractError> {
+    if let Some(forwarded) = state::PENDING_FORWARD.may_load(storage)? {
+        state::PENDING_FORWARD.remove(storage);
+        state::FORWARDS.save(
+            storage,
+            (packet.source_channel.as_str(), packet.sequence.value()),
+            &forwarded,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Removes and returns the incoming packet forwarded by the given packet, if any.
+///
+/// # Errors
+/// Will return an error if the state cannot be loaded.
+pub fn take(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+) -> Result<Option<state::ForwardedPacket>, ContractError> {
+    let key = (packet.source_channel.as_str(), packet.sequence.value());
+    let forwarded = state::FORWARDS.may_load(storage, key)?;
+    if forwarded.is_some() {
+        state::FORWARDS.remove(storage, key);
+    }
+
+    Ok(forwarded)
+}
+
+/// Reverts the forward of an incoming packet that failed, since its tokens are refunded on the
+/// source chain instead. The tokens sent on the forwarding channel are released from escrow, or
+/// minted back if they were burned, and the tokens received on the incoming channel are escrowed
+/// again, or burned if they were minted. The flows of both packets are removed from the rate
+/// limits. Returns the messages minting or burning vouchers.
+///
+/// # Errors
+/// Will return an error if the escrowed balance on the forwarding channel is insufficient.
+pub fn revert(
+    storage: &mut dyn Storage,
+    env: &Env,
+    packet: &ibc::Packet,
+    forwarded: &state::ForwardedPacket,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut msgs = vec![];
+    for token in &forwarded.tokens {
+        rate_limit::undo(
+            storage,
+            packet.source_channel.as_str(),
+            &token.denom,
+            FlowDirection::Send,
+            token.amount,
+        )?;
+        rate_limit::undo(
+            storage,
+            forwarded.packet.destination_channel.as_str(),
+            &token.denom,
+            FlowDirection::Recv,
+            token.amount,
+        )?;
+
+        let burned = voucher::returning(
+            storage,
+            packet.source_port.as_str(),
+            packet.source_channel.as_str(),
+            &token.denom,
+        )?;
+        // NOTE: Vouchers are minted with the port of this contract, which sent the packet.
+        let minted = voucher::returning(
+            storage,
+            packet.source_port.as_str(),
+            forwarded.packet.destination_channel.as_str(),
+            &token.denom,
+        )?;
+        match (burned, minted) {
+            // The vouchers minted for the incoming packet were burned by the forward.
+            (Some(_), Some(_)) => {}
+            (Some(voucher), None) => {
+                msgs.push(voucher::mint_msg(
+                    &voucher,
+                    env.contract.address.to_string(),
+                    token.amount,
+                )?);
+                escrow(
+                    storage,
+                    forwarded.packet.destination_channel.as_str(),
+                    token,
+                )?;
+            }
+            (None, Some(voucher)) => {
+                release(storage, packet.source_channel.as_str(), token)?;
+                msgs.push(voucher::burn_msg(&voucher, token.amount)?);
+            }
+            (None, None) => {
+                release(storage, packet.source_channel.as_str(), token)?;
+                escrow(
+                    storage,
+                    forwarded.packet.destination_channel.as_str(),
+                    token,
+                )?;
+            }
+        }
+    }
+
+    Ok(msgs)
+}
+
+/// Adds the token to the escrowed balance of the channel.
+fn escrow(storage: &mut dyn Storage, channel: &str, token: &Token) -> Result<(), ContractError> {
+    state::ESCROW.update(
+        storage,
+        (channel, &token.denom),
+        |escrowed_bal| -> Result<_, ContractError> {
+            Ok(escrowed_bal.unwrap_or_default() + token.amount)
+        },
+    )?;
+    Ok(())
+}
+
+/// Subtracts the token from the escrowed balance of the channel.
+fn release(storage: &mut dyn Storage, channel: &str, token: &Token) -> Result<(), ContractError> {
+    state::ESCROW.update(
+        storage,
+        (channel, &token.denom),
+        |escrowed_bal| -> Result<_, ContractError> {
+            let escrowed_bal = escrowed_bal.unwrap_or_default();
+            escrowed_bal.checked_sub(token.amount).map_err(|_| {
+                TransferError::insufficient_funds_in_escrow(escrowed_bal, token.amount).into()
+            })
+        },
+    )?;
+    Ok(())
+}
@@ -1,4 +1,18 @@
 //! This module contains the ibc types and handlers used in the contract.
 
+#[cfg(feature = "export")]
+pub mod callbacks;
+#[cfg(feature = "export")]
+pub mod encoding;
+#[cfg(feature = "export")]
+pub mod escrow;
+#[cfg(feature = "export")]
+pub mod forward;
+#[cfg(feature = "export")]
+pub mod policy;
+#[cfg(feature = "export")]
+pub mod rate_limit;
 #[cfg(feature = "export")]
 pub mod relay;
+#[cfg(feature = "export")]
+pub mod voucher;
@@ -0,0 +1,56 @@
+//! This module contains the checks of the token and channel policies set by the contract admin.
+
+use cosmwasm_std::Storage;
+use cw_ibc_lite_shared::types::{
+    error::ContractError,
+    transfer::{error::TransferError, rate_limit::FlowDirection},
+};
+
+use crate::types::state::{self, TokenPolicy};
+
+/// Asserts that the tokens of the given local denoms can be transferred over the channel in the
+/// given direction.
+///
+/// # Errors
+/// Will return an error if the channel is not allowed or disabled in that direction, or any of
+/// the tokens is not allowed.
+pub fn assert_allowed<'a>(
+    storage: &dyn Storage,
+    channel: &str,
+    denoms: impl IntoIterator<Item = &'a str>,
+    direction: FlowDirection,
+) -> Result<(), ContractError> {
+    let allowlists = state::ALLOWLISTS.may_load(storage)?.unwrap_or_default();
+
+    match state::CHANNEL_POLICIES.may_load(storage, channel)? {
+        Some(policy) => {
+            let enabled = match direction {
+                FlowDirection::Send => policy.send_enabled,
+                FlowDirection::Recv => policy.receive_enabled,
+            };
+            if !enabled {
+                return Err(match direction {
+                    FlowDirection::Send => TransferError::SendDisabled(channel.to_string()),
+                    FlowDirection::Recv => TransferError::ReceiveDisabled(channel.to_string()),
+                }
+                .into());
+            }
+        }
+        None if allowlists.channels => {
+            return Err(TransferError::ChannelNotAllowed(channel.to_string()).into());
+        }
+        None => {}
+    }
+
+    for denom in denoms {
+        match state::TOKEN_POLICIES.may_load(storage, denom)? {
+            Some(TokenPolicy::Allowed) => {}
+            None if !allowlists.tokens => {}
+            Some(TokenPolicy::Blocked) | None => {
+                return Err(TransferError::TokenNotAllowed(denom.to_string()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! This module contains the helpers for the rate limits of the transfers of a denom over a
+//! channel.
+
+use cosmwasm_std::{Deps, DepsMut, Env, Storage, Uint128};
+use cw_ibc_lite_shared::types::{
+    error::ContractError,
+    transfer::rate_limit::{FlowDirection, QuotaBase},
+};
+
+use crate::types::state;
+
+/// Adds the amount to the flow of the denom over the channel in every rate limit on them,
+/// starting a new period for those whose period has ended.
+///
+/// # Errors
+/// Will return an error if any of the quotas is exceeded, or the channel value cannot be
+/// queried.
+pub fn allow(
+    deps: DepsMut,
+    env: &Env,
+    channel: &str,
+    denom: &str,
+    direction: FlowDirection,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let Some(mut rate_limits) = state::RATE_LIMITS.may_load(deps.storage, (channel, denom))? else {
+        return Ok(());
+    };
+
+    for rate_limit in &mut rate_limits {
+        if rate_limit.is_expired(env.block.time) {
+            let channel_value =
+                channel_value(deps.as_ref(), channel, denom, rate_limit.quota.base)?;
+            rate_limit.reset(env.block.time, channel_value);
+        } else if rate_limit.flow.channel_value.is_zero() {
+            // NOTE: A quota only allows its zero value capacity while its channel value is
+            // zero, so the value is taken again until there is one, e.g. after the first
+            // transfer on the channel.
+            rate_limit.flow.channel_value =
+                channel_value(deps.as_ref(), channel, denom, rate_limit.quota.base)?;
+        }
+        rate_limit.allow(direction, amount)?;
+    }
+
+    Ok(state::RATE_LIMITS.save(deps.storage, (channel, denom), &rate_limits)?)
+}
+
+/// Removes the amount from the flow of the denom over the channel in every rate limit on them,
+/// used when a transfer is refunded.
+///
+/// # Errors
+/// Will return an error if the rate limits cannot be loaded or saved.
+pub fn undo(
+    storage: &mut dyn Storage,
+    channel: &str,
+    denom: &str,
+    direction: FlowDirection,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let Some(mut rate_limits) = state::RATE_LIMITS.may_load(storage, (channel, denom))? else {
+        return Ok(());
+    };
+
+    for rate_limit in &mut rate_limits {
+        rate_limit.undo(direction, amount);
+    }
+
+    Ok(state::RATE_LIMITS.save(storage, (channel, denom), &rate_limits)?)
+}
+
+/// Returns the value of the denom that the percentages of a quota with the given base apply
+/// to. A voucher received for the first time is not registered yet, so its denom trace is
+/// queried as a native denom, which has no supply.
+///
+/// # Errors
+/// Will return an error if the supply of a voucher, cw20 token or native denom cannot be
+/// queried.
+pub fn channel_value(
+    deps: Deps,
+    channel: &str,
+    denom: &str,
+    base: QuotaBase,
+) -> Result<Uint128, ContractError> {
+    match base {
+        QuotaBase::Escrow => Ok(state::ESCROW
+            .may_load(deps.storage, (channel, denom))?
+            .unwrap_or_default()),
+        QuotaBase::Supply => {
+            let Some(cw20_contract) =
+                crate::ibc::voucher::cw20_contract(deps.storage, deps.api, denom)?
+            else {
+                return Ok(deps.querier.query_supply(denom)?.amount);
+            };
+
+            let info: cw20::TokenInfoResponse = deps
+                .querier
+                .query_wasm_smart(cw20_contract, &cw20::Cw20QueryMsg::TokenInfo {})?;
+            Ok(info.total_supply)
+        }
+    }
+}
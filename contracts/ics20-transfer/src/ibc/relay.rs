@@ -1,27 +1,169 @@
 //! This module contains the callback handlers for the IBC module.
 
-use cosmwasm_std::{Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg, WasmMsg};
+use cosmwasm_std::{
+    Api, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, IbcTimeout, MessageInfo, Response,
+    Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw_ibc_lite_ics26_router::{
+    helpers::IbcLiteRouterContract, types::msg::execute::SendPacketMsg,
+};
 use cw_ibc_lite_shared::{
     types::{
         error::ContractError,
         ibc,
         transfer::{
+            callbacks::TransferOutcome,
+            encoding::PacketEncoding,
             error::TransferError,
-            packet::{Ics20Ack, Ics20Packet},
+            packet::{
+                Ics20Ack, Ics20BatchPacket, Ics20Packet, Ics20PacketData, Ics20PacketV2, Token,
+            },
+            rate_limit::FlowDirection,
         },
     },
     utils,
 };
 
-use crate::types::{keys, state};
+use crate::types::{
+    events, keys,
+    msg::{BatchTransferMsg, ExecuteMsg, TransferMsg, TransferTimeout},
+    state,
+};
+
+/// Returns the router message sending an ICS-20 packet for tokens already received by this
+/// contract. A single token without forwarding hops is sent as an `ics20-1` packet, anything
+/// else as an `ics20-2` packet. The packet data is encoded as the counterparty app on the channel
+/// expects, and only `ics20-1` packet data can be protobuf encoded.
+///
+/// # Errors
+/// Will return an error if the router address cannot be loaded, or the packet data is invalid
+/// or cannot be encoded for the channel.
+pub fn send_packet_msg(
+    storage: &dyn Storage,
+    env: &Env,
+    mut tokens: Vec<Token>,
+    sender: String,
+    transfer_msg: TransferMsg,
+) -> Result<CosmosMsg, ContractError> {
+    let encoding = crate::ibc::encoding::channel_encoding(storage, &transfer_msg.source_channel)?;
+    let (data, version) = match (tokens.len(), transfer_msg.forwarding) {
+        (1, None) => {
+            let token = tokens.remove(0);
+            let packet = Ics20Packet::try_new(
+                token.amount,
+                token.denom,
+                transfer_msg.receiver,
+                sender,
+                transfer_msg.memo,
+            )?;
+            (packet.encode(encoding)?, keys::ICS20_VERSION)
+        }
+        (_, forwarding) => {
+            let packet = Ics20PacketV2 {
+                tokens,
+                receiver: transfer_msg.receiver,
+                sender,
+                memo: transfer_msg.memo,
+                forwarding: forwarding.unwrap_or_default(),
+            };
+            packet.validate()?;
+            (packet.encode(encoding)?, keys::ICS20_V2_VERSION)
+        }
+    };
+
+    router_send_msg(
+        storage,
+        env,
+        transfer_msg.source_channel,
+        transfer_msg.timeout,
+        data,
+        version,
+    )
+}
+
+/// Returns the router message sending an `ics20-batch-1` packet of a batch transfer, for tokens
+/// already received by this contract. The packet data is always JSON encoded.
+///
+/// # Errors
+/// Will return an error if the router address cannot be loaded, or the packet data is invalid.
+pub fn send_batch_packet_msg(
+    storage: &dyn Storage,
+    env: &Env,
+    denom: String,
+    sender: String,
+    batch_msg: BatchTransferMsg,
+) -> Result<CosmosMsg, ContractError> {
+    let packet = Ics20BatchPacket {
+        denom,
+        recipients: batch_msg.recipients,
+        sender,
+        memo: batch_msg.memo,
+    };
+    packet.validate()?;
+    let data = packet.encode(PacketEncoding::Json)?;
+
+    router_send_msg(
+        storage,
+        env,
+        batch_msg.source_channel,
+        batch_msg.timeout,
+        data,
+        keys::ICS20_BATCH_VERSION,
+    )
+}
+
+/// Returns the router message sending a packet with the given data on the channel. If no
+/// timeout is given, the default timeout of the channel is used.
+fn router_send_msg(
+    storage: &dyn Storage,
+    env: &Env,
+    source_channel: String,
+    timeout: Option<TransferTimeout>,
+    data: Binary,
+    version: &str,
+) -> Result<CosmosMsg, ContractError> {
+    let ics26_address = cw_ownable::get_ownership(storage)?
+        .owner
+        .ok_or(ContractError::Unauthorized)?;
+    let ics26_contract = IbcLiteRouterContract::new(ics26_address);
+
+    let source_port = utils::apps::contract_port_id(&env.contract.address)?.into();
+    let timeout = match timeout {
+        Some(TransferTimeout::Relative(seconds)) => {
+            IbcTimeout::with_timestamp(env.block.time.plus_seconds(seconds))
+        }
+        Some(TransferTimeout::Timestamp(timestamp)) => IbcTimeout::with_timestamp(timestamp),
+        Some(TransferTimeout::Height(block)) => IbcTimeout::with_block(block),
+        None => {
+            let seconds = state::DEFAULT_TIMEOUTS
+                .may_load(storage, &source_channel)?
+                .unwrap_or(keys::DEFAULT_TIMEOUT_SECONDS);
+            IbcTimeout::with_timestamp(env.block.time.plus_seconds(seconds))
+        }
+    };
+
+    let send_packet_msg = SendPacketMsg {
+        source_port,
+        source_channel,
+        dest_port: keys::DEFAULT_PORT_ID.to_string(),
+        dest_channel: None, // NOTE: Router will determine the dest channel.
+        data,
+        timeout,
+        version: version.to_string(),
+    };
+
+    Ok(ics26_contract.call(send_packet_msg)?)
+}
 
 /// Handles the callback for the `on_send_packet` IBC handler.
 ///
 /// # Errors
-/// Will return an error if the sender is not this contract or the balance cannot be recorded.
+/// Will return an error if the sender is not this contract, the packet data does not match the
+/// version, the transfer is not allowed by the policies, a rate limit is exceeded, or the
+/// balance cannot be recorded.
 #[allow(clippy::needless_pass_by_value)]
 pub fn on_send_packet(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     packet: ibc::Packet,
@@ -33,36 +175,117 @@ pub fn on_send_packet(
     if sender != env.contract.address.as_str() {
         return Err(ContractError::Unauthorized);
     }
-    if version != keys::ICS20_VERSION {
-        return Err(TransferError::InvalidVersion.into());
-    }
 
     let port_id = utils::apps::contract_port_id(&env.contract.address)?;
     if packet.source_port != port_id {
         return Err(TransferError::unexpected_port_id(port_id, packet.source_port).into());
     }
 
-    let ics20_packet: Ics20Packet = cosmwasm_std::from_json(packet.data)?;
-    ics20_packet.validate()?;
+    let encoding =
+        crate::ibc::encoding::channel_encoding(deps.storage, packet.source_channel.as_str())?;
+    let data = Ics20PacketData::decode(&packet.data, encoding)?;
+    // NOTE: The tokens of a batch are recorded in flight as a single token sent to many
+    // recipients.
+    let batch = match (version.as_str(), &data) {
+        (keys::ICS20_VERSION, Ics20PacketData::V1(_))
+        | (keys::ICS20_V2_VERSION, Ics20PacketData::V2(_)) => None,
+        (keys::ICS20_BATCH_VERSION, Ics20PacketData::Batch(batch)) => Some((
+            Token {
+                denom: batch.denom.clone(),
+                amount: batch.total()?,
+            },
+            batch.recipients.clone(),
+        )),
+        _ => return Err(TransferError::InvalidVersion.into()),
+    };
+    let transfers = data.into_transfers()?;
+    let tokens: Vec<Token> = transfers
+        .iter()
+        .flat_map(|ics20_packet| ics20_packet.tokens.clone())
+        .collect();
+    crate::ibc::policy::assert_allowed(
+        deps.storage,
+        packet.source_channel.as_str(),
+        tokens.iter().map(|token| token.denom.as_str()),
+        FlowDirection::Send,
+    )?;
 
-    // Add amount to the escrowed balance.
-    state::ESCROW.update(
+    let send_events: Vec<_> = transfers
+        .iter()
+        .flat_map(|ics20_packet| events::transfer::send(&packet, ics20_packet))
+        .collect();
+    let (receiver, in_flight_tokens, recipients) = match batch {
+        Some((total, recipients)) => (String::new(), vec![total], recipients),
+        None => (transfers[0].receiver.clone(), tokens.clone(), vec![]),
+    };
+    state::IN_FLIGHT.save(
         deps.storage,
-        (packet.source_channel.as_str(), &ics20_packet.denom),
-        |escrowed_bal| -> Result<_, ContractError> {
-            let mut escrowed_bal = escrowed_bal.unwrap_or_default();
-            escrowed_bal += ics20_packet.amount;
-            Ok(escrowed_bal)
+        (
+            transfers[0].sender.as_str(),
+            packet.source_channel.as_str(),
+            packet.sequence.value(),
+        ),
+        &state::InFlightTransfer {
+            channel: packet.source_channel.to_string(),
+            sequence: packet.sequence.value(),
+            receiver,
+            tokens: in_flight_tokens,
+            recipients,
+            timeout: packet.timeout.clone(),
         },
     )?;
 
-    Ok(Response::default())
+    let mut burn_msgs = vec![];
+    for token in tokens {
+        crate::ibc::rate_limit::allow(
+            deps.branch(),
+            &env,
+            packet.source_channel.as_str(),
+            &token.denom,
+            FlowDirection::Send,
+            token.amount,
+        )?;
+
+        // Burn the vouchers of foreign tokens returning to their source, they are minted again
+        // when the tokens come back. Vouchers sent on any other channel are escrowed.
+        if let Some(voucher) = crate::ibc::voucher::returning(
+            deps.storage,
+            packet.source_port.as_str(),
+            packet.source_channel.as_str(),
+            &token.denom,
+        )? {
+            burn_msgs.push(crate::ibc::voucher::burn_msg(&voucher, token.amount)?);
+            continue;
+        }
+
+        // Add amount to the escrowed balance.
+        state::ESCROW.update(
+            deps.storage,
+            (packet.source_channel.as_str(), &token.denom),
+            |escrowed_bal| -> Result<_, ContractError> {
+                let mut escrowed_bal = escrowed_bal.unwrap_or_default();
+                escrowed_bal += token.amount;
+                Ok(escrowed_bal)
+            },
+        )?;
+    }
+
+    crate::ibc::encoding::register_sent(deps.storage, &packet, encoding)?;
+    crate::ibc::forward::register_pending(deps.storage, &packet)?;
+    crate::ibc::callbacks::register_pending(deps.storage, &packet)?;
+
+    Ok(Response::new()
+        .add_messages(burn_msgs)
+        .add_events(send_events))
 }
 
-/// Handles the callback for the `on_recv_packet` IBC handler.
+/// Handles the callback for the `on_recv_packet` IBC handler. The tokens are received in
+/// [`receive_packet_tokens`], called by this contract as a submessage so that an error
+/// acknowledgement is written if any of them cannot be received. If the tokens are forwarded,
+/// the acknowledgement is written once the forwarded packet is acknowledged or times out.
 ///
 /// # Errors
-/// Will return an error if new balance cannot be transferred to the receiver.
+/// Will return an error if the packet is not for this contract.
 #[allow(clippy::needless_pass_by_value)]
 pub fn on_recv_packet(
     deps: DepsMut,
@@ -71,56 +294,235 @@ pub fn on_recv_packet(
     packet: ibc::Packet,
     _relayer: String,
 ) -> Result<Response, ContractError> {
+    // NOTE: Counterparties send packets to the `transfer` port, which the router may map to
+    // this contract besides its own port.
     let port_id = utils::apps::contract_port_id(&env.contract.address)?;
-    if packet.destination_port != port_id {
+    if packet.destination_port != port_id && packet.destination_port != keys::DEFAULT_PORT_ID {
         return Err(TransferError::unexpected_port_id(port_id, packet.destination_port).into());
     }
 
-    let ics20_packet: Ics20Packet = cosmwasm_std::from_json(packet.data)?;
-    let base_denom = utils::transfer::parse_voucher_denom(
-        &ics20_packet.denom,
-        packet.source_port.as_str(),
-        packet.source_channel.as_str(),
-    )?;
+    // NOTE: Invalid packet data or forwarding instructions fail in `receive_packet_tokens`.
+    let encoding =
+        crate::ibc::encoding::channel_encoding(deps.storage, packet.destination_channel.as_str())?;
+    let is_forwarded = Ics20PacketData::decode(&packet.data, encoding)
+        .ok()
+        .and_then(|data| data.into_v2().ok())
+        .and_then(|ics20_packet| ics20_packet.next_hop().ok().flatten())
+        .is_some();
+
+    let receive_msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: cosmwasm_std::to_json_binary(&ExecuteMsg::ReceivePacketTokens { packet })?,
+        funds: vec![],
+    };
+    let receive_submsg = SubMsg::reply_on_error(receive_msg, keys::reply::ON_RECV_PACKET_TRANSFER);
+
+    // NOTE: The acknowledgement will be overwritten by the SubMsg reply in case of error.
+    let response = Response::new().add_submessage(receive_submsg);
+    if is_forwarded {
+        return Ok(response);
+    }
+    Ok(response.set_data(Ics20Ack::success().to_vec()))
+}
 
-    // Subtract amount from the escrowed balance.
-    state::ESCROW.update(
+/// Receives all tokens of an incoming packet. Tokens returning to this chain are released from
+/// escrow, and foreign tokens are minted as vouchers. If the packet is to be forwarded, the
+/// tokens are kept by this contract and sent on to the next hop. Otherwise, if the memo has a
+/// destination callback, the receiver contract is executed after it receives the tokens, with
+/// the native tokens as funds. Each recipient of a batch receives its tokens, and none of them
+/// does if any of the transfers fails.
+///
+/// # Errors
+/// Will return an error if the packet data is invalid, the transfer is not allowed by the
+/// policies, a rate limit is exceeded, any of the tokens cannot be received or forwarded, or
+/// the destination callback fails.
+pub fn receive_packet_tokens(
+    mut deps: DepsMut,
+    env: &Env,
+    packet: ibc::Packet,
+) -> Result<Response, ContractError> {
+    let encoding =
+        crate::ibc::encoding::channel_encoding(deps.storage, packet.destination_channel.as_str())?;
+    let transfers = Ics20PacketData::decode(&packet.data, encoding)?.into_transfers()?;
+
+    let mut response = Response::new();
+    for ics20_packet in transfers {
+        let transfer_response = receive_transfer(deps.branch(), env, &packet, ics20_packet)?;
+        response = response
+            .add_submessages(transfer_response.messages)
+            .add_events(transfer_response.events);
+    }
+    Ok(response)
+}
+
+/// Receives the tokens of a single transfer of an incoming packet.
+fn receive_transfer(
+    mut deps: DepsMut,
+    env: &Env,
+    packet: &ibc::Packet,
+    ics20_packet: Ics20PacketV2,
+) -> Result<Response, ContractError> {
+    let receive_events = events::transfer::receive(packet, &ics20_packet);
+    let next_hop = ics20_packet.next_hop()?;
+    // NOTE: The callback of a forwarded packet is executed on its final destination.
+    let callback = if next_hop.is_none() {
+        ics20_packet.destination_callback()?
+    } else {
+        None
+    };
+    let receiver = if next_hop.is_some() {
+        env.contract.address.to_string()
+    } else {
+        ics20_packet.receiver
+    };
+
+    let mut msgs = vec![];
+    let mut local_tokens = vec![];
+    let mut callback_funds = vec![];
+    for token in ics20_packet.tokens {
+        let Ok(base_denom) = utils::transfer::parse_voucher_denom(
+            &token.denom,
+            packet.source_port.as_str(),
+            packet.source_channel.as_str(),
+        ) else {
+            let denom_trace = voucher_denom_trace(env, packet, &token.denom)?;
+            // NOTE: The rate limits are checked before a new voucher is registered.
+            crate::ibc::rate_limit::allow(
+                deps.branch(),
+                env,
+                packet.destination_channel.as_str(),
+                &denom_trace,
+                FlowDirection::Recv,
+                token.amount,
+            )?;
+            msgs.extend(mint_vouchers(
+                deps.branch(),
+                env,
+                &denom_trace,
+                token.amount,
+                receiver.clone(),
+            )?);
+            local_tokens.push(Token {
+                denom: denom_trace,
+                amount: token.amount,
+            });
+            continue;
+        };
+
+        crate::ibc::rate_limit::allow(
+            deps.branch(),
+            env,
+            packet.destination_channel.as_str(),
+            base_denom,
+            FlowDirection::Recv,
+            token.amount,
+        )?;
+
+        // Subtract amount from the escrowed balance.
+        state::ESCROW.update(
+            deps.storage,
+            (packet.destination_channel.as_str(), base_denom),
+            |escrowed_bal| -> Result<_, ContractError> {
+                let mut escrowed_bal = escrowed_bal.unwrap_or_default();
+                escrowed_bal = escrowed_bal.checked_sub(token.amount).map_err(|_| {
+                    TransferError::insufficient_funds_in_escrow(escrowed_bal, token.amount)
+                })?;
+                Ok(escrowed_bal)
+            },
+        )?;
+
+        // NOTE: Forwarded tokens stay with this contract, and native tokens are sent along
+        // with the destination callback.
+        if next_hop.is_none() {
+            let is_native =
+                crate::ibc::voucher::cw20_contract(deps.storage, deps.api, base_denom)?.is_none();
+            if callback.is_some() && is_native {
+                callback_funds.push(Coin::new(token.amount, base_denom));
+            } else {
+                msgs.push(transfer_tokens_msg(
+                    deps.storage,
+                    deps.api,
+                    base_denom,
+                    receiver.clone(),
+                    token.amount,
+                )?);
+            }
+        }
+        local_tokens.push(Token {
+            denom: base_denom.to_string(),
+            amount: token.amount,
+        });
+    }
+
+    crate::ibc::policy::assert_allowed(
         deps.storage,
-        (packet.destination_channel.as_str(), base_denom),
-        |escrowed_bal| -> Result<_, ContractError> {
-            let mut escrowed_bal = escrowed_bal.unwrap_or_default();
-            escrowed_bal = escrowed_bal.checked_sub(ics20_packet.amount).map_err(|_| {
-                TransferError::insufficient_funds_in_escrow(escrowed_bal, ics20_packet.amount)
-            })?;
-            Ok(escrowed_bal)
-        },
+        packet.destination_channel.as_str(),
+        local_tokens.iter().map(|token| token.denom.as_str()),
+        FlowDirection::Recv,
     )?;
 
-    // TODO: move to helper
-    let cw20_msg: CosmosMsg = WasmMsg::Execute {
-        contract_addr: base_denom.to_string(),
-        msg: cosmwasm_std::to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
-            recipient: ics20_packet.receiver,
-            amount: ics20_packet.amount,
-        })?,
-        funds: vec![],
+    if let Some(next_hop) = next_hop {
+        msgs.push(crate::ibc::forward::forward_tokens_msg(
+            deps.storage,
+            env,
+            packet.clone(),
+            next_hop,
+            local_tokens,
+        )?);
+    }
+
+    if let Some(callback) = callback {
+        // NOTE: Funds must be sorted by denom.
+        callback_funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+        msgs.push(
+            WasmMsg::Execute {
+                contract_addr: callback.contract,
+                msg: callback.msg,
+                funds: callback_funds,
+            }
+            .into(),
+        );
     }
-    .into();
-    let reply_args = state::RecvPacketReplyPayload {
-        channel_id: packet.destination_channel.into(),
-        denom: base_denom.to_string(),
-        amount: ics20_packet.amount,
-    };
-    let cw20_submsg = SubMsg::reply_on_error(cw20_msg, keys::reply::ON_RECV_PACKET_CW20_TRANSFER)
-        .with_payload(cosmwasm_std::to_json_binary(&reply_args)?);
 
-    // NOTE: The success acknowledgement will be overwritten by the SubMsg reply in case of error.
     Ok(Response::new()
-        .add_submessage(cw20_submsg)
-        .set_data(Ics20Ack::success().to_vec()))
+        .add_messages(msgs)
+        .add_events(receive_events))
+}
+
+/// Returns the denom trace of the vouchers of a foreign token received in the packet, which is
+/// its denom prefixed with the port of this contract and the destination channel. This is the
+/// port packets are sent from, so the counterparty recognizes the prefix when the vouchers are
+/// sent back.
+fn voucher_denom_trace(
+    env: &Env,
+    packet: &ibc::Packet,
+    denom: &str,
+) -> Result<String, ContractError> {
+    Ok(format!(
+        "{}/{}/{denom}",
+        utils::apps::contract_port_id(&env.contract.address)?.as_str(),
+        packet.destination_channel.as_str(),
+    ))
+}
+
+/// Returns the messages minting vouchers of the denom trace to the receiver, instantiating the
+/// voucher contract first if needed.
+fn mint_vouchers(
+    deps: DepsMut,
+    env: &Env,
+    denom_trace: &str,
+    amount: Uint128,
+    receiver: String,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let (voucher, instantiate_msg) =
+        crate::ibc::voucher::load_or_instantiate(deps, env, denom_trace)?;
+
+    let mint_msg = crate::ibc::voucher::mint_msg(&voucher, receiver, amount)?;
+    Ok(instantiate_msg.into_iter().chain([mint_msg]).collect())
 }
 
-/// Handles the callback for the `on_acknowledgement_packet` IBC handler.
+/// Handles the callback for the `on_acknowledgement_packet` IBC handler. The callback contract
+/// registered by the sender, if any, is notified of the outcome.
 ///
 /// # Errors
 /// Will return an error if the acknowledgement cannot be processed.
@@ -134,15 +536,39 @@ pub fn on_acknowledgement_packet(
 ) -> Result<Response, ContractError> {
     match cosmwasm_std::from_json(ack)? {
         Ics20Ack::Result(result) => {
-            on_acknowledgement_packet::success(deps, env, info, packet, result, relayer)
+            let transfers = take_in_flight(deps.storage, &packet)?;
+            let ack_events: Vec<_> = transfers
+                .iter()
+                .flat_map(|ics20_packet| events::transfer::ack_success(&packet, ics20_packet))
+                .collect();
+            let callback_msg =
+                crate::ibc::callbacks::take_msg(deps.storage, &packet, TransferOutcome::Success)?;
+            let response =
+                on_acknowledgement_packet::success(deps, env, info, packet, result, relayer)?;
+            Ok(response
+                .add_events(ack_events)
+                .add_submessages(callback_msg))
         }
         Ics20Ack::Error(err) => {
-            on_acknowledgement_packet::error(deps, env, info, packet, err, relayer)
+            let transfers = take_in_flight(deps.storage, &packet)?;
+            let ack_events: Vec<_> = transfers
+                .iter()
+                .flat_map(|ics20_packet| events::transfer::ack_error(&packet, ics20_packet, &err))
+                .collect();
+            let outcome = TransferOutcome::Error { error: err.clone() };
+            let callback_msg = crate::ibc::callbacks::take_msg(deps.storage, &packet, outcome)?;
+            let response = on_acknowledgement_packet::error(
+                deps, env, info, packet, &transfers, err, relayer,
+            )?;
+            Ok(response
+                .add_events(ack_events)
+                .add_submessages(callback_msg))
         }
     }
 }
 
-/// Handles the callback for the `on_timeout_packet` IBC handler.
+/// Handles the callback for the `on_timeout_packet` IBC handler. The callback contract
+/// registered by the sender, if any, is notified of the timeout.
 ///
 /// # Errors
 /// Will return an error if the timeout cannot be processed and tokens refunded.
@@ -153,23 +579,93 @@ pub fn on_timeout_packet(
     packet: ibc::Packet,
     relayer: String,
 ) -> Result<Response, ContractError> {
-    on_acknowledgement_packet::error(deps, env, info, packet, "timeout".to_string(), relayer)
+    let transfers = take_in_flight(deps.storage, &packet)?;
+    let timeout_events: Vec<_> = transfers
+        .iter()
+        .flat_map(|ics20_packet| events::transfer::timeout(&packet, ics20_packet))
+        .collect();
+    let callback_msg =
+        crate::ibc::callbacks::take_msg(deps.storage, &packet, TransferOutcome::Timeout)?;
+    let response = on_acknowledgement_packet::error(
+        deps,
+        env,
+        info,
+        packet,
+        &transfers,
+        "timeout".to_string(),
+        relayer,
+    )?;
+    Ok(response
+        .add_events(timeout_events)
+        .add_submessages(callback_msg))
+}
+
+/// Removes the in-flight transfer of a packet sent by this contract, and returns its data
+/// decoded in the encoding it was sent in, with one transfer per recipient of a batch.
+fn take_in_flight(
+    storage: &mut dyn Storage,
+    packet: &ibc::Packet,
+) -> Result<Vec<Ics20PacketV2>, ContractError> {
+    let encoding = crate::ibc::encoding::take_sent(storage, packet)?;
+    let transfers = Ics20PacketData::decode(&packet.data, encoding)?.into_transfers()?;
+    state::IN_FLIGHT.remove(
+        storage,
+        (
+            transfers[0].sender.as_str(),
+            packet.source_channel.as_str(),
+            packet.sequence.value(),
+        ),
+    );
+
+    Ok(transfers)
+}
+
+/// Returns the message that transfers tokens held by this contract to the recipient. The denom
+/// is a cw20 contract address if it is a valid address, the denom trace of a voucher, or a
+/// native denom otherwise.
+///
+/// # Errors
+/// Will return an error if the voucher cannot be loaded, or the cw20 transfer message cannot be
+/// serialized.
+pub fn transfer_tokens_msg(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    denom: &str,
+    recipient: String,
+    amount: Uint128,
+) -> Result<CosmosMsg, ContractError> {
+    let Some(cw20_contract) = crate::ibc::voucher::cw20_contract(storage, api, denom)? else {
+        return Ok(BankMsg::Send {
+            to_address: recipient,
+            amount: vec![Coin::new(amount, denom)],
+        }
+        .into());
+    };
+
+    Ok(WasmMsg::Execute {
+        contract_addr: cw20_contract.into_string(),
+        msg: cosmwasm_std::to_json_binary(&cw20::Cw20ExecuteMsg::Transfer { recipient, amount })?,
+        funds: vec![],
+    }
+    .into())
 }
 
 mod on_acknowledgement_packet {
     use cosmwasm_std::HexBinary;
 
+    use crate::ibc::forward;
+
     use super::{
-        ibc, state, utils, Binary, ContractError, CosmosMsg, DepsMut, Env, Ics20Ack, Ics20Packet,
-        MessageInfo, Response, TransferError, WasmMsg,
+        events, ibc, state, utils, Binary, ContractError, DepsMut, Env, FlowDirection, Ics20Ack,
+        Ics20PacketV2, MessageInfo, Response, TransferError,
     };
 
     #[allow(clippy::needless_pass_by_value)]
     pub fn success(
-        _deps: DepsMut,
+        deps: DepsMut,
         _env: Env,
         _info: MessageInfo,
-        _packet: ibc::Packet,
+        packet: ibc::Packet,
         result: Binary,
         _relayer: String,
     ) -> Result<Response, ContractError> {
@@ -179,6 +675,13 @@ mod on_acknowledgement_packet {
             );
         }
 
+        // Acknowledge the forwarded incoming packet, if any.
+        if let Some(forwarded) = forward::take(deps.storage, &packet)? {
+            let write_ack_msg =
+                forward::write_ack_msg(deps.storage, forwarded, &Ics20Ack::success())?;
+            return Ok(Response::new().add_message(write_ack_msg));
+        }
+
         Ok(Response::default())
     }
 
@@ -188,40 +691,83 @@ mod on_acknowledgement_packet {
         env: Env,
         _info: MessageInfo,
         packet: ibc::Packet,
-        _err: String,
+        transfers: &[Ics20PacketV2],
+        err: String,
         _relayer: String,
     ) -> Result<Response, ContractError> {
-        let ics20_packet: Ics20Packet = cosmwasm_std::from_json(packet.data)?;
+        // The tokens of a forwarded incoming packet are refunded on the source chain instead.
+        if let Some(forwarded) = forward::take(deps.storage, &packet)? {
+            let revert_msgs = forward::revert(deps.storage, &env, &packet, &forwarded)?;
+            let write_ack_msg =
+                forward::write_ack_msg(deps.storage, forwarded, &Ics20Ack::error(err))?;
+            return Ok(Response::new()
+                .add_messages(revert_msgs)
+                .add_message(write_ack_msg));
+        }
 
         let port_id = utils::apps::contract_port_id(&env.contract.address)?;
         if packet.source_port != port_id {
             return Err(TransferError::unexpected_port_id(port_id, packet.source_port).into());
         }
 
-        // Refund the escrowed balance.
-        state::ESCROW.update(
-            deps.storage,
-            (packet.source_channel.as_str(), &ics20_packet.denom),
-            |escrowed_bal| -> Result<_, ContractError> {
-                let mut escrowed_bal = escrowed_bal.unwrap_or_default();
-                escrowed_bal = escrowed_bal.checked_sub(ics20_packet.amount).map_err(|_| {
-                    TransferError::insufficient_funds_in_escrow(escrowed_bal, ics20_packet.amount)
-                })?;
-                Ok(escrowed_bal)
-            },
-        )?;
+        let mut refund_msgs = vec![];
+        let mut refund_events = vec![];
+        let refunds = transfers.iter().flat_map(|ics20_packet| {
+            ics20_packet
+                .tokens
+                .iter()
+                .map(move |token| (ics20_packet, token))
+        });
+        for (ics20_packet, token) in refunds {
+            refund_events.push(events::transfer::refund(&packet, ics20_packet, token));
+            crate::ibc::rate_limit::undo(
+                deps.storage,
+                packet.source_channel.as_str(),
+                &token.denom,
+                FlowDirection::Send,
+                token.amount,
+            )?;
+
+            // Mint the burned vouchers back to the sender.
+            if let Some(voucher) = crate::ibc::voucher::returning(
+                deps.storage,
+                packet.source_port.as_str(),
+                packet.source_channel.as_str(),
+                &token.denom,
+            )? {
+                refund_msgs.push(crate::ibc::voucher::mint_msg(
+                    &voucher,
+                    ics20_packet.sender.clone(),
+                    token.amount,
+                )?);
+                continue;
+            }
+
+            // Refund the escrowed balance.
+            state::ESCROW.update(
+                deps.storage,
+                (packet.source_channel.as_str(), &token.denom),
+                |escrowed_bal| -> Result<_, ContractError> {
+                    let mut escrowed_bal = escrowed_bal.unwrap_or_default();
+                    escrowed_bal = escrowed_bal.checked_sub(token.amount).map_err(|_| {
+                        TransferError::insufficient_funds_in_escrow(escrowed_bal, token.amount)
+                    })?;
+                    Ok(escrowed_bal)
+                },
+            )?;
 
-        let cw20_msg: CosmosMsg = WasmMsg::Execute {
-            contract_addr: ics20_packet.denom,
-            msg: cosmwasm_std::to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
-                recipient: ics20_packet.sender,
-                amount: ics20_packet.amount,
-            })?,
-            funds: vec![],
+            refund_msgs.push(super::transfer_tokens_msg(
+                deps.storage,
+                deps.api,
+                &token.denom,
+                ics20_packet.sender.clone(),
+                token.amount,
+            )?);
         }
-        .into();
 
         // NOTE: If the refund fails, the packet cannot be acknowledged and must be retried.
-        Ok(Response::new().add_message(cw20_msg))
+        Ok(Response::new()
+            .add_messages(refund_msgs)
+            .add_events(refund_events))
     }
 }
@@ -0,0 +1,139 @@
+//! This module contains the helpers for the cw20 vouchers minted for foreign tokens.
+
+use cosmwasm_std::{
+    Addr, Api, CosmosMsg, DepsMut, Env, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw_ibc_lite_shared::{
+    types::{
+        error::ContractError,
+        transfer::{denom::DenomTrace, error::TransferError},
+    },
+    utils,
+};
+use sha2::{Digest, Sha256};
+
+use crate::types::{keys, state};
+
+/// Returns the voucher contract of the denom trace. If the voucher does not exist yet, its
+/// address and the denom trace are registered and the message instantiating it is returned as
+/// well.
+///
+/// # Errors
+/// Will return an error if no voucher code id is configured, in which case foreign tokens are
+/// not accepted, or if the denom trace is invalid.
+pub fn load_or_instantiate(
+    deps: DepsMut,
+    env: &Env,
+    denom_trace: &str,
+) -> Result<(Addr, Option<CosmosMsg>), ContractError> {
+    if let Some(voucher) = state::VOUCHERS.may_load(deps.storage, denom_trace)? {
+        return Ok((voucher, None));
+    }
+
+    let trace: DenomTrace = denom_trace.parse()?;
+    let code_id = state::VOUCHER_CODE_ID
+        .may_load(deps.storage)?
+        .ok_or(TransferError::NoForeignTokens)?;
+    let checksum = deps.querier.query_wasm_code_info(code_id)?.checksum;
+    let salt = Sha256::digest(denom_trace.as_bytes()).to_vec();
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let voucher = deps.api.addr_humanize(
+        &cosmwasm_std::instantiate2_address(checksum.as_slice(), &creator, &salt)
+            .map_err(|e| StdError::generic_err(e.to_string()))?,
+    )?;
+
+    state::VOUCHERS.save(deps.storage, denom_trace, &voucher)?;
+    state::VOUCHER_TRACES.save(deps.storage, &voucher, &denom_trace.to_string())?;
+    state::DENOM_TRACES.save(deps.storage, &trace.hash(), &trace)?;
+
+    let instantiate_msg = cw20_base::msg::InstantiateMsg {
+        name: voucher_name(denom_trace),
+        symbol: keys::VOUCHER_SYMBOL.to_string(),
+        decimals: keys::VOUCHER_DECIMALS,
+        initial_balances: vec![],
+        mint: Some(cw20::MinterResponse {
+            minter: env.contract.address.to_string(),
+            cap: None,
+        }),
+        marketing: None,
+    };
+    let instantiate2 = WasmMsg::Instantiate2 {
+        admin: Some(env.contract.address.to_string()),
+        code_id,
+        label: format!("ics20 voucher {denom_trace}"),
+        msg: cosmwasm_std::to_json_binary(&instantiate_msg)?,
+        funds: vec![],
+        salt: salt.into(),
+    };
+
+    Ok((voucher, Some(instantiate2.into())))
+}
+
+/// Returns the voucher contract of the denom if it is the trace of a voucher received on the
+/// given port and channel. Such vouchers return to their source when sent on the same channel,
+/// so they are burned instead of escrowed.
+///
+/// # Errors
+/// Will return an error if the voucher cannot be loaded.
+pub fn returning(
+    storage: &dyn Storage,
+    port_id: &str,
+    channel_id: &str,
+    denom: &str,
+) -> StdResult<Option<Addr>> {
+    if utils::transfer::parse_voucher_denom(denom, port_id, channel_id).is_err() {
+        return Ok(None);
+    }
+
+    state::VOUCHERS.may_load(storage, denom)
+}
+
+/// Returns the cw20 contract of a local denom: the voucher contract of a denom trace, or the
+/// denom itself if it is a valid address. Native denoms have none.
+///
+/// # Errors
+/// Will return an error if the voucher cannot be loaded.
+pub fn cw20_contract(storage: &dyn Storage, api: &dyn Api, denom: &str) -> StdResult<Option<Addr>> {
+    if let Some(voucher) = state::VOUCHERS.may_load(storage, denom)? {
+        return Ok(Some(voucher));
+    }
+
+    Ok(api.addr_validate(denom).ok())
+}
+
+/// Returns the message minting vouchers to the recipient.
+///
+/// # Errors
+/// Will return an error if the message cannot be serialized.
+pub fn mint_msg(voucher: &Addr, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: voucher.to_string(),
+        msg: cosmwasm_std::to_json_binary(&cw20::Cw20ExecuteMsg::Mint { recipient, amount })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Returns the message burning vouchers held by this contract.
+///
+/// # Errors
+/// Will return an error if the message cannot be serialized.
+pub fn burn_msg(voucher: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: voucher.to_string(),
+        msg: cosmwasm_std::to_json_binary(&cw20::Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Returns the cw20 token name of a voucher, which is the base denom of the trace.
+/// `cw20-base` requires names between 3 and 50 characters.
+fn voucher_name(denom_trace: &str) -> String {
+    let base_denom = denom_trace.rsplit('/').next().unwrap_or_default();
+    if (3..=50).contains(&base_denom.chars().count()) {
+        base_denom.to_string()
+    } else {
+        keys::VOUCHER_DEFAULT_NAME.to_string()
+    }
+}
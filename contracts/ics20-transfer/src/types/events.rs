@@ -1 +1,172 @@
 //! `cw-ibc-lite-ics20-transfer` Event Keys
+
+/// `EVENT_TYPE_SEND` is the event type for a token sent in a packet
+pub const EVENT_TYPE_SEND: &str = "ics20_send";
+/// `EVENT_TYPE_RECEIVE` is the event type for a token received in a packet
+pub const EVENT_TYPE_RECEIVE: &str = "ics20_receive";
+/// `EVENT_TYPE_ACK_SUCCESS` is the event type for a token of a successfully acknowledged packet
+pub const EVENT_TYPE_ACK_SUCCESS: &str = "ics20_ack_success";
+/// `EVENT_TYPE_ACK_ERROR` is the event type for a token of a packet acknowledged with an error
+pub const EVENT_TYPE_ACK_ERROR: &str = "ics20_ack_error";
+/// `EVENT_TYPE_TIMEOUT` is the event type for a token of a timed out packet
+pub const EVENT_TYPE_TIMEOUT: &str = "ics20_timeout";
+/// `EVENT_TYPE_REFUND` is the event type for a token refunded to the sender
+pub const EVENT_TYPE_REFUND: &str = "ics20_refund";
+/// `EVENT_TYPE_SET_ESCROW` is the event type for an escrowed amount set by the contract admin
+pub const EVENT_TYPE_SET_ESCROW: &str = "ics20_set_escrow";
+/// `EVENT_TYPE_RETURN_FUNDS` is the event type for stranded funds returned by the contract admin
+pub const EVENT_TYPE_RETURN_FUNDS: &str = "ics20_return_funds";
+
+/// `ATTRIBUTE_KEY_DENOM` is the attribute key for the denom of the token
+pub const ATTRIBUTE_KEY_DENOM: &str = "denom";
+/// `ATTRIBUTE_KEY_AMOUNT` is the attribute key for the amount of the token
+pub const ATTRIBUTE_KEY_AMOUNT: &str = "amount";
+/// `ATTRIBUTE_KEY_SENDER` is the attribute key for the sender of the packet
+pub const ATTRIBUTE_KEY_SENDER: &str = "sender";
+/// `ATTRIBUTE_KEY_RECEIVER` is the attribute key for the receiver of the packet
+pub const ATTRIBUTE_KEY_RECEIVER: &str = "receiver";
+/// `ATTRIBUTE_KEY_CHANNEL` is the attribute key for the local channel of the packet
+pub const ATTRIBUTE_KEY_CHANNEL: &str = "channel";
+/// `ATTRIBUTE_KEY_SEQUENCE` is the attribute key for the packet sequence
+pub const ATTRIBUTE_KEY_SEQUENCE: &str = "sequence";
+/// `ATTRIBUTE_KEY_ERROR` is the attribute key for the error of an acknowledgement
+pub const ATTRIBUTE_KEY_ERROR: &str = "error";
+/// `ATTRIBUTE_KEY_PREVIOUS_AMOUNT` is the attribute key for the escrowed amount before it was set
+pub const ATTRIBUTE_KEY_PREVIOUS_AMOUNT: &str = "previous_amount";
+/// `ATTRIBUTE_KEY_RECIPIENT` is the attribute key for the recipient of returned funds
+pub const ATTRIBUTE_KEY_RECIPIENT: &str = "recipient";
+
+/// Contains the event messages of the tokens of ICS-20 packets. One event is emitted per token,
+/// in the denom carried by the packet.
+pub mod transfer {
+    use cosmwasm_std::{Attribute, Event};
+    use cw_ibc_lite_shared::types::{
+        ibc,
+        transfer::packet::{Ics20PacketV2, Token},
+    };
+
+    /// `send` is the event message for the tokens of a packet sent by this contract
+    #[must_use]
+    pub fn send(packet: &ibc::Packet, data: &Ics20PacketV2) -> Vec<Event> {
+        token_events(
+            super::EVENT_TYPE_SEND,
+            packet.source_channel.as_str(),
+            packet,
+            data,
+        )
+    }
+
+    /// `receive` is the event message for the tokens of a packet received by this contract
+    #[must_use]
+    pub fn receive(packet: &ibc::Packet, data: &Ics20PacketV2) -> Vec<Event> {
+        token_events(
+            super::EVENT_TYPE_RECEIVE,
+            packet.destination_channel.as_str(),
+            packet,
+            data,
+        )
+    }
+
+    /// `ack_success` is the event message for the tokens of a successfully acknowledged packet
+    #[must_use]
+    pub fn ack_success(packet: &ibc::Packet, data: &Ics20PacketV2) -> Vec<Event> {
+        token_events(
+            super::EVENT_TYPE_ACK_SUCCESS,
+            packet.source_channel.as_str(),
+            packet,
+            data,
+        )
+    }
+
+    /// `ack_error` is the event message for the tokens of a packet acknowledged with an error
+    #[must_use]
+    pub fn ack_error(packet: &ibc::Packet, data: &Ics20PacketV2, error: &str) -> Vec<Event> {
+        token_events(
+            super::EVENT_TYPE_ACK_ERROR,
+            packet.source_channel.as_str(),
+            packet,
+            data,
+        )
+        .into_iter()
+        .map(|event| event.add_attribute(super::ATTRIBUTE_KEY_ERROR, error))
+        .collect()
+    }
+
+    /// `timeout` is the event message for the tokens of a timed out packet
+    #[must_use]
+    pub fn timeout(packet: &ibc::Packet, data: &Ics20PacketV2) -> Vec<Event> {
+        token_events(
+            super::EVENT_TYPE_TIMEOUT,
+            packet.source_channel.as_str(),
+            packet,
+            data,
+        )
+    }
+
+    /// `refund` is the event message for a token of a failed packet refunded to its sender
+    #[must_use]
+    pub fn refund(packet: &ibc::Packet, data: &Ics20PacketV2, token: &Token) -> Event {
+        token_event(
+            super::EVENT_TYPE_REFUND,
+            packet.source_channel.as_str(),
+            packet,
+            data,
+            token,
+        )
+    }
+
+    fn token_events(
+        event_type: &str,
+        channel: &str,
+        packet: &ibc::Packet,
+        data: &Ics20PacketV2,
+    ) -> Vec<Event> {
+        data.tokens
+            .iter()
+            .map(|token| token_event(event_type, channel, packet, data, token))
+            .collect()
+    }
+
+    fn token_event(
+        event_type: &str,
+        channel: &str,
+        packet: &ibc::Packet,
+        data: &Ics20PacketV2,
+        token: &Token,
+    ) -> Event {
+        Event::new(event_type).add_attributes(vec![
+            Attribute::new(super::ATTRIBUTE_KEY_DENOM, token.denom.as_str()),
+            Attribute::new(super::ATTRIBUTE_KEY_AMOUNT, token.amount.to_string()),
+            Attribute::new(super::ATTRIBUTE_KEY_SENDER, data.sender.as_str()),
+            Attribute::new(super::ATTRIBUTE_KEY_RECEIVER, data.receiver.as_str()),
+            Attribute::new(super::ATTRIBUTE_KEY_CHANNEL, channel),
+            Attribute::new(super::ATTRIBUTE_KEY_SEQUENCE, packet.sequence.to_string()),
+        ])
+    }
+}
+
+/// Contains the event messages of the recovery actions of the contract admin.
+pub mod recovery {
+    use cosmwasm_std::{Attribute, Event, Uint128};
+
+    /// `set_escrow` is the event message for an escrowed amount set by the contract admin
+    #[must_use]
+    pub fn set_escrow(channel: &str, denom: &str, previous: Uint128, amount: Uint128) -> Event {
+        Event::new(super::EVENT_TYPE_SET_ESCROW).add_attributes(vec![
+            Attribute::new(super::ATTRIBUTE_KEY_CHANNEL, channel),
+            Attribute::new(super::ATTRIBUTE_KEY_DENOM, denom),
+            Attribute::new(super::ATTRIBUTE_KEY_PREVIOUS_AMOUNT, previous.to_string()),
+            Attribute::new(super::ATTRIBUTE_KEY_AMOUNT, amount.to_string()),
+        ])
+    }
+
+    /// `return_funds` is the event message for stranded funds returned by the contract admin
+    #[must_use]
+    pub fn return_funds(denom: &str, amount: Uint128, recipient: &str) -> Event {
+        Event::new(super::EVENT_TYPE_RETURN_FUNDS).add_attributes(vec![
+            Attribute::new(super::ATTRIBUTE_KEY_DENOM, denom),
+            Attribute::new(super::ATTRIBUTE_KEY_AMOUNT, amount.to_string()),
+            Attribute::new(super::ATTRIBUTE_KEY_RECIPIENT, recipient),
+        ])
+    }
+}
@@ -8,15 +8,37 @@ pub const CONTRACT_NAME: &str = "crates.io:cw-ibc-lite-ics20-transfer";
 /// This is also the version of the contract recorded in [`cw2`]
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// `ICS20_VERSION` is the version of the ICS20 module used in the contract.
+/// `ICS20_VERSION` is the version of the ICS20 module used for single token transfers.
 pub const ICS20_VERSION: &str = "ics20-1";
+/// `ICS20_V2_VERSION` is the version of the ICS20 module used for multi-token transfers and
+/// transfers with forwarding hops.
+pub const ICS20_V2_VERSION: &str = "ics20-2";
+/// `ICS20_BATCH_VERSION` is the version of the ICS20 module used for transfers of a single token
+/// to many receivers, see [`cw_ibc_lite_shared::types::transfer::packet::Ics20BatchPacket`].
+pub const ICS20_BATCH_VERSION: &str = "ics20-batch-1";
 /// `DEFAULT_PORT_ID` is the default port ID used in the counterparty chain.
 pub const DEFAULT_PORT_ID: &str = "transfer";
-/// `DEFAULT_TIMEOUT_SECONDS` is the default timeout in seconds for the ICS20 module.
+/// `DEFAULT_TIMEOUT_SECONDS` is the default timeout in seconds for the ICS20 module, used for
+/// channels without a default timeout of their own.
 pub const DEFAULT_TIMEOUT_SECONDS: u64 = 600;
+/// `DEFAULT_CALLBACK_GAS_LIMIT` is the gas limit of source callbacks, used if the contract admin
+/// has not set one.
+pub const DEFAULT_CALLBACK_GAS_LIMIT: u64 = 1_000_000;
+
+/// `VOUCHER_SYMBOL` is the cw20 token symbol of the vouchers minted for foreign tokens.
+pub const VOUCHER_SYMBOL: &str = "IBC";
+/// `VOUCHER_DECIMALS` is the cw20 token decimals of the vouchers minted for foreign tokens.
+pub const VOUCHER_DECIMALS: u8 = 6;
+/// `VOUCHER_DEFAULT_NAME` is the cw20 token name of a voucher whose base denom is not a valid
+/// cw20 token name.
+pub const VOUCHER_DEFAULT_NAME: &str = "ibc voucher";
 
 /// Contains the reply ids for various `SubMsg` replies
 pub mod reply {
-    /// `ON_RECV_PACKET_CW20_TRANSFER` is the reply id for the `on_recv_packet` reply
-    pub const ON_RECV_PACKET_CW20_TRANSFER: u64 = 1;
+    /// `ON_RECV_PACKET_TRANSFER` is the reply id for the `on_recv_packet` reply, which writes
+    /// an error acknowledgement if the tokens cannot be received
+    pub const ON_RECV_PACKET_TRANSFER: u64 = 1;
+    /// `ON_SOURCE_CALLBACK` is the reply id for the source callback reply, which ignores the
+    /// errors of the callback contract
+    pub const ON_SOURCE_CALLBACK: u64 = 2;
 }
@@ -3,26 +3,158 @@
 //! This module defines the messages that this contract receives.
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{IbcTimeoutBlock, Timestamp, Uint128};
 
-use cw_ibc_lite_shared::types::apps::helpers::ibc_lite_app_callback;
+use cw_ibc_lite_shared::types::{
+    apps::helpers::ibc_lite_app_callback,
+    ibc,
+    transfer::{encoding::PacketEncoding, packet::Forwarding, rate_limit::Quota},
+};
+
+pub use cw_ibc_lite_shared::types::transfer::packet::BatchRecipient;
+
+use crate::types::state::{Allowlists, ChannelPolicy, TokenPolicy};
 
 /// The message to instantiate the contract.
 #[cw_serde]
 pub struct InstantiateMsg {
     /// The contract address allowed to make IBC callbacks.
     pub ics26_router_address: String,
+    /// The code id of the cw20 contract instantiated as a voucher for each foreign denom.
+    /// Foreign tokens are not accepted if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voucher_code_id: Option<u64>,
 }
 
+/// The message to migrate the contract.
+#[cw_serde]
+pub struct MigrateMsg {}
+
 /// The execute messages supported by the contract.
 #[ibc_lite_app_callback]
 #[cw_serde]
 pub enum ExecuteMsg {
     /// This accepts a properly-encoded ReceiveMsg from a cw20 contract
-    /// The wrapped message is expected to be [`TransferMsg`].
+    /// The wrapped message is expected to be a [`ReceiveMsg`].
     Receive(cw20::Cw20ReceiveMsg),
+    /// Transfers the native tokens sent as funds. If more than one coin is sent, they are
+    /// transferred in a single `ics20-2` packet.
+    Transfer(TransferMsg),
+    /// Transfers the native tokens sent as funds to many receivers, see [`BatchTransferMsg`].
+    /// Exactly one coin must be sent, and its amount must be the sum of the amounts of the
+    /// receivers.
+    BatchTransfer(BatchTransferMsg),
+    /// Receives the tokens of an incoming packet. This can only be called by the contract itself
+    /// while handling `OnRecvPacket`, so that either all tokens of the packet are received or
+    /// none are and an error acknowledgement is written.
+    ReceivePacketTokens {
+        /// The incoming packet.
+        packet: ibc::Packet,
+    },
+    /// Sets the quotas on the transfers of a denom over a channel, replacing the existing ones
+    /// and starting their first period. An empty list removes the rate limits.
+    /// This can only be called by the contract admin.
+    SetRateLimits {
+        /// The local channel identifier.
+        channel: String,
+        /// The local denom: a cw20 contract address, a native denom, or the denom trace of a
+        /// voucher.
+        denom: String,
+        /// The quotas.
+        quotas: Vec<Quota>,
+    },
+    /// Sets or removes the encoding of the packet data of the counterparty app on a channel.
+    /// It should not be changed while packets are being received on the channel.
+    /// This can only be called by the contract admin.
+    SetChannelEncoding {
+        /// The local channel identifier.
+        channel: String,
+        /// The encoding, or `None` to use JSON.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        encoding: Option<PacketEncoding>,
+    },
+    /// Sets whether the counterparty app on a channel accepts `ics20-batch-1` packets, which
+    /// carry a batch transfer to many receivers in a single packet.
+    /// This can only be called by the contract admin.
+    SetChannelBatchSupport {
+        /// The local channel identifier.
+        channel: String,
+        /// Whether `ics20-batch-1` packets are sent on the channel.
+        supported: bool,
+    },
+    /// Sets or removes the default timeout of the transfers sent on a channel.
+    /// This can only be called by the contract admin.
+    SetDefaultTimeout {
+        /// The local channel identifier.
+        channel: String,
+        /// The number of seconds after the current block time, or `None` to use
+        /// [`crate::types::keys::DEFAULT_TIMEOUT_SECONDS`].
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timeout_seconds: Option<u64>,
+    },
+    /// Sets or removes the gas limit of source callbacks, so that a callback contract cannot
+    /// consume the gas of the relayer acknowledging or timing out its packet.
+    /// This can only be called by the contract admin.
+    SetCallbackGasLimit {
+        /// The gas limit, or `None` to use
+        /// [`crate::types::keys::DEFAULT_CALLBACK_GAS_LIMIT`].
+        #[serde(skip_serializing_if = "Option::is_none")]
+        gas_limit: Option<u64>,
+    },
+    /// Enables or disables the token and channel allowlists.
+    /// This can only be called by the contract admin.
+    SetAllowlists(Allowlists),
+    /// Sets or removes the policy of a token.
+    /// This can only be called by the contract admin.
+    SetTokenPolicy {
+        /// The local denom: a cw20 contract address, a native denom, or the denom trace of a
+        /// voucher.
+        denom: String,
+        /// The policy, or `None` to remove it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        policy: Option<TokenPolicy>,
+    },
+    /// Sets or removes the policy of a channel.
+    /// This can only be called by the contract admin.
+    SetChannelPolicy {
+        /// The local channel identifier.
+        channel: String,
+        /// The policy, or `None` to remove it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        policy: Option<ChannelPolicy>,
+    },
+    /// Reconciles the escrowed amounts with the tokens held by this contract, or returns funds
+    /// stranded in it. Every recovery action emits an event.
+    /// This can only be called by the contract admin.
+    Recover(Recovery),
+}
+
+/// The recovery actions for discrepancies between the escrowed amounts and the tokens held by
+/// this contract. See [`QueryMsg::EscrowInvariant`].
+#[cw_serde]
+pub enum Recovery {
+    /// Sets the escrowed amount of a denom on a channel. A zero amount removes the escrow.
+    SetEscrow {
+        /// The local channel identifier.
+        channel: String,
+        /// The cw20 contract address or the native denom.
+        denom: String,
+        /// The escrowed amount.
+        amount: Uint128,
+    },
+    /// Sends tokens held by this contract that are not escrowed, such as tokens sent to it
+    /// directly. The escrowed tokens cannot be returned.
+    ReturnFunds {
+        /// The cw20 contract address or the native denom.
+        denom: String,
+        /// The amount to return.
+        amount: Uint128,
+        /// The recipient of the funds.
+        recipient: String,
+    },
 }
 
-/// This is the message we accept via [`ExecuteMsg::Receive`].
+/// This is the message we accept via [`ExecuteMsg::Receive`] and [`ExecuteMsg::Transfer`].
 #[cw_serde]
 pub struct TransferMsg {
     /// The local channel to send the packets on
@@ -31,12 +163,64 @@ pub struct TransferMsg {
     /// Don't use HumanAddress as this will likely have a different Bech32 prefix than we use
     /// and cannot be validated locally
     pub receiver: String,
-    /// How long the packet lives in seconds. If not specified, use default_timeout
+    /// When the packet times out. If not specified, the default timeout of the channel is used.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub timeout: Option<u64>,
+    pub timeout: Option<TransferTimeout>,
     /// An optional memo to add to the IBC transfer
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
+    /// Optional hops to forward the tokens through after the destination chain. The transfer is
+    /// sent as an `ics20-2` packet if given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarding: Option<Forwarding>,
+    /// An optional contract notified with
+    /// [`cw_ibc_lite_shared::types::transfer::callbacks::IbcTransferCallback`] once the packet
+    /// is acknowledged or times out. It must be the sender of the transfer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback: Option<String>,
+}
+
+/// The message wrapped in [`ExecuteMsg::Receive`]. The variants are told apart by their
+/// fields.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    /// Transfers the received cw20 tokens to a single receiver.
+    Transfer(TransferMsg),
+    /// Transfers the received cw20 tokens to many receivers. The amount received must be the
+    /// sum of the amounts of the receivers.
+    BatchTransfer(BatchTransferMsg),
+}
+
+/// This is the message we accept via [`ExecuteMsg::Receive`] and [`ExecuteMsg::BatchTransfer`]
+/// to transfer tokens to many receivers. The tokens are received once. If the counterparty app
+/// accepts `ics20-batch-1` packets, see [`ExecuteMsg::SetChannelBatchSupport`], and packet data
+/// is JSON encoded on the channel, a single packet is sent to all receivers, and it is received
+/// or refunded as a whole. Otherwise, one `ics20-1` packet is sent per receiver, so that each one
+/// is refunded on its own if it fails or times out.
+#[cw_serde]
+pub struct BatchTransferMsg {
+    /// The local channel to send the packets on
+    pub source_channel: String,
+    /// The receivers and the amounts they are sent.
+    pub recipients: Vec<BatchRecipient>,
+    /// When the packets time out. If not specified, the default timeout of the channel is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<TransferTimeout>,
+    /// An optional memo to add to every packet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// The timeout of a transfer.
+#[cw_serde]
+pub enum TransferTimeout {
+    /// The number of seconds after the current block time.
+    Relative(u64),
+    /// An absolute timestamp.
+    Timestamp(Timestamp),
+    /// A block height of the counterparty chain.
+    Height(IbcTimeoutBlock),
 }
 
 /// The query messages supported by the contract.
@@ -44,21 +228,123 @@ pub struct TransferMsg {
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    /// The escrowed amount for the given channel and cw20 contract address.
+    /// The escrowed amount for the given channel and denom.
     #[returns(cosmwasm_std::Uint128)]
     EscrowAmount {
         /// The channel identifier.
         channel: String,
-        /// The cw20 contract address.
+        /// The cw20 contract address or the native denom.
         cw20_address: String,
     },
+    /// The voucher contract of the given denom trace.
+    #[returns(responses::VoucherInfo)]
+    Voucher {
+        /// The denom trace in the ICS-20 `{port_id}/{channel_id}/{denom}` form.
+        denom_trace: String,
+    },
+    /// The denom trace of the given hash.
+    #[returns(responses::DenomTraceInfo)]
+    DenomTrace {
+        /// The uppercase hex encoded hash of the denom trace, optionally prefixed with `ibc/`.
+        hash: String,
+    },
+    /// The list of all denom traces, ordered by hash.
+    #[returns(responses::DenomTraceList)]
+    ListDenomTraces {
+        /// start pagination after this hash
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_after: Option<String>,
+        /// limit results to this number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// The hash of the given denom trace. The trace does not need to be registered.
+    #[returns(responses::DenomHash)]
+    DenomHash {
+        /// The full path of the denom trace, e.g. `transfer/08-wasm-0/uatom`.
+        trace: String,
+    },
+    /// The rate limits on the transfers of a denom over a channel, with their current flows.
+    /// The flow of a period that has ended is only reset by the next transfer.
+    #[returns(responses::RateLimitList)]
+    RateLimits {
+        /// The local channel identifier.
+        channel: String,
+        /// The local denom.
+        denom: String,
+    },
+    /// The transfers of the given sender that are not acknowledged or timed out yet, ordered by
+    /// channel and sequence.
+    #[returns(responses::InFlightTransferList)]
+    InFlightTransfers {
+        /// The sender of the transfers.
+        sender: String,
+        /// start pagination after this (channel, sequence)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_after: Option<(String, u64)>,
+        /// limit results to this number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// The default timeout of the transfers sent on the given channel.
+    #[returns(responses::DefaultTimeout)]
+    DefaultTimeout {
+        /// The local channel identifier.
+        channel: String,
+    },
+    /// The encoding of the packet data of the counterparty app on the given channel.
+    #[returns(responses::ChannelEncoding)]
+    ChannelEncoding {
+        /// The local channel identifier.
+        channel: String,
+    },
+    /// Whether the counterparty app on the given channel accepts `ics20-batch-1` packets.
+    #[returns(responses::ChannelBatchSupport)]
+    ChannelBatchSupport {
+        /// The local channel identifier.
+        channel: String,
+    },
+    /// The gas limit of source callbacks.
+    #[returns(responses::CallbackGasLimit)]
+    CallbackGasLimit {},
+    /// Whether the token and channel allowlists are enabled.
+    #[returns(Allowlists)]
+    Allowlists {},
+    /// The list of all token policies, ordered by denom.
+    #[returns(responses::TokenPolicyList)]
+    ListTokenPolicies {
+        /// start pagination after this denom
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_after: Option<String>,
+        /// limit results to this number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// The list of all channel policies, ordered by channel.
+    #[returns(responses::ChannelPolicyList)]
+    ListChannelPolicies {
+        /// start pagination after this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_after: Option<String>,
+        /// limit results to this number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// Compares the total escrowed amount of a denom over all channels with the tokens held by
+    /// this contract, which must cover it.
+    #[returns(responses::EscrowInvariant)]
+    EscrowInvariant {
+        /// The cw20 contract address or the native denom.
+        denom: String,
+    },
     /// The list of all escrows for the given channel.
-    /// Returns (cw20_address, amount) pairs.
+    /// Returns (denom, amount) pairs, where the denom is a cw20 contract address or a native
+    /// denom.
     #[returns(responses::EscrowList)]
     ListEscrows {
         /// The channel identifier.
         channel: String,
-        /// start pagination after this contract address
+        /// start pagination after this denom
         #[serde(skip_serializing_if = "Option::is_none")]
         start_after: Option<String>,
         /// limit results to this number
@@ -70,6 +356,11 @@ pub enum QueryMsg {
 /// Contains the query responses
 pub mod responses {
     use cosmwasm_std::Uint128;
+    use cw_ibc_lite_shared::types::transfer::{
+        denom::DenomTrace, encoding::PacketEncoding, rate_limit::RateLimit,
+    };
+
+    use crate::types::state::{ChannelPolicy, InFlightTransfer, TokenPolicy};
 
     /// Response to [`super::QueryMsg::ListEscrows`]
     #[super::cw_serde]
@@ -78,14 +369,139 @@ pub mod responses {
         pub list: Vec<EscrowInfo>,
     }
 
-    /// Information on the escrowed amount for a given channel and cw20 address
+    /// Information on the escrowed amount for a given channel and denom
     #[super::cw_serde]
     pub struct EscrowInfo {
         /// The channel identifier of the escrowed amount
         pub channel: String,
-        /// The address of the cw20 token contract
+        /// The address of the cw20 token contract, or the native denom
         pub cw20_address: String,
         /// Amount of funds escrowed
         pub amount: Uint128,
     }
+
+    /// Response to [`super::QueryMsg::EscrowInvariant`]
+    #[super::cw_serde]
+    pub struct EscrowInvariant {
+        /// The cw20 contract address or the native denom
+        pub denom: String,
+        /// The total amount escrowed over all channels
+        pub escrowed: Uint128,
+        /// The amount held by this contract
+        pub balance: Uint128,
+        /// Whether the balance covers the escrowed amount
+        pub holds: bool,
+    }
+
+    /// Response to [`super::QueryMsg::Voucher`]
+    #[super::cw_serde]
+    pub struct VoucherInfo {
+        /// The denom trace of the voucher
+        pub denom_trace: String,
+        /// The address of the voucher cw20 contract
+        pub address: String,
+    }
+
+    /// Response to [`super::QueryMsg::DenomTrace`]
+    #[super::cw_serde]
+    pub struct DenomTraceInfo {
+        /// The uppercase hex encoded hash of the denom trace
+        pub hash: String,
+        /// The denom trace
+        pub denom_trace: DenomTrace,
+    }
+
+    /// Response to [`super::QueryMsg::ListDenomTraces`]
+    #[super::cw_serde]
+    pub struct DenomTraceList {
+        /// List of denom trace infos
+        pub list: Vec<DenomTraceInfo>,
+    }
+
+    /// Response to [`super::QueryMsg::DenomHash`]
+    #[super::cw_serde]
+    pub struct DenomHash {
+        /// The uppercase hex encoded hash of the denom trace
+        pub hash: String,
+    }
+
+    /// Response to [`super::QueryMsg::RateLimits`]
+    #[super::cw_serde]
+    pub struct RateLimitList {
+        /// List of rate limits
+        pub list: Vec<RateLimit>,
+    }
+
+    /// Response to [`super::QueryMsg::ListTokenPolicies`]
+    #[super::cw_serde]
+    pub struct TokenPolicyList {
+        /// List of token policy infos
+        pub list: Vec<TokenPolicyInfo>,
+    }
+
+    /// The policy of a token
+    #[super::cw_serde]
+    pub struct TokenPolicyInfo {
+        /// The local denom of the token
+        pub denom: String,
+        /// The policy of the token
+        pub policy: TokenPolicy,
+    }
+
+    /// Response to [`super::QueryMsg::ListChannelPolicies`]
+    #[super::cw_serde]
+    pub struct ChannelPolicyList {
+        /// List of channel policy infos
+        pub list: Vec<ChannelPolicyInfo>,
+    }
+
+    /// The policy of a channel
+    #[super::cw_serde]
+    pub struct ChannelPolicyInfo {
+        /// The local channel identifier
+        pub channel: String,
+        /// The policy of the channel
+        pub policy: ChannelPolicy,
+    }
+
+    /// Response to [`super::QueryMsg::DefaultTimeout`]
+    #[super::cw_serde]
+    pub struct DefaultTimeout {
+        /// The local channel identifier
+        pub channel: String,
+        /// The number of seconds after the current block time
+        pub timeout_seconds: u64,
+    }
+
+    /// Response to [`super::QueryMsg::ChannelEncoding`]
+    #[super::cw_serde]
+    pub struct ChannelEncoding {
+        /// The local channel identifier
+        pub channel: String,
+        /// The encoding of the packet data
+        pub encoding: PacketEncoding,
+    }
+
+    /// Response to [`super::QueryMsg::ChannelBatchSupport`]
+    #[super::cw_serde]
+    pub struct ChannelBatchSupport {
+        /// The local channel identifier
+        pub channel: String,
+        /// Whether `ics20-batch-1` packets are sent on the channel
+        pub supported: bool,
+    }
+
+    /// Response to [`super::QueryMsg::CallbackGasLimit`]
+    #[super::cw_serde]
+    pub struct CallbackGasLimit {
+        /// The gas limit of source callbacks
+        pub gas_limit: u64,
+    }
+
+    /// Response to [`super::QueryMsg::InFlightTransfers`]
+    #[super::cw_serde]
+    pub struct InFlightTransferList {
+        /// List of in-flight transfers
+        pub list: Vec<InFlightTransfer>,
+    }
 }
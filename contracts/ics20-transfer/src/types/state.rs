@@ -1,19 +1,153 @@
 //! This module defines the state storage of the Contract.
 
-use cosmwasm_std::Uint128;
-use cw_storage_plus::Map;
+use cosmwasm_std::{Addr, Empty, IbcTimeout, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use cw_ibc_lite_shared::types::{
+    ibc,
+    transfer::{
+        denom::DenomTrace,
+        encoding::PacketEncoding,
+        packet::{BatchRecipient, Token},
+        rate_limit::RateLimit,
+    },
+};
 
 /// The item that stores the escrowed tokens per denom.
 /// It maps (`channel_id`, `denom`) to the escrowed amount.
 pub const ESCROW: Map<(&str, &str), Uint128> = Map::new("escrow");
 
-/// Used to pass state to the reply handler in `on_recv_packet`.
+/// The code id of the cw20 contract instantiated as a voucher for each foreign denom.
+/// If not set, foreign tokens are not accepted.
+pub const VOUCHER_CODE_ID: Item<u64> = Item::new("voucher_code_id");
+
+/// The map from the denom traces of foreign tokens to their voucher contracts.
+/// A denom trace is in the ICS-20 `{port_id}/{channel_id}/{denom}` form, where the port and
+/// channel are the ones the tokens were received on.
+pub const VOUCHERS: Map<&str, Addr> = Map::new("vouchers");
+
+/// The map from voucher contracts to their denom traces, the reverse of [`VOUCHERS`].
+pub const VOUCHER_TRACES: Map<&Addr, String> = Map::new("voucher_traces");
+
+/// The map from the uppercase hex encoded hashes of denom traces to the traces of all foreign
+/// tokens received by this contract.
+pub const DENOM_TRACES: Map<&str, DenomTrace> = Map::new("denom_traces");
+
+/// The map from local channel identifiers to the default timeout in seconds of the transfers
+/// sent on them. If not set, [`crate::types::keys::DEFAULT_TIMEOUT_SECONDS`] is used.
+pub const DEFAULT_TIMEOUTS: Map<&str, u64> = Map::new("default_timeouts");
+
+/// The map from local channel identifiers to the encoding of the packet data of the
+/// counterparty app. Unlike IBC v2 payloads, `cw-ibc-lite` packets carry no encoding, so it is
+/// metadata of the channel. If not set, packet data is JSON encoded. Acknowledgements are JSON
+/// encoded on every channel.
+pub const CHANNEL_ENCODINGS: Map<&str, PacketEncoding> = Map::new("channel_encodings");
+
+/// The local channels whose counterparty app accepts `ics20-batch-1` packets, see
+/// [`crate::types::keys::ICS20_BATCH_VERSION`]. `cw-ibc-lite` has no version negotiation, so
+/// this is metadata of the channel. Batch transfers on other channels are sent as one packet per
+/// receiver.
+pub const BATCH_CHANNELS: Map<&str, Empty> = Map::new("batch_channels");
+
+/// The map from sent packets to the encoding of their data, so that their acknowledgement or
+/// timeout can be handled if the encoding of the channel changes in the meantime.
+/// It maps (`channel_id`, `sequence`) of the packet to its encoding.
+pub const SENT_ENCODINGS: Map<(&str, u64), PacketEncoding> = Map::new("sent_encodings");
+
+/// Whether only the tokens and channels with a policy allowing them are accepted.
+pub const ALLOWLISTS: Item<Allowlists> = Item::new("allowlists");
+
+/// The map from local denoms to their [`TokenPolicy`]. A local denom is a cw20 contract
+/// address, a native denom, or the denom trace of a voucher.
+pub const TOKEN_POLICIES: Map<&str, TokenPolicy> = Map::new("token_policies");
+
+/// The map from local channel identifiers to their [`ChannelPolicy`].
+pub const CHANNEL_POLICIES: Map<&str, ChannelPolicy> = Map::new("channel_policies");
+
+/// The rate limits on the transfers of a denom over a channel.
+/// It maps (`channel_id`, `denom`) to the rate limits, where the denom is the local denom: a
+/// cw20 contract address, a native denom, or the denom trace of a voucher.
+pub const RATE_LIMITS: Map<(&str, &str), Vec<RateLimit>> = Map::new("rate_limits");
+
+/// The transfers sent by this contract that are not acknowledged or timed out yet.
+/// It maps (`sender`, `channel_id`, `sequence`) of the packet to the [`InFlightTransfer`].
+pub const IN_FLIGHT: Map<(&str, &str, u64), InFlightTransfer> = Map::new("in_flight");
+
+/// The callback contract registered by the sender of the packet being sent. It is moved to
+/// [`SOURCE_CALLBACKS`] in `on_send_packet` once the sequence of the packet is known.
+pub const PENDING_CALLBACK: Item<Addr> = Item::new("pending_callback");
+
+/// The gas limit of source callbacks. If not set,
+/// [`crate::types::keys::DEFAULT_CALLBACK_GAS_LIMIT`] is used.
+pub const CALLBACK_GAS_LIMIT: Item<u64> = Item::new("callback_gas_limit");
+
+/// The map from sent packets to the callback contracts registered by their senders.
+/// It maps (`channel_id`, `sequence`) of the packet to the callback contract.
+pub const SOURCE_CALLBACKS: Map<(&str, u64), Addr> = Map::new("source_callbacks");
+
+/// The incoming packet whose tokens are being forwarded. It is set while receiving the tokens,
+/// and moved to [`FORWARDS`] in `on_send_packet` once the sequence of the forwarded packet is
+/// known.
+pub const PENDING_FORWARD: Item<ForwardedPacket> = Item::new("pending_forward");
+
+/// The map from forwarded packets to the incoming packets they forward.
+/// It maps (`channel_id`, `sequence`) of the forwarded packet to the [`ForwardedPacket`].
+pub const FORWARDS: Map<(&str, u64), ForwardedPacket> = Map::new("forwards");
+
+/// An incoming packet whose tokens were forwarded, and whose acknowledgement is written once
+/// the forwarded packet is acknowledged or times out.
+#[cosmwasm_schema::cw_serde]
+pub struct ForwardedPacket {
+    /// The incoming packet.
+    pub packet: ibc::Packet,
+    /// The forwarded tokens, in their local denoms.
+    pub tokens: Vec<Token>,
+}
+
+/// Whether only the tokens and channels with a policy allowing them are accepted. Otherwise,
+/// all tokens that are not blocked and all channels are accepted.
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct Allowlists {
+    /// Whether only tokens with [`TokenPolicy::Allowed`] are accepted.
+    pub tokens: bool,
+    /// Whether only channels with a [`ChannelPolicy`] are accepted.
+    pub channels: bool,
+}
+
+/// The policy of a token.
+#[cosmwasm_schema::cw_serde]
+pub enum TokenPolicy {
+    /// The token is accepted, even if the token allowlist is enabled.
+    Allowed,
+    /// The token is never accepted.
+    Blocked,
+}
+
+/// The policy of a channel, in the style of the `SendEnabled` and `ReceiveEnabled` params of
+/// the ICS-20 module of `ibc-go`.
+#[cosmwasm_schema::cw_serde]
+pub struct ChannelPolicy {
+    /// Whether tokens can be sent on the channel.
+    pub send_enabled: bool,
+    /// Whether tokens can be received on the channel.
+    pub receive_enabled: bool,
+}
+
+/// A transfer sent by this contract that is not acknowledged or timed out yet.
 #[cosmwasm_schema::cw_serde]
-pub struct RecvPacketReplyPayload {
-    /// The channel identifier.
-    pub channel_id: String,
-    /// The denomination of the transferred tokens.
-    pub denom: String,
-    /// The amount of tokens transferred.
-    pub amount: Uint128,
+pub struct InFlightTransfer {
+    /// The local channel the packet was sent on.
+    pub channel: String,
+    /// The sequence of the packet.
+    pub sequence: u64,
+    /// The receiver on the counterparty chain, empty for an `ics20-batch-1` packet.
+    pub receiver: String,
+    /// The transferred tokens, in their local denoms.
+    pub tokens: Vec<Token>,
+    /// The receivers of an `ics20-batch-1` packet and the amounts they are sent.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<BatchRecipient>,
+    /// The timeout of the packet.
+    pub timeout: IbcTimeout,
 }
@@ -8,7 +8,7 @@ use cw_ibc_lite_shared::types::error::ContractError;
 
 use crate::types::{
     keys,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state,
 };
 
@@ -32,7 +32,9 @@ pub fn instantiate(
         deps.api,
         &deps.querier,
         &env,
-        ics02_client::types::msg::InstantiateMsg {},
+        ics02_client::types::msg::InstantiateMsg {
+            creation_policy: msg.ics02_creation_policy,
+        },
         format!("{}.{}", keys::ICS02_CLIENT_SALT, env.contract.address),
         None::<String>,
         keys::ICS02_CLIENT_SALT,
@@ -59,9 +61,15 @@ pub fn execute(
         ExecuteMsg::RecvPacket(recv_msg) => execute::recv_packet(deps, env, info, recv_msg),
         ExecuteMsg::Acknowledgement(ack_msg) => execute::acknowledgement(deps, env, info, ack_msg),
         ExecuteMsg::Timeout(timeout_msg) => execute::timeout(deps, env, info, timeout_msg),
+        ExecuteMsg::WriteAcknowledgement(write_ack_msg) => {
+            execute::write_acknowledgement(deps, env, info, write_ack_msg)
+        }
         ExecuteMsg::RegisterIbcApp { port_id, address } => {
             execute::register_ibc_app(deps, env, info, port_id, address)
         }
+        ExecuteMsg::UpdateIcs02CreationPolicy { creation_policy } => {
+            execute::update_ics02_creation_policy(deps, env, info, creation_policy)
+        }
     }
 }
 
@@ -92,12 +100,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
     }
 }
 
+/// Migrates the contract to this version. Refuses to migrate from another contract or from a
+/// newer version of this one.
+///
+/// # Errors
+/// Will return an error if the recorded contract name or version does not allow the migration.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // NOTE: State migrations from the returned previous version are to be run here once the
+    // storage layout changes.
+    cw_ibc_lite_shared::utils::migrate::ensure_from_older_version(
+        deps.storage,
+        keys::CONTRACT_NAME,
+        keys::CONTRACT_VERSION,
+    )?;
+
+    Ok(Response::default())
+}
+
 mod execute {
     use std::str::FromStr;
 
     use crate::types::{
         events,
-        msg::execute::{AcknowledgementMsg, RecvPacketMsg, SendPacketMsg, TimeoutMsg},
+        msg::execute::{
+            AcknowledgementMsg, RecvPacketMsg, SendPacketMsg, TimeoutMsg, WriteAcknowledgementMsg,
+        },
     };
 
     use super::{keys, state, ContractError, DepsMut, Env, MessageInfo, Response};
@@ -147,8 +176,14 @@ mod execute {
             }
         }
 
-        // Ensure the timeout is valid.
-        utils::timeout::validate(&env, &msg.timeout)?;
+        // Ensure the timeout has not passed on the counterparty.
+        utils::timeout::validate_send(&env, &msg.timeout, || {
+            Ok(ics02_contract
+                .query(&deps.querier)
+                .client_querier(msg.source_channel.as_str())?
+                .latest_height()?
+                .height)
+        })?;
 
         // Construct the packet.
         let sequence =
@@ -377,24 +412,32 @@ mod execute {
             ));
         }
 
-        // Verify the timeout timestamp.
-        let timeout_timestamp = packet
-            .timeout
-            .timestamp()
-            .ok_or(ContractError::EmptyTimestamp)?
-            .nanos();
-        let counterparty_timestamp = ics02_contract
-            .query(&deps.querier)
-            .client_querier(packet.source_channel.as_str())?
-            .timestamp_at_height(TimestampAtHeightMsg {
-                height: msg.proof_height.clone().into(),
-            })?
-            .timestamp;
-        if counterparty_timestamp < timeout_timestamp {
-            return Err(ContractError::invalid_timeout_timestamp(
-                counterparty_timestamp,
-                timeout_timestamp,
-            ));
+        // Verify that the timeout height or timestamp has passed on the counterparty.
+        let timeout_block = utils::timeout::block(&packet.timeout);
+        let is_height_reached = timeout_block
+            .as_ref()
+            .is_some_and(|b| utils::timeout::is_height_reached(b, &msg.proof_height));
+        if !is_height_reached {
+            let Some(timeout_timestamp) = packet.timeout.timestamp() else {
+                return Err(ContractError::invalid_timeout_block(
+                    msg.proof_height.revision_height,
+                    timeout_block.map_or(0, |b| b.height),
+                ));
+            };
+            let timeout_timestamp = timeout_timestamp.nanos();
+            let counterparty_timestamp = ics02_contract
+                .query(&deps.querier)
+                .client_querier(packet.source_channel.as_str())?
+                .timestamp_at_height(TimestampAtHeightMsg {
+                    height: msg.proof_height.clone().into(),
+                })?
+                .timestamp;
+            if counterparty_timestamp < timeout_timestamp {
+                return Err(ContractError::invalid_timeout_timestamp(
+                    counterparty_timestamp,
+                    timeout_timestamp,
+                ));
+            }
         }
 
         // Verify the packet non-membership.
@@ -430,6 +473,32 @@ mod execute {
             .add_event(event))
     }
 
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn write_acknowledgement(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: WriteAcknowledgementMsg,
+    ) -> Result<Response, ContractError> {
+        msg.packet.validate()?;
+        let packet = msg.packet;
+
+        // NOTE: Only the IBC app that received the packet can write its acknowledgement.
+        let ibc_app_address =
+            state::IBC_APPS.load(deps.storage, packet.destination_port.as_str())?;
+        if info.sender != ibc_app_address {
+            return Err(ContractError::Unauthorized);
+        }
+
+        state::helpers::assert_packet_receipt(deps.storage, &packet)?;
+
+        // NOTE: This fails if the acknowledgement has already been written.
+        let ack: ibc::Acknowledgement = msg.acknowledgement.try_into()?;
+        state::helpers::commit_packet_ack(deps.storage, &packet, &ack)?;
+
+        Ok(Response::new().add_event(events::write_acknowledgement::success(&packet, &ack)))
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     pub fn register_ibc_app(
         deps: DepsMut,
@@ -457,6 +526,24 @@ mod execute {
             info.sender.as_str(),
         )))
     }
+
+    /// Forwards the new creation policy to cw-ibc-lite-ics02-client, which is owned by this
+    /// contract.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_ics02_creation_policy(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        creation_policy: ics02_client::types::state::CreationPolicy,
+    ) -> Result<Response, ContractError> {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+        let ics02_address = state::ICS02_CLIENT_ADDRESS.load(deps.storage)?;
+        let ics02_contract = ics02_client::helpers::Ics02ClientContract::new(ics02_address);
+
+        let update_msg = ics02_contract
+            .call(ics02_client::types::msg::ExecuteMsg::UpdateCreationPolicy { creation_policy })?;
+        Ok(Response::new().add_message(update_msg))
+    }
 }
 
 mod reply {
@@ -469,7 +556,9 @@ mod reply {
 
     /// Handles the reply to
     /// [`cw_ibc_lite_shared::types::apps::callbacks::IbcAppCallbackMsg::OnRecvPacket`].
-    /// It writes the acknowledgement and emits the write acknowledgement events.
+    /// It writes the acknowledgement and emits the write acknowledgement events. If the IBC app
+    /// responded without acknowledgement data, it must write the acknowledgement later with
+    /// [`crate::types::msg::ExecuteMsg::WriteAcknowledgement`].
     #[allow(clippy::needless_pass_by_value)]
     pub fn write_acknowledgement(
         deps: DepsMut,
@@ -479,11 +568,15 @@ mod reply {
     ) -> Result<Response, ContractError> {
         match result {
             SubMsgResult::Ok(resp) => {
-                let ack = ibc::Acknowledgement::new(
-                    anybuf::Bufany::deserialize(&resp.msg_responses[0].value)?
-                        .bytes(1)
-                        .unwrap(),
-                );
+                let ack_data = anybuf::Bufany::deserialize(&resp.msg_responses[0].value)?
+                    .bytes(1)
+                    .unwrap_or_default();
+                if ack_data.is_empty() {
+                    // NOTE: The acknowledgement is written asynchronously by the IBC app.
+                    return Ok(Response::new());
+                }
+
+                let ack = ibc::Acknowledgement::new(ack_data);
                 let packet: ibc::Packet = cosmwasm_std::from_json(payload)?;
 
                 state::helpers::commit_packet_ack(deps.storage, &packet, &ack)?;
@@ -509,3 +602,67 @@ mod query {
         )?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    use super::{execute, keys, migrate, query, state, ExecuteMsg, MigrateMsg, QueryMsg};
+
+    /// The last released version.
+    const PREVIOUS_VERSION: &str = "0.1.0";
+    const CLIENT_ID: &str = "08-wasm-0";
+
+    #[test]
+    fn migrate_keeps_the_apps_and_sequences_of_the_previous_version() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let ics02_client = deps.api.addr_make("ics02_client");
+        let (transfer, other_app) = (deps.api.addr_make("transfer"), deps.api.addr_make("app"));
+        {
+            let deps = deps.as_mut();
+            cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner.as_str())).unwrap();
+            cw2::set_contract_version(deps.storage, keys::CONTRACT_NAME, PREVIOUS_VERSION).unwrap();
+            state::ICS02_CLIENT_ADDRESS
+                .save(deps.storage, &ics02_client)
+                .unwrap();
+            state::IBC_APPS
+                .save(deps.storage, "transfer", &transfer)
+                .unwrap();
+            state::NEXT_SEQUENCE_SEND
+                .save(deps.storage, ("transfer", CLIENT_ID), &4)
+                .unwrap();
+        }
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, keys::CONTRACT_VERSION);
+        assert_eq!(
+            state::ICS02_CLIENT_ADDRESS.load(&deps.storage).unwrap(),
+            ics02_client
+        );
+        let msg = QueryMsg::PortRouter {
+            port_id: "transfer".to_string(),
+        };
+        let app: String =
+            cosmwasm_std::from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(app, transfer.as_str());
+        let sequence =
+            state::helpers::new_sequence_send(&mut deps.storage, "transfer", CLIENT_ID).unwrap();
+        assert_eq!(sequence, 4);
+
+        // the owner can still register apps with custom port ids
+        let msg = ExecuteMsg::RegisterIbcApp {
+            port_id: Some("other".to_string()),
+            address: other_app.to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(owner.as_str(), &[]),
+            msg,
+        )
+        .unwrap();
+    }
+}
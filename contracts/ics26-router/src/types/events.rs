@@ -23,6 +23,8 @@ pub const ATTRIBUTE_KEY_SENDER: &str = "sender";
 pub const ATTRIBUTE_KEY_DATA_HEX: &str = "packet_data_hex";
 /// `ATTRIBUTE_KEY_TIMEOUT_TIMESTAMP` is the attribute key for the packet timeout timestamp
 pub const ATTRIBUTE_KEY_TIMEOUT_TIMESTAMP: &str = "packet_timeout_timestamp";
+/// `ATTRIBUTE_KEY_TIMEOUT_HEIGHT` is the attribute key for the packet timeout height
+pub const ATTRIBUTE_KEY_TIMEOUT_HEIGHT: &str = "packet_timeout_height";
 /// `ATTRIBUTE_KEY_SEQUENCE` is the attribute key for the packet sequence
 pub const ATTRIBUTE_KEY_SEQUENCE: &str = "packet_sequence";
 /// `ATTRIBUTE_KEY_SRC_PORT` is the attribute key for the packet source port
@@ -56,12 +58,14 @@ pub mod send_packet {
     use cosmwasm_std::{Attribute, Event, HexBinary};
     use cw_ibc_lite_shared::types::ibc::Packet;
 
-    /// `send_packet` is the event message for a send packet event
-    ///
-    /// # Panics
-    /// Panics if the packet timeout timestamp is not set
+    /// `send_packet` is the event message for a send packet event. An unset timeout height or
+    /// timestamp is emitted as zero.
     #[must_use]
     pub fn success(packet: &Packet) -> Event {
+        let timeout_height = packet.timeout.block().map_or_else(
+            || "0-0".to_string(),
+            |b| format!("{}-{}", b.revision, b.height),
+        );
         Event::new(super::EVENT_TYPE_SEND_PACKET).add_attributes(vec![
             Attribute::new(
                 super::ATTRIBUTE_KEY_DATA_HEX,
@@ -69,8 +73,13 @@ pub mod send_packet {
             ),
             Attribute::new(
                 super::ATTRIBUTE_KEY_TIMEOUT_TIMESTAMP,
-                packet.timeout.timestamp().unwrap().nanos().to_string(),
+                packet
+                    .timeout
+                    .timestamp()
+                    .map_or(0, |ts| ts.nanos())
+                    .to_string(),
             ),
+            Attribute::new(super::ATTRIBUTE_KEY_TIMEOUT_HEIGHT, timeout_height),
             Attribute::new(super::ATTRIBUTE_KEY_SEQUENCE, packet.sequence.to_string()),
             Attribute::new(super::ATTRIBUTE_KEY_SRC_PORT, packet.source_port.as_str()),
             Attribute::new(
@@ -10,11 +10,20 @@ pub struct InstantiateMsg {
     /// cw-ibc-lite-ics02-client router code id
     pub ics02_client_code_id: u64,
     /// The owner of the contract.
-    /// This address can register IBC apps with custom port IDs.
+    /// This address can register IBC apps with custom port IDs, and update the client creation
+    /// policy.
     /// Use CosmosSDK governance module address if unsure.
     pub owner: String,
+    /// The policy enforced on client creation by the cw-ibc-lite-ics02-client contract.
+    /// Defaults to permissionless client creation if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ics02_creation_policy: Option<cw_ibc_lite_ics02_client::types::state::CreationPolicy>,
 }
 
+/// The message to migrate the contract.
+#[cw_serde]
+pub struct MigrateMsg {}
+
 /// The execute messages supported by the contract.
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -29,6 +38,10 @@ pub enum ExecuteMsg {
     /// Timeout a packet sent to another client.
     /// From https://github.com/cosmos/ibc-go/blob/cf191f4ab3ff27a2e68b3dac17c547669f80102c/modules/core/04-channel/types/tx.pb.go#L725
     Timeout(execute::TimeoutMsg),
+    /// Write the acknowledgement of a received packet asynchronously.
+    /// Only the IBC app of the destination port can write the acknowledgement, and only if it
+    /// responded to `OnRecvPacket` without any acknowledgement data.
+    WriteAcknowledgement(execute::WriteAcknowledgementMsg),
     /// Anyone can register an IBC app with this contract.
     /// A custom port ID can only be provided if the caller is the admin of the contract.
     RegisterIbcApp {
@@ -39,6 +52,12 @@ pub enum ExecuteMsg {
         /// The contract address of the IBC app.
         address: String,
     },
+    /// Update the policy enforced on client creation by the cw-ibc-lite-ics02-client contract.
+    /// Can only be called by the admin of the contract.
+    UpdateIcs02CreationPolicy {
+        /// The new policy enforced on client creation.
+        creation_policy: cw_ibc_lite_ics02_client::types::state::CreationPolicy,
+    },
 }
 
 /// The query messages supported by the contract.
@@ -117,6 +136,15 @@ pub mod execute {
         pub next_sequence_recv: u64,
     }
 
+    /// The message to write the acknowledgement of a received packet asynchronously.
+    #[cw_serde]
+    pub struct WriteAcknowledgementMsg {
+        /// The received packet.
+        pub packet: Packet,
+        /// The acknowledgement data.
+        pub acknowledgement: Binary,
+    }
+
     impl From<SendPacketMsg> for super::ExecuteMsg {
         fn from(msg: SendPacketMsg) -> Self {
             Self::SendPacket(msg)
@@ -137,4 +165,9 @@ pub mod execute {
             Self::Timeout(msg)
         }
     }
+    impl From<WriteAcknowledgementMsg> for super::ExecuteMsg {
+        fn from(msg: WriteAcknowledgementMsg) -> Self {
+            Self::WriteAcknowledgement(msg)
+        }
+    }
 }
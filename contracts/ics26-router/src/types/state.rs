@@ -119,6 +119,31 @@ pub mod helpers {
         Ok(())
     }
 
+    /// Ensures that the packet has been received, i.e. its receipt is in the provable packet
+    /// receipt store.
+    ///
+    /// # Errors
+    /// Returns an error if the receipt cannot be found.
+    pub fn assert_packet_receipt(
+        storage: &dyn Storage,
+        packet: &ibc::Packet,
+    ) -> Result<(), ContractError> {
+        let item: PureItem = PacketReceiptPath {
+            port_id: packet.destination_port.clone(),
+            channel_id: packet.destination_channel.clone(),
+            sequence: packet.sequence,
+        }
+        .into();
+
+        if !item.exists(storage) {
+            return Err(ContractError::packet_receipt_not_found(
+                item.as_slice().to_vec(),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Commits an acknowledgment to the provable packet acknowledgment store.
     /// This is used to prove the `AcknowledgementPacket` in the counterparty chain.
     ///
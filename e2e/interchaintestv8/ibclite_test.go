@@ -567,7 +567,7 @@ func (s *IBCLiteTestSuite) TestTimeout() {
 		transferMsg := cw20base.MsgTransfer{
 			SourceChannel: testvalues.FirstWasmClientID,
 			Receiver:      s.UserB.FormattedAddress(),
-			Timeout:       &timeoutSeconds,
+			Timeout:       &cw20base.TransferTimeout{Relative: &timeoutSeconds},
 		}
 		cw20SendMsg := cw20base.ExecuteMsg{
 			Send: &cw20base.ExecuteMsg_Send{
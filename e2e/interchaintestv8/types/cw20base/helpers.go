@@ -13,12 +13,30 @@ type MsgTransfer struct {
 	// Don't use HumanAddress as this will likely have a different Bech32 prefix than we use
 	// and cannot be validated locally
 	Receiver string `json:"receiver"`
-	// How long the packet lives in seconds. If not specified, use default_timeout
-	Timeout *uint64 `json:"timeout,omitempty"`
+	// When the packet times out. If not specified, the default timeout of the channel is used.
+	Timeout *TransferTimeout `json:"timeout,omitempty"`
 	// An optional memo to add to the IBC transfer
 	Memo *string `json:"memo,omitempty"`
 }
 
+// TransferTimeout is the timeout of a transfer. Exactly one of the fields must be set.
+type TransferTimeout struct {
+	// The number of seconds after the current block time.
+	Relative *uint64 `json:"relative,omitempty"`
+	// An absolute timestamp in nanoseconds since epoch, as a string.
+	Timestamp *string `json:"timestamp,omitempty"`
+	// A block height of the counterparty chain.
+	Height *TransferTimeoutHeight `json:"height,omitempty"`
+}
+
+// TransferTimeoutHeight is a block height of the counterparty chain.
+type TransferTimeoutHeight struct {
+	// The revision number of the chain.
+	Revision uint64 `json:"revision"`
+	// The block height within the revision.
+	Height uint64 `json:"height"`
+}
+
 // Converts any object to a base64-encoded JSON binary.
 func ToJsonBinary(obj any) Binary {
 	bz, err := json.Marshal(obj)
@@ -0,0 +1,468 @@
+//! This module handles the execution logic of the contract.
+
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_ibc_lite_shared::types::{clients::error::LightClientError, error::ContractError};
+
+use crate::types::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state,
+};
+
+/// Instantiates a new contract.
+///
+/// # Errors
+/// Will return an error if the client or consensus state cannot be decoded.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let client_state: state::ClientState = cosmwasm_std::from_json(&msg.client_state)?;
+    let consensus_state: state::ConsensusState = cosmwasm_std::from_json(&msg.consensus_state)?;
+
+    state::CLIENT_STATE.save(deps.storage, &client_state)?;
+    state::CONSENSUS_STATES.save(
+        deps.storage,
+        (
+            client_state.latest_height.revision_number,
+            client_state.latest_height.revision_height,
+        ),
+        &consensus_state,
+    )?;
+
+    Ok(Response::default())
+}
+
+/// Handles the execution of the contract by routing the messages to the respective handlers.
+/// Can be called by anyone.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateState(msg) => {
+            execute::update_state(deps, env, cosmwasm_std::from_json(&msg.client_message)?)
+        }
+        ExecuteMsg::UpdateStateOnMisbehaviour(_) => execute::update_state_on_misbehaviour(deps),
+        ExecuteMsg::VerifyUpgradeAndUpdateState(_) => {
+            Err(LightClientError::UpgradeNotSupported.into())
+        }
+    }
+}
+
+/// Handles the query messages by routing them to the respective handlers.
+///
+/// # Errors
+/// Will return an error if the handler returns an error.
+#[allow(clippy::needless_pass_by_value)]
+#[cosmwasm_std::entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Status(_) => query::status(deps),
+        QueryMsg::ExportMetadata(_) => query::export_metadata(),
+        QueryMsg::TimestampAtHeight(msg) => query::timestamp_at_height(deps, msg.height.into()),
+        QueryMsg::VerifyClientMessage(_) => query::verify_client_message(deps),
+        QueryMsg::CheckForMisbehaviour(_) => query::check_for_misbehaviour(),
+        QueryMsg::VerifyMembership(_) | QueryMsg::VerifyNonMembership(_) => {
+            query::verify_membership(deps, msg.try_into()?)
+        }
+        QueryMsg::ClientState {} => query::client_state(deps),
+        QueryMsg::ConsensusState { height } => query::consensus_state(deps, height),
+        QueryMsg::LatestHeight {} => query::latest_height(deps),
+        QueryMsg::ConsensusHeights { start_after, limit } => {
+            query::consensus_heights(deps, start_after, limit)
+        }
+    }
+}
+
+mod execute {
+    use cw_ibc_lite_shared::types::clients::error::LightClientError;
+    use ibc_client_cw::types::ContractResult;
+
+    use crate::types::{msg::ClientMessage, state};
+
+    use super::{ContractError, DepsMut, Env, Response};
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn update_state(
+        deps: DepsMut,
+        _env: Env,
+        client_message: ClientMessage,
+    ) -> Result<Response, ContractError> {
+        let mut client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        match client_message {
+            ClientMessage::Update { height, timestamp } => {
+                if client_state.behavior == state::Behavior::RejectAll {
+                    return Err(LightClientError::InvalidHeader(
+                        "mock client rejects all updates".to_string(),
+                    )
+                    .into());
+                }
+
+                state::CONSENSUS_STATES.save(
+                    deps.storage,
+                    (height.revision_number, height.revision_height),
+                    &state::ConsensusState { timestamp },
+                )?;
+                if (height.revision_number, height.revision_height)
+                    > (
+                        client_state.latest_height.revision_number,
+                        client_state.latest_height.revision_height,
+                    )
+                {
+                    client_state.latest_height = height;
+                }
+            }
+            ClientMessage::SetBehavior(behavior) => client_state.behavior = behavior,
+            ClientMessage::SetStatus(status) => client_state.status = status,
+        }
+        state::CLIENT_STATE.save(deps.storage, &client_state)?;
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+
+    /// Freezes the client, unless the behavior is [`state::Behavior::RejectAll`].
+    pub fn update_state_on_misbehaviour(deps: DepsMut) -> Result<Response, ContractError> {
+        let mut client_state = state::CLIENT_STATE.load(deps.storage)?;
+        if client_state.behavior == state::Behavior::RejectAll {
+            return Err(LightClientError::InvalidMisbehaviour(
+                "mock client rejects all misbehaviour".to_string(),
+            )
+            .into());
+        }
+
+        client_state.status = state::Status::Frozen;
+        state::CLIENT_STATE.save(deps.storage, &client_state)?;
+
+        Ok(Response::new().set_data(cosmwasm_std::to_json_binary(&ContractResult::success())?))
+    }
+}
+
+mod query {
+    use cosmwasm_std::{Order, StdResult};
+    use cw_ibc_lite_shared::types::{
+        clients::{
+            error::LightClientError,
+            msg::{query_responses, VerifyMembershipQuery},
+        },
+        ibc::Height,
+    };
+    use cw_storage_plus::Bound;
+    use ibc_client_cw::types::ContractResult;
+
+    use crate::types::state;
+
+    use super::{Binary, ContractError, Deps};
+
+    pub fn status(deps: Deps) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(&query_responses::Status {
+            status: client_state.status.as_str().to_string(),
+        })?)
+    }
+
+    pub fn export_metadata() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ExportMetadata { metadata: vec![] },
+        )?)
+    }
+
+    pub fn timestamp_at_height(deps: Deps, height: Height) -> Result<Binary, ContractError> {
+        let consensus_state = state::CONSENSUS_STATES.load(
+            deps.storage,
+            (height.revision_number, height.revision_height),
+        )?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::TimestampAtHeight {
+                timestamp: consensus_state.timestamp,
+            },
+        )?)
+    }
+
+    pub fn verify_client_message(deps: Deps) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::VerifyClientMessage {
+                is_valid: client_state.behavior == state::Behavior::AcceptAll,
+            },
+        )?)
+    }
+
+    pub fn check_for_misbehaviour() -> Result<Binary, ContractError> {
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::CheckForMisbehaviour {
+                found_misbehaviour: false,
+            },
+        )?)
+    }
+
+    /// Accepts any proof if the client is active and the behavior is
+    /// [`state::Behavior::AcceptAll`].
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify_membership(
+        deps: Deps,
+        _query: VerifyMembershipQuery,
+    ) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        match client_state.status {
+            state::Status::Active => {}
+            state::Status::Frozen => return Err(LightClientError::ClientFrozen.into()),
+            state::Status::Expired => return Err(LightClientError::ClientExpired.into()),
+        }
+        if client_state.behavior == state::Behavior::RejectAll {
+            return Err(LightClientError::InvalidProof(
+                "mock client rejects all proofs".to_string(),
+            )
+            .into());
+        }
+
+        Ok(cosmwasm_std::to_json_binary(&ContractResult::success())?)
+    }
+
+    pub fn client_state(deps: Deps) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+        let frozen_height = (client_state.status == state::Status::Frozen)
+            .then(|| client_state.latest_height.clone());
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ClientState {
+                latest_height: client_state.latest_height,
+                frozen_height,
+                chain_id: None,
+                trusting_period: None,
+            },
+        )?)
+    }
+
+    pub fn consensus_state(deps: Deps, height: Height) -> Result<Binary, ContractError> {
+        let consensus_state = state::CONSENSUS_STATES.load(
+            deps.storage,
+            (height.revision_number, height.revision_height),
+        )?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusState {
+                timestamp: consensus_state.timestamp,
+                root: Binary::default(),
+            },
+        )?)
+    }
+
+    pub fn latest_height(deps: Deps) -> Result<Binary, ContractError> {
+        let client_state = state::CLIENT_STATE.load(deps.storage)?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::LatestHeight {
+                height: client_state.latest_height,
+            },
+        )?)
+    }
+
+    /// Default limit for pagination
+    const DEFAULT_LIMIT: u32 = 10;
+    /// Maximum limit for pagination
+    const MAX_LIMIT: u32 = 100;
+
+    pub fn consensus_heights(
+        deps: Deps,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> Result<Binary, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after
+            .map(|height| Bound::exclusive((height.revision_number, height.revision_height)));
+
+        let heights = state::CONSENSUS_STATES
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|key| {
+                key.map(|(revision_number, revision_height)| Height {
+                    revision_number,
+                    revision_height,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(cosmwasm_std::to_json_binary(
+            &query_responses::ConsensusHeights { heights },
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Binary, Deps, DepsMut, Response,
+    };
+    use ibc_client_cw::types::{
+        MerklePath, StatusMsg, TimestampAtHeightMsg, UpdateStateMsgRaw, VerifyMembershipMsgRaw,
+    };
+
+    use cw_ibc_lite_shared::types::{clients::msg::query_responses, ibc::Height};
+
+    use crate::types::{
+        msg::ClientMessage,
+        state::{Behavior, ClientState, ConsensusState, Status},
+    };
+
+    use super::{execute, instantiate, query, ContractError, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+    const fn height(revision_height: u64) -> Height {
+        Height {
+            revision_number: 1,
+            revision_height,
+        }
+    }
+
+    /// Instantiates an active client that accepts all proofs, whose latest height is `10` with
+    /// the timestamp `100`.
+    fn instantiate_client(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            client_state: cosmwasm_std::to_json_binary(&ClientState {
+                latest_height: height(10),
+                behavior: Behavior::default(),
+                status: Status::default(),
+            })
+            .unwrap(),
+            consensus_state: cosmwasm_std::to_json_binary(&ConsensusState { timestamp: 100 })
+                .unwrap(),
+            checksum: None,
+        };
+
+        instantiate(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
+
+    fn execute_client(deps: DepsMut, msg: &ClientMessage) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::UpdateState(UpdateStateMsgRaw {
+            client_message: cosmwasm_std::to_json_binary(msg).unwrap(),
+        });
+        execute(deps, mock_env(), mock_info("anyone", &[]), msg)
+    }
+
+    fn update(
+        deps: DepsMut,
+        revision_height: u64,
+        timestamp: u64,
+    ) -> Result<Response, ContractError> {
+        execute_client(
+            deps,
+            &ClientMessage::Update {
+                height: height(revision_height),
+                timestamp,
+            },
+        )
+    }
+
+    fn query_as<T: serde::de::DeserializeOwned>(deps: Deps, msg: QueryMsg) -> T {
+        cosmwasm_std::from_json(query(deps, mock_env(), msg).unwrap()).unwrap()
+    }
+
+    fn verify_membership(deps: Deps) -> Result<Binary, ContractError> {
+        let msg = VerifyMembershipMsgRaw {
+            proof: b"proof".to_vec(),
+            path: MerklePath {
+                key_path: vec!["ibc".to_string(), "path".to_string()],
+            },
+            value: b"value".to_vec(),
+            height: height(10).into(),
+            delay_time_period: 0,
+            delay_block_period: 0,
+        };
+        query(deps, mock_env(), QueryMsg::VerifyMembership(msg))
+    }
+
+    fn status(deps: Deps) -> String {
+        query_as::<query_responses::Status>(deps, QueryMsg::Status(StatusMsg {})).status
+    }
+
+    fn timestamp_at_height(deps: Deps, revision_height: u64) -> Result<Binary, ContractError> {
+        let msg = TimestampAtHeightMsg {
+            height: height(revision_height).into(),
+        };
+        query(deps, mock_env(), QueryMsg::TimestampAtHeight(msg))
+    }
+
+    #[test]
+    fn behavior_sets_the_validity_of_proofs_and_updates() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+        verify_membership(deps.as_ref()).unwrap();
+        update(deps.as_mut(), 11, 200).unwrap();
+
+        execute_client(
+            deps.as_mut(),
+            &ClientMessage::SetBehavior(Behavior::RejectAll),
+        )
+        .unwrap();
+        verify_membership(deps.as_ref()).unwrap_err();
+        update(deps.as_mut(), 12, 300).unwrap_err();
+        timestamp_at_height(deps.as_ref(), 12).unwrap_err();
+
+        // the client can always be reconfigured
+        execute_client(
+            deps.as_mut(),
+            &ClientMessage::SetBehavior(Behavior::AcceptAll),
+        )
+        .unwrap();
+        verify_membership(deps.as_ref()).unwrap();
+        update(deps.as_mut(), 12, 300).unwrap();
+    }
+
+    #[test]
+    fn proofs_are_rejected_unless_active() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+        assert_eq!(status(deps.as_ref()), "Active");
+
+        execute_client(deps.as_mut(), &ClientMessage::SetStatus(Status::Frozen)).unwrap();
+        assert_eq!(status(deps.as_ref()), "Frozen");
+        verify_membership(deps.as_ref()).unwrap_err();
+        let client_state: query_responses::ClientState =
+            query_as(deps.as_ref(), QueryMsg::ClientState {});
+        assert_eq!(client_state.frozen_height, Some(height(10)));
+
+        execute_client(deps.as_mut(), &ClientMessage::SetStatus(Status::Expired)).unwrap();
+        assert_eq!(status(deps.as_ref()), "Expired");
+        verify_membership(deps.as_ref()).unwrap_err();
+
+        execute_client(deps.as_mut(), &ClientMessage::SetStatus(Status::Active)).unwrap();
+        assert_eq!(status(deps.as_ref()), "Active");
+        verify_membership(deps.as_ref()).unwrap();
+    }
+
+    #[test]
+    fn timestamps_are_scripted_per_height() {
+        let mut deps = mock_dependencies();
+        instantiate_client(deps.as_mut());
+        update(deps.as_mut(), 12, 300).unwrap();
+        // an update below the latest height does not lower it
+        update(deps.as_mut(), 11, 200).unwrap();
+
+        let latest: query_responses::LatestHeight =
+            query_as(deps.as_ref(), QueryMsg::LatestHeight {});
+        assert_eq!(latest.height, height(12));
+        for (revision_height, timestamp) in [(10, 100), (11, 200), (12, 300)] {
+            let response: query_responses::TimestampAtHeight = cosmwasm_std::from_json(
+                timestamp_at_height(deps.as_ref(), revision_height).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(response.timestamp, timestamp);
+        }
+        timestamp_at_height(deps.as_ref(), 13).unwrap_err();
+    }
+}
@@ -0,0 +1,4 @@
+//! This file contains helper functions for working with this contract from
+//! external contracts.
+
+pub use cw_ibc_lite_shared::types::clients::helpers::*;
@@ -0,0 +1,10 @@
+//! # Keys
+//!
+//! Contains key constants definitions for the contract.
+
+/// `STATUS_ACTIVE` is the status of an active client.
+pub const STATUS_ACTIVE: &str = "Active";
+/// `STATUS_FROZEN` is the status of a frozen client.
+pub const STATUS_FROZEN: &str = "Frozen";
+/// `STATUS_EXPIRED` is the status of an expired client.
+pub const STATUS_EXPIRED: &str = "Expired";
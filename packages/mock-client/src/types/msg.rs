@@ -0,0 +1,29 @@
+//! # Messages
+//!
+//! This module defines the messages that this contract receives. The client messages are JSON
+//! encoded into the raw bytes of the shared light client messages.
+
+use cosmwasm_schema::cw_serde;
+
+use cw_ibc_lite_shared::types::ibc::Height;
+
+pub use cw_ibc_lite_shared::types::clients::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+use super::state::{Behavior, Status};
+
+/// The client messages accepted by the contract.
+#[cw_serde]
+pub enum ClientMessage {
+    /// Set the timestamp at the given height, and advance the latest height if it is higher.
+    /// Rejected if the behavior is [`Behavior::RejectAll`].
+    Update {
+        /// The height to set the timestamp at.
+        height: Height,
+        /// The timestamp in nanoseconds since the unix epoch.
+        timestamp: u64,
+    },
+    /// Set the verification behavior of the client.
+    SetBehavior(Behavior),
+    /// Set the status of the client.
+    SetStatus(Status),
+}
@@ -0,0 +1,69 @@
+//! This module defines the state storage of the Contract.
+
+use cosmwasm_schema::cw_serde;
+
+use cw_ibc_lite_shared::types::ibc::Height;
+use cw_storage_plus::{Item, Map};
+
+/// The item for storing the [`ClientState`].
+pub const CLIENT_STATE: Item<ClientState> = Item::new("client_state");
+
+/// The map for storing the scripted [`ConsensusState`]s.
+/// Maps (`revision_number`, `revision_height`) to the consensus state at that height.
+pub const CONSENSUS_STATES: Map<(u64, u64), ConsensusState> = Map::new("consensus_states");
+
+/// The client state of the mock client.
+#[cw_serde]
+pub struct ClientState {
+    /// The latest height of the client.
+    pub latest_height: Height,
+    /// The verification behavior of the client.
+    #[serde(default)]
+    pub behavior: Behavior,
+    /// The status of the client.
+    #[serde(default)]
+    pub status: Status,
+}
+
+/// The consensus state of the mock client.
+#[cw_serde]
+pub struct ConsensusState {
+    /// The timestamp in nanoseconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// The verification behavior of the mock client.
+#[cw_serde]
+#[derive(Default)]
+pub enum Behavior {
+    /// All proofs and client messages are valid.
+    #[default]
+    AcceptAll,
+    /// All proofs and client messages are invalid.
+    RejectAll,
+}
+
+/// The status of the mock client.
+#[cw_serde]
+#[derive(Default)]
+pub enum Status {
+    /// The client is active.
+    #[default]
+    Active,
+    /// The client is frozen.
+    Frozen,
+    /// The client is expired.
+    Expired,
+}
+
+impl Status {
+    /// Returns the status string as defined by `ibc-go`.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => super::keys::STATUS_ACTIVE,
+            Self::Frozen => super::keys::STATUS_FROZEN,
+            Self::Expired => super::keys::STATUS_EXPIRED,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Defines shared error types for light client contracts in ibc-lite.
+
+/// `LightClientError` is the error type returned by light client contracts.
+#[allow(missing_docs, clippy::module_name_repetitions)]
+#[non_exhaustive]
+#[derive(thiserror::Error, Debug)]
+pub enum LightClientError {
+    #[error("client is frozen")]
+    ClientFrozen,
+    #[error("client is expired")]
+    ClientExpired,
+    #[error("unexpected type url: expected {expected}, actual {actual}")]
+    UnexpectedTypeUrl { expected: String, actual: String },
+    #[error("unsupported public key type: {0}")]
+    UnsupportedPublicKey(String),
+    #[error("unsupported signature data, only single signatures are supported")]
+    UnsupportedSignatureData,
+    #[error("signature verification failed")]
+    InvalidSignature,
+    #[error("invalid client state: {0}")]
+    InvalidClientState(String),
+    #[error("invalid consensus state: {0}")]
+    InvalidConsensusState(String),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("invalid misbehaviour: {0}")]
+    InvalidMisbehaviour(String),
+    #[error("invalid proof height: expected {expected}, actual {actual}")]
+    InvalidProofHeight { expected: String, actual: String },
+    #[error("invalid proof timestamp: consensus timestamp {consensus} is greater than proof timestamp {proof}")]
+    InvalidProofTimestamp { consensus: u64, proof: u64 },
+    #[error("invalid proof: {0}")]
+    InvalidProof(String),
+    #[error("empty proof")]
+    EmptyProof,
+    #[error("merkle path is empty")]
+    EmptyMerklePath,
+    #[error("client upgrades are not supported by this client")]
+    UpgradeNotSupported,
+}
+
+impl LightClientError {
+    /// Creates [`LightClientError::UnexpectedTypeUrl`] error.
+    #[must_use]
+    pub fn unexpected_type_url(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self::UnexpectedTypeUrl {
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+
+    /// Creates [`LightClientError::InvalidProofHeight`] error.
+    #[must_use]
+    pub fn invalid_proof_height(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self::InvalidProofHeight {
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+}
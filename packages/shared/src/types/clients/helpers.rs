@@ -2,6 +2,7 @@
 //! from external contracts.
 
 use super::msg::{self, query_responses};
+use crate::types::ibc::Height;
 
 use ibc_client_cw::types::{
     CheckForMisbehaviourMsgRaw, ContractResult, ExportMetadataMsg, StatusMsg, TimestampAtHeightMsg,
@@ -260,6 +261,55 @@ impl<'a> LightClientContractQuerier<'a> {
             .query_wasm_smart(&self.addr, &msg::QueryMsg::VerifyNonMembership(msg.into()))
     }
 
+    /// `client_state` sends a [`msg::QueryMsg::ClientState`] query to this contract.
+    ///
+    /// # Errors
+    /// This function returns an error if the query fails
+    pub fn client_state(&self) -> StdResult<query_responses::ClientState> {
+        self.querier
+            .query_wasm_smart(&self.addr, &msg::QueryMsg::ClientState {})
+    }
+
+    /// `consensus_state` sends a [`msg::QueryMsg::ConsensusState`] query to this contract.
+    ///
+    /// # Errors
+    /// This function returns an error if the query fails
+    pub fn consensus_state(
+        &self,
+        height: impl Into<Height>,
+    ) -> StdResult<query_responses::ConsensusState> {
+        self.querier.query_wasm_smart(
+            &self.addr,
+            &msg::QueryMsg::ConsensusState {
+                height: height.into(),
+            },
+        )
+    }
+
+    /// `latest_height` sends a [`msg::QueryMsg::LatestHeight`] query to this contract.
+    ///
+    /// # Errors
+    /// This function returns an error if the query fails
+    pub fn latest_height(&self) -> StdResult<query_responses::LatestHeight> {
+        self.querier
+            .query_wasm_smart(&self.addr, &msg::QueryMsg::LatestHeight {})
+    }
+
+    /// `consensus_heights` sends a [`msg::QueryMsg::ConsensusHeights`] query to this contract.
+    ///
+    /// # Errors
+    /// This function returns an error if the query fails
+    pub fn consensus_heights(
+        &self,
+        start_after: Option<Height>,
+        limit: Option<u32>,
+    ) -> StdResult<query_responses::ConsensusHeights> {
+        self.querier.query_wasm_smart(
+            &self.addr,
+            &msg::QueryMsg::ConsensusHeights { start_after, limit },
+        )
+    }
+
     /// `smart_raw` sends a [`msg::QueryMsg`] query to this contract.
     /// It returns the raw [`cosmwasm_std::Binary`] response from the contract.
     ///
@@ -1,5 +1,6 @@
 //! This module contains types used by light client contracts in ibc-lite
 
+pub mod error;
 pub mod helpers;
 #[allow(clippy::module_name_repetitions)]
 pub mod msg;
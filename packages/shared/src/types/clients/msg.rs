@@ -3,12 +3,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Binary;
 use ibc_client_cw::types::{
-    CheckForMisbehaviourMsgRaw, ExportMetadataMsg, StatusMsg, TimestampAtHeightMsg,
+    CheckForMisbehaviourMsgRaw, ExportMetadataMsg, MerklePath, StatusMsg, TimestampAtHeightMsg,
     UpdateStateMsgRaw, UpdateStateOnMisbehaviourMsgRaw, VerifyClientMessageRaw,
     VerifyMembershipMsgRaw, VerifyNonMembershipMsgRaw, VerifyUpgradeAndUpdateStateMsgRaw,
 };
 
-use super::super::error::ContractError;
+use super::super::{error::ContractError, ibc::Height};
 
 /// Instantiate message for all light client contracts in ibc-lite
 #[cw_serde]
@@ -17,6 +17,11 @@ pub struct InstantiateMsg {
     pub client_state: Binary,
     /// The initial consensus state.
     pub consensus_state: Binary,
+    /// The checksum of the light client contract code.
+    /// This is set by cw-ibc-lite-ics02-client when creating the client, any value provided by
+    /// the client creator is overwritten.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<Binary>,
 }
 
 /// Execute messages supported by all light client contracts in ibc-lite
@@ -55,11 +60,53 @@ pub enum QueryMsg {
     /// Verify non-membership
     #[returns(ibc_client_cw::types::ContractResult)]
     VerifyNonMembership(VerifyNonMembershipMsgRaw),
+    /// Get the client state
+    #[returns(query_responses::ClientState)]
+    ClientState {},
+    /// Get the consensus state at the given height
+    #[returns(query_responses::ConsensusState)]
+    ConsensusState {
+        /// The height of the consensus state
+        height: Height,
+    },
+    /// Get the latest height of the client
+    #[returns(query_responses::LatestHeight)]
+    LatestHeight {},
+    /// Get the heights of all stored consensus states in ascending order
+    #[returns(query_responses::ConsensusHeights)]
+    ConsensusHeights {
+        /// start pagination after this height
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_after: Option<Height>,
+        /// limit results to this number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+}
+
+/// A membership or non-membership proof to be verified by a light client.
+/// Light clients convert both [`QueryMsg::VerifyMembership`] and [`QueryMsg::VerifyNonMembership`]
+/// into this, and verify it in their query handler where storage is read-only.
+#[cw_serde]
+pub struct VerifyMembershipQuery {
+    /// The height of the consensus state to verify the proof against.
+    pub height: Height,
+    /// The proof of (non-)membership.
+    pub proof: Binary,
+    /// The merkle path of the key, starting with the prefix of the counterparty store.
+    pub key_path: Vec<String>,
+    /// The value stored at the path, or [`None`] if nothing may be stored at the path.
+    pub value: Option<Binary>,
+    /// The delay period in nanoseconds.
+    pub delay_time_period: u64,
+    /// The delay period in blocks.
+    pub delay_block_period: u64,
 }
 
 /// Contains the query responses supported by all light client contracts in ibc-lite
 pub mod query_responses {
-    use super::{cw_serde, ContractError};
+    use super::{cw_serde, ContractError, Height};
+    use cosmwasm_std::Binary;
     use ibc_client_cw::types::GenesisMetadata;
 
     /// The response to [`super::QueryMsg::Status`]
@@ -94,6 +141,42 @@ pub mod query_responses {
         pub found_misbehaviour: bool,
     }
 
+    /// The response to [`super::QueryMsg::ClientState`]
+    #[cw_serde]
+    pub struct ClientState {
+        /// The latest height of the client
+        pub latest_height: Height,
+        /// The height at which the client was frozen, if it is frozen
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub frozen_height: Option<Height>,
+        /// The chain id of the counterparty, if tracked by the client
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub chain_id: Option<String>,
+        /// The trusting period of the client in seconds, if the client has one
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub trusting_period: Option<u64>,
+    }
+    /// The response to [`super::QueryMsg::ConsensusState`]
+    #[cw_serde]
+    pub struct ConsensusState {
+        /// The timestamp of the consensus state in nanoseconds since the unix epoch
+        pub timestamp: u64,
+        /// The commitment root of the consensus state
+        pub root: Binary,
+    }
+    /// The response to [`super::QueryMsg::LatestHeight`]
+    #[cw_serde]
+    pub struct LatestHeight {
+        /// The latest height of the client
+        pub height: Height,
+    }
+    /// The response to [`super::QueryMsg::ConsensusHeights`]
+    #[cw_serde]
+    pub struct ConsensusHeights {
+        /// The heights of the stored consensus states
+        pub heights: Vec<Height>,
+    }
+
     impl TryFrom<ibc_client_cw::types::QueryResponse> for Status {
         type Error = ContractError;
 
@@ -201,8 +284,7 @@ impl From<InstantiateMsg> for ibc_client_cw::types::InstantiateMsg {
         Self {
             client_state: instantiate_msg.client_state.into(),
             consensus_state: instantiate_msg.consensus_state.into(),
-            // TODO: Add checksum
-            checksum: Vec::new(),
+            checksum: instantiate_msg.checksum.map(Into::into).unwrap_or_default(),
         }
     }
 }
@@ -232,14 +314,157 @@ impl TryFrom<QueryMsg> for ibc_client_cw::types::QueryMsg {
     }
 }
 
-impl TryFrom<QueryMsg> for ibc_client_cw::types::SudoMsg {
+impl TryFrom<QueryMsg> for VerifyMembershipQuery {
     type Error = ContractError;
 
     fn try_from(query_msg: QueryMsg) -> Result<Self, Self::Error> {
         match query_msg {
-            QueryMsg::VerifyMembership(msg) => Ok(Self::VerifyMembership(msg)),
-            QueryMsg::VerifyNonMembership(msg) => Ok(Self::VerifyNonMembership(msg)),
+            QueryMsg::VerifyMembership(msg) => Ok(Self {
+                height: msg.height.into(),
+                proof: msg.proof.into(),
+                key_path: msg.path.key_path,
+                value: Some(msg.value.into()),
+                delay_time_period: msg.delay_time_period,
+                delay_block_period: msg.delay_block_period,
+            }),
+            QueryMsg::VerifyNonMembership(msg) => Ok(Self {
+                height: msg.height.into(),
+                proof: msg.proof.into(),
+                key_path: msg.path.key_path,
+                value: None,
+                delay_time_period: msg.delay_time_period,
+                delay_block_period: msg.delay_block_period,
+            }),
             _ => Err(ContractError::try_from::<QueryMsg, Self>()),
         }
     }
 }
+
+impl TryFrom<VerifyMembershipQuery> for VerifyMembershipMsgRaw {
+    type Error = ContractError;
+
+    fn try_from(query: VerifyMembershipQuery) -> Result<Self, Self::Error> {
+        let value = query
+            .value
+            .ok_or_else(ContractError::try_from::<VerifyMembershipQuery, Self>)?;
+
+        Ok(Self {
+            proof: query.proof.into(),
+            path: MerklePath {
+                key_path: query.key_path,
+            },
+            value: value.into(),
+            height: query.height.into(),
+            delay_time_period: query.delay_time_period,
+            delay_block_period: query.delay_block_period,
+        })
+    }
+}
+
+impl TryFrom<VerifyMembershipQuery> for VerifyNonMembershipMsgRaw {
+    type Error = ContractError;
+
+    fn try_from(query: VerifyMembershipQuery) -> Result<Self, Self::Error> {
+        if query.value.is_some() {
+            return Err(ContractError::try_from::<VerifyMembershipQuery, Self>());
+        }
+
+        Ok(Self {
+            proof: query.proof.into(),
+            path: MerklePath {
+                key_path: query.key_path,
+            },
+            height: query.height.into(),
+            delay_time_period: query.delay_time_period,
+            delay_block_period: query.delay_block_period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_client_cw::types::{MerklePath, VerifyMembershipMsgRaw, VerifyNonMembershipMsgRaw};
+
+    use super::{Height, QueryMsg, VerifyMembershipQuery};
+
+    fn path() -> MerklePath {
+        MerklePath {
+            key_path: vec![
+                "ibc".to_string(),
+                "commitments/ports/p/channels/c/sequences/1".to_string(),
+            ],
+        }
+    }
+
+    fn membership_msg() -> VerifyMembershipMsgRaw {
+        VerifyMembershipMsgRaw {
+            proof: b"proof".to_vec(),
+            path: path(),
+            value: b"value".to_vec(),
+            height: Height {
+                revision_number: 1,
+                revision_height: 10,
+            }
+            .into(),
+            delay_time_period: 1,
+            delay_block_period: 2,
+        }
+    }
+
+    fn non_membership_msg() -> VerifyNonMembershipMsgRaw {
+        let msg = membership_msg();
+        VerifyNonMembershipMsgRaw {
+            proof: msg.proof,
+            path: msg.path,
+            height: msg.height,
+            delay_time_period: msg.delay_time_period,
+            delay_block_period: msg.delay_block_period,
+        }
+    }
+
+    #[test]
+    fn membership_query() {
+        let query =
+            VerifyMembershipQuery::try_from(QueryMsg::VerifyMembership(membership_msg())).unwrap();
+        assert_eq!(
+            query,
+            VerifyMembershipQuery {
+                height: Height {
+                    revision_number: 1,
+                    revision_height: 10,
+                },
+                proof: b"proof".into(),
+                key_path: path().key_path,
+                value: Some(b"value".into()),
+                delay_time_period: 1,
+                delay_block_period: 2,
+            }
+        );
+
+        VerifyNonMembershipMsgRaw::try_from(query.clone()).unwrap_err();
+        assert_eq!(
+            VerifyMembershipMsgRaw::try_from(query).unwrap(),
+            membership_msg()
+        );
+    }
+
+    #[test]
+    fn non_membership_query() {
+        let query =
+            VerifyMembershipQuery::try_from(QueryMsg::VerifyNonMembership(non_membership_msg()))
+                .unwrap();
+        assert_eq!(query.value, None);
+        assert_eq!(query.key_path, path().key_path);
+
+        VerifyMembershipMsgRaw::try_from(query.clone()).unwrap_err();
+        assert_eq!(
+            VerifyNonMembershipMsgRaw::try_from(query).unwrap(),
+            non_membership_msg()
+        );
+    }
+
+    #[test]
+    fn other_queries_are_not_membership_queries() {
+        VerifyMembershipQuery::try_from(QueryMsg::LatestHeight {}).unwrap_err();
+    }
+}
@@ -22,6 +22,12 @@ pub enum ContractError {
     TransferError(#[from] super::transfer::error::TransferError),
     #[error("{0}")]
     BufanyError(#[from] anybuf::BufanyError),
+    #[error("{0}")]
+    LightClientError(#[from] super::clients::error::LightClientError),
+    #[error("{0}")]
+    ProstDecodeError(#[from] prost::DecodeError),
+    #[error("{0}")]
+    VerificationError(#[from] cosmwasm_std::VerificationError),
 
     #[error("unauthorized")]
     Unauthorized,
@@ -34,6 +40,13 @@ pub enum ContractError {
     },
     #[error("unknown reply id: {0}")]
     UnknownReplyId(u64),
+    #[error("unexpected funds: this message does not accept funds")]
+    UnexpectedFunds,
+
+    #[error("insufficient client creation fee: required {required}")]
+    InsufficientCreationFee { required: String },
+    #[error("client creation quota exceeded: at most {max} clients per creator")]
+    ClientQuotaExceeded { max: u64 },
 
     #[error("counterparty already provided")]
     CounterpartyAlreadyProvided,
@@ -53,6 +66,8 @@ pub enum ContractError {
     InvalidRevisionNumber { current: u64, revision: u64 },
     #[error("empty timestamp")]
     EmptyTimestamp,
+    #[error("timeout height and timestamp cannot both be empty")]
+    EmptyTimeout,
     #[error("packet already commited: key: {:02x?}", key)]
     PacketAlreadyCommited { key: Vec<u8> },
     #[error(
@@ -66,12 +81,12 @@ pub enum ContractError {
     },
     #[error("packet commitment not found: key: {:02x?}", key)]
     PacketCommitmentNotFound { key: Vec<u8> },
+    #[error("packet receipt not found: key: {:02x?}", key)]
+    PacketReceiptNotFound { key: Vec<u8> },
     #[error("merkle prefix is empty, it must at least have one element (can be empty string)")]
     EmptyMerklePrefix,
 
-    #[error(
-        "recv packet callback must return an acknowledgement data, but it returned nothing, async acknowledgement is not supported"
-    )]
+    #[error("an acknowledgement must not be empty")]
     RecvPacketCallbackNoResponse,
 }
 
@@ -94,6 +109,18 @@ impl ContractError {
         }
     }
 
+    /// Returns a new [`ContractError::InsufficientCreationFee`] with the given required fee.
+    #[must_use]
+    pub fn insufficient_creation_fee(required: &[cosmwasm_std::Coin]) -> Self {
+        Self::InsufficientCreationFee {
+            required: required
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+
     /// Returns a new [`ContractError::InvalidCounterparty`] with the given expected and actual
     /// values.
     #[must_use]
@@ -140,6 +167,12 @@ impl ContractError {
     pub const fn packet_commitment_not_found(key: Vec<u8>) -> Self {
         Self::PacketCommitmentNotFound { key }
     }
+
+    /// Returns a new [`ContractError::PacketReceiptNotFound`] with the given key.
+    #[must_use]
+    pub const fn packet_receipt_not_found(key: Vec<u8>) -> Self {
+        Self::PacketReceiptNotFound { key }
+    }
 }
 
 impl From<ibc_client_cw::types::ContractError> for ContractError {
@@ -190,6 +190,15 @@ impl From<Height> for ibc_core_client_types::Height {
     }
 }
 
+impl From<ibc_core_client_types::Height> for Height {
+    fn from(height: ibc_core_client_types::Height) -> Self {
+        Self {
+            revision_number: height.revision_number(),
+            revision_height: height.revision_height(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::transfer::packet::Ics20Ack;
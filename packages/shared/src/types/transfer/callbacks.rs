@@ -0,0 +1,158 @@
+//! This module defines the contract callbacks of ICS-20 transfers: destination callbacks
+//! requested in the memo of incoming packets, and source callbacks notifying the senders of
+//! outgoing packets.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, CosmosMsg, StdResult, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{error::TransferError, packet::Ics20PacketV2};
+use crate::types::ibc;
+
+/// The destination callback in the memo of an ICS-20 packet, in the style of `ibc-hooks`:
+/// `{"wasm":{"contract":"<address>","msg":"<base64 encoded execute message>"}}`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DestinationCallback {
+    /// The contract executed with the received tokens. It must be the receiver of the packet.
+    pub contract: String,
+    /// The message the contract is executed with.
+    pub msg: Binary,
+}
+
+/// Used to find out whether a memo has a destination callback, before parsing it.
+#[derive(Deserialize)]
+struct RawCallbackMetadata {
+    #[serde(default)]
+    wasm: Option<serde::de::IgnoredAny>,
+}
+
+/// Used to parse the destination callback of a memo. Unknown fields are ignored.
+#[derive(Deserialize)]
+struct CallbackMetadata {
+    wasm: DestinationCallback,
+}
+
+impl Ics20PacketV2 {
+    /// Returns the destination callback in the memo of the packet, if any. A memo that is not
+    /// JSON or has no destination callback is not an error.
+    ///
+    /// # Errors
+    /// Returns an error if the destination callback is malformed, or its contract is not the
+    /// receiver of the packet.
+    pub fn destination_callback(&self) -> Result<Option<DestinationCallback>, TransferError> {
+        let Some(memo) = self.memo.as_deref() else {
+            return Ok(None);
+        };
+        match cosmwasm_std::from_json::<RawCallbackMetadata>(memo) {
+            Ok(RawCallbackMetadata { wasm: Some(_) }) => {}
+            _ => return Ok(None),
+        }
+
+        let callback = cosmwasm_std::from_json::<CallbackMetadata>(memo)
+            .map_err(|e| TransferError::InvalidCallback(e.to_string()))?
+            .wasm;
+        // NOTE: The receiver must be the contract, so that the tokens are not credited to an
+        // account that did not ask for them.
+        if callback.contract != self.receiver {
+            return Err(TransferError::InvalidCallback(format!(
+                "contract {} is not the receiver {}",
+                callback.contract, self.receiver
+            )));
+        }
+
+        Ok(Some(callback))
+    }
+}
+
+/// The source callback sent to the contract registered by the sender of a transfer, once the
+/// packet is acknowledged or times out.
+#[cw_serde]
+pub struct IbcTransferCallback {
+    /// The packet that was sent.
+    pub packet: ibc::Packet,
+    /// The sequence of the packet.
+    pub sequence: u64,
+    /// The outcome of the transfer.
+    pub outcome: TransferOutcome,
+}
+
+/// The outcome of a transfer, see [`IbcTransferCallback`].
+#[cw_serde]
+pub enum TransferOutcome {
+    /// The packet was acknowledged successfully, and the tokens were received.
+    Success,
+    /// The packet was acknowledged with an error, and the tokens were refunded.
+    Error {
+        /// The error in the acknowledgement.
+        error: String,
+    },
+    /// The packet timed out, and the tokens were refunded.
+    Timeout,
+}
+
+/// This is just a helper to properly serialize [`IbcTransferCallback`].
+/// The callback contract should include this variant in its larger `ExecuteMsg` enum.
+#[cw_serde]
+enum SourceCallbackExecuteMsg {
+    IbcTransferCallback(IbcTransferCallback),
+}
+
+impl IbcTransferCallback {
+    /// `into_cosmos_msg` converts this callback into a [`CosmosMsg`] executing the callback
+    /// contract.
+    ///
+    /// # Errors
+    /// This function returns an error if the message cannot be serialized.
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: cosmwasm_std::to_json_binary(&SourceCallbackExecuteMsg::IbcTransferCallback(
+                self,
+            ))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::transfer::packet::{Forwarding, Token};
+
+    fn packet(memo: Option<&str>) -> Ics20PacketV2 {
+        Ics20PacketV2 {
+            tokens: vec![Token {
+                denom: "uatom".to_string(),
+                amount: 100u128.into(),
+            }],
+            receiver: "dex".to_string(),
+            sender: "alice".to_string(),
+            memo: memo.map(ToString::to_string),
+            forwarding: Forwarding::default(),
+        }
+    }
+
+    #[test]
+    fn destination_callback() {
+        assert_eq!(packet(None).destination_callback().unwrap(), None);
+        assert_eq!(packet(Some("hi")).destination_callback().unwrap(), None);
+        assert_eq!(
+            packet(Some(r#"{"forward":{}}"#))
+                .destination_callback()
+                .unwrap(),
+            None
+        );
+
+        let memo = r#"{"wasm":{"contract":"dex","msg":"eyJzd2FwIjp7fX0="}}"#;
+        let callback = packet(Some(memo)).destination_callback().unwrap().unwrap();
+        assert_eq!(callback.contract, "dex");
+        assert_eq!(callback.msg.as_slice(), br#"{"swap":{}}"#);
+
+        let memo = r#"{"wasm":{"contract":"other","msg":"e30="}}"#;
+        assert!(packet(Some(memo)).destination_callback().is_err());
+        let memo = r#"{"wasm":{"contract":"dex"}}"#;
+        assert!(packet(Some(memo)).destination_callback().is_err());
+    }
+}
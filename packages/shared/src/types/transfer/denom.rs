@@ -0,0 +1,139 @@
+//! This module defines the denom trace of tokens transferred with ICS-20.
+
+use std::str::FromStr;
+
+use cosmwasm_std::HexBinary;
+use ibc_core_host::types::identifiers::{ClientId, PortId};
+use sha2::{Digest, Sha256};
+
+use super::error::TransferError;
+
+/// The prefix of the hashed denom of a [`DenomTrace`].
+pub const IBC_DENOM_PREFIX: &str = "ibc";
+
+/// The path a foreign token took to reach this chain, and its base denom on the chain it
+/// originated from.
+#[allow(clippy::module_name_repetitions)]
+#[cosmwasm_schema::cw_serde]
+pub struct DenomTrace {
+    /// The `{port_id}/{channel_id}` hops the token was received on, most recent first.
+    /// Empty for native tokens.
+    pub path: String,
+    /// The base denom on the chain the token originated from.
+    pub base_denom: String,
+}
+
+impl DenomTrace {
+    /// Returns the full ICS-20 `{path}/{base_denom}` form of the trace.
+    #[must_use]
+    pub fn full_path(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+
+    /// Returns the uppercase hex encoded sha256 hash of the full path.
+    #[must_use]
+    pub fn hash(&self) -> String {
+        HexBinary::from(Sha256::digest(self.full_path().as_bytes()).as_slice())
+            .to_hex()
+            .to_uppercase()
+    }
+
+    /// Returns the `ibc/{hash}` denom of the trace, or the base denom for native tokens.
+    #[must_use]
+    pub fn ibc_denom(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{IBC_DENOM_PREFIX}/{}", self.hash())
+        }
+    }
+}
+
+impl FromStr for DenomTrace {
+    type Err = TransferError;
+
+    /// Parses the full path of a denom trace. Leading segment pairs that are a valid port id
+    /// followed by a valid channel id make up the path, the rest is the base denom.
+    fn from_str(full_path: &str) -> Result<Self, Self::Err> {
+        if full_path.is_empty() {
+            return Err(TransferError::EmptyDenom);
+        }
+
+        let segments: Vec<&str> = full_path.split('/').collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(TransferError::InvalidDenomTrace(full_path.to_string()));
+        }
+
+        let mut hops = 0;
+        while segments.len() > 2 * hops + 2
+            && PortId::from_str(segments[2 * hops]).is_ok()
+            && ClientId::from_str(segments[2 * hops + 1]).is_ok()
+        {
+            hops += 1;
+        }
+
+        Ok(Self {
+            path: segments[..2 * hops].join("/"),
+            base_denom: segments[2 * hops..].join("/"),
+        })
+    }
+}
+
+/// Parses the hash of an `ibc/{hash}` denom. The prefix is optional.
+///
+/// # Errors
+/// Returns an error if the hash is not a hex encoded sha256 hash.
+pub fn parse_denom_hash(denom: &str) -> Result<String, TransferError> {
+    let hash = denom
+        .strip_prefix(IBC_DENOM_PREFIX)
+        .and_then(|hash| hash.strip_prefix('/'))
+        .unwrap_or(denom);
+
+    match HexBinary::from_hex(hash) {
+        Ok(bytes) if bytes.len() == 32 => Ok(hash.to_uppercase()),
+        _ => Err(TransferError::InvalidDenomHash(denom.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_denom_trace() {
+        let trace: DenomTrace = "transfer/08-wasm-0/uatom".parse().unwrap();
+        assert_eq!(trace.path, "transfer/08-wasm-0");
+        assert_eq!(trace.base_denom, "uatom");
+        assert_eq!(trace.full_path(), "transfer/08-wasm-0/uatom");
+
+        let trace: DenomTrace = "transfer/08-wasm-0/transfer/07-tendermint-1/gamm/pool/1"
+            .parse()
+            .unwrap();
+        assert_eq!(trace.path, "transfer/08-wasm-0/transfer/07-tendermint-1");
+        assert_eq!(trace.base_denom, "gamm/pool/1");
+
+        let trace: DenomTrace = "uatom".parse().unwrap();
+        assert!(trace.path.is_empty());
+        assert_eq!(trace.ibc_denom(), "uatom");
+
+        assert!("".parse::<DenomTrace>().is_err());
+        assert!("transfer//uatom".parse::<DenomTrace>().is_err());
+    }
+
+    #[test]
+    fn denom_hash() {
+        let trace: DenomTrace = "transfer/channel-0/uatom".parse().unwrap();
+        // Matches the hash computed by `ibc-go` for the same trace.
+        let hash = "27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+        assert_eq!(trace.hash(), hash);
+        assert_eq!(trace.ibc_denom(), format!("ibc/{hash}"));
+
+        assert_eq!(parse_denom_hash(&trace.ibc_denom()).unwrap(), hash);
+        assert_eq!(parse_denom_hash(&hash.to_lowercase()).unwrap(), hash);
+        assert!(parse_denom_hash("ibc/1234").is_err());
+    }
+}
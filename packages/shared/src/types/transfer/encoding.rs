@@ -0,0 +1,246 @@
+//! This module defines the encodings of ICS-20 packet data: the cosmwasm JSON encoding, and the
+//! protobuf encoding of `FungibleTokenPacketData` used by `ibc-go`. Acknowledgements are always
+//! JSON encoded, as in `ibc-go`. Only `ics20-1` packet data can be protobuf encoded.
+
+use std::str::FromStr;
+
+use anybuf::{Anybuf, Bufany};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Uint128};
+
+use super::{
+    error::TransferError,
+    packet::{Ics20BatchPacket, Ics20Packet, Ics20PacketData, Ics20PacketV2},
+};
+
+/// The content type of JSON encoded packet data.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+/// The content type of protobuf encoded packet data.
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// The encoding of the data of an ICS-20 packet.
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum PacketEncoding {
+    /// The cosmwasm JSON encoding.
+    #[default]
+    Json,
+    /// The protobuf encoding of `FungibleTokenPacketData` in `ibc-go`. Only `ics20-1` packet
+    /// data can be protobuf encoded.
+    Protobuf,
+}
+
+impl PacketEncoding {
+    /// Returns the content type of the encoding.
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::Protobuf => PROTOBUF_CONTENT_TYPE,
+        }
+    }
+}
+
+/// The field numbers of `ibc.applications.transfer.v2.FungibleTokenPacketData`.
+mod packet_fields {
+    pub const DENOM: u32 = 1;
+    pub const AMOUNT: u32 = 2;
+    pub const SENDER: u32 = 3;
+    pub const RECEIVER: u32 = 4;
+    pub const MEMO: u32 = 5;
+}
+
+impl Ics20PacketData {
+    /// Decodes packet data in the given encoding. JSON encoded data can be of any version,
+    /// protobuf encoded data is always `ics20-1` and is validated once decoded.
+    ///
+    /// # Errors
+    /// Returns an error if the data cannot be decoded, or protobuf decoded data is invalid.
+    pub fn decode(data: &[u8], encoding: PacketEncoding) -> Result<Self, TransferError> {
+        match encoding {
+            PacketEncoding::Json => cosmwasm_std::from_json(data)
+                .map_err(|e| TransferError::InvalidPacketData(e.to_string())),
+            PacketEncoding::Protobuf => {
+                let packet = Ics20Packet::decode_proto(data)?;
+                packet.validate()?;
+                Ok(Self::V1(packet))
+            }
+        }
+    }
+}
+
+impl Ics20Packet {
+    /// Encodes the packet data in the given encoding.
+    ///
+    /// # Errors
+    /// Returns an error if the packet data cannot be serialized.
+    pub fn encode(&self, encoding: PacketEncoding) -> Result<Binary, TransferError> {
+        match encoding {
+            PacketEncoding::Json => cosmwasm_std::to_json_binary(self)
+                .map_err(|e| TransferError::InvalidPacketData(e.to_string())),
+            PacketEncoding::Protobuf => Ok(Anybuf::new()
+                .append_string(packet_fields::DENOM, &self.denom)
+                .append_string(packet_fields::AMOUNT, self.amount.to_string())
+                .append_string(packet_fields::SENDER, &self.sender)
+                .append_string(packet_fields::RECEIVER, &self.receiver)
+                .append_string(
+                    packet_fields::MEMO,
+                    self.memo.as_deref().unwrap_or_default(),
+                )
+                .into_vec()
+                .into()),
+        }
+    }
+
+    /// Decodes protobuf encoded `FungibleTokenPacketData`. An empty memo is no memo.
+    fn decode_proto(data: &[u8]) -> Result<Self, TransferError> {
+        let msg = Bufany::deserialize(data)
+            .map_err(|e| TransferError::InvalidPacketData(e.to_string()))?;
+        let amount = msg.string(packet_fields::AMOUNT).unwrap_or_default();
+        let amount = Uint128::from_str(&amount)
+            .map_err(|e| TransferError::InvalidPacketData(e.to_string()))?;
+        let memo = msg
+            .string(packet_fields::MEMO)
+            .filter(|memo| !memo.is_empty());
+
+        Ok(Self {
+            amount,
+            denom: msg.string(packet_fields::DENOM).unwrap_or_default(),
+            receiver: msg.string(packet_fields::RECEIVER).unwrap_or_default(),
+            sender: msg.string(packet_fields::SENDER).unwrap_or_default(),
+            memo,
+        })
+    }
+}
+
+impl Ics20PacketV2 {
+    /// Encodes the packet data in the given encoding.
+    ///
+    /// # Errors
+    /// Returns an error if the encoding is not JSON, or the packet data cannot be serialized.
+    pub fn encode(&self, encoding: PacketEncoding) -> Result<Binary, TransferError> {
+        match encoding {
+            PacketEncoding::Json => cosmwasm_std::to_json_binary(self)
+                .map_err(|e| TransferError::InvalidPacketData(e.to_string())),
+            PacketEncoding::Protobuf => Err(TransferError::UnsupportedEncoding(
+                encoding.content_type().to_string(),
+            )),
+        }
+    }
+}
+
+impl Ics20BatchPacket {
+    /// Encodes the packet data in the given encoding.
+    ///
+    /// # Errors
+    /// Returns an error if the encoding is not JSON, or the packet data cannot be serialized.
+    pub fn encode(&self, encoding: PacketEncoding) -> Result<Binary, TransferError> {
+        match encoding {
+            PacketEncoding::Json => cosmwasm_std::to_json_binary(self)
+                .map_err(|e| TransferError::InvalidPacketData(e.to_string())),
+            PacketEncoding::Protobuf => Err(TransferError::UnsupportedEncoding(
+                encoding.content_type().to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::packet::BatchRecipient;
+    use super::*;
+
+    fn packet() -> Ics20Packet {
+        Ics20Packet::try_new(
+            100u128.into(),
+            "uatom".to_string(),
+            "bob".to_string(),
+            "alice".to_string(),
+            Some("hello".to_string()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn packet_encodings() {
+        let packet = packet();
+
+        let json = packet.encode(PacketEncoding::Json).unwrap();
+        assert_eq!(
+            Ics20PacketData::decode(&json, PacketEncoding::Json).unwrap(),
+            Ics20PacketData::V1(packet.clone())
+        );
+        Ics20PacketData::decode(&json, PacketEncoding::Protobuf).unwrap_err();
+
+        let proto = packet.encode(PacketEncoding::Protobuf).unwrap();
+        assert_eq!(
+            Ics20PacketData::decode(&proto, PacketEncoding::Protobuf).unwrap(),
+            Ics20PacketData::V1(packet)
+        );
+        Ics20PacketData::decode(&proto, PacketEncoding::Json).unwrap_err();
+
+        let v2: Ics20PacketV2 = packet().into();
+        assert!(v2.encode(PacketEncoding::Protobuf).is_err());
+
+        let batch = Ics20BatchPacket {
+            denom: "uatom".to_string(),
+            recipients: vec![BatchRecipient {
+                receiver: "bob".to_string(),
+                amount: 100u128.into(),
+            }],
+            sender: "alice".to_string(),
+            memo: None,
+        };
+        let json = batch.encode(PacketEncoding::Json).unwrap();
+        assert_eq!(
+            Ics20PacketData::decode(&json, PacketEncoding::Json).unwrap(),
+            Ics20PacketData::Batch(batch.clone())
+        );
+        assert!(batch.encode(PacketEncoding::Protobuf).is_err());
+    }
+
+    #[test]
+    fn decode_protobuf_packet() {
+        // `FungibleTokenPacketData` as encoded by `ibc-go`, without a memo.
+        let proto = Anybuf::new()
+            .append_string(1, "transfer/08-wasm-0/uatom")
+            .append_string(2, "100")
+            .append_string(3, "alice")
+            .append_string(4, "bob")
+            .into_vec();
+        let Ics20PacketData::V1(packet) =
+            Ics20PacketData::decode(&proto, PacketEncoding::Protobuf).unwrap()
+        else {
+            panic!("expected ics20-1 packet data");
+        };
+        assert_eq!(packet.denom, "transfer/08-wasm-0/uatom");
+        assert_eq!(packet.amount.u128(), 100);
+        assert_eq!(packet.memo, None);
+    }
+
+    #[test]
+    fn decode_invalid_protobuf_packet() {
+        // the receiver is missing
+        let proto = Anybuf::new()
+            .append_string(1, "uatom")
+            .append_string(2, "100")
+            .append_string(3, "alice")
+            .into_vec();
+        assert!(matches!(
+            Ics20PacketData::decode(&proto, PacketEncoding::Protobuf),
+            Err(TransferError::EmptyReceiver)
+        ));
+
+        // the amount is zero
+        let proto = Anybuf::new()
+            .append_string(1, "uatom")
+            .append_string(2, "0")
+            .append_string(3, "alice")
+            .append_string(4, "bob")
+            .into_vec();
+        assert!(matches!(
+            Ics20PacketData::decode(&proto, PacketEncoding::Protobuf),
+            Err(TransferError::ZeroAmount)
+        ));
+    }
+}
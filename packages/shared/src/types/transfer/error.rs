@@ -7,10 +7,26 @@
 pub enum TransferError {
     #[error("unexpected native token")]
     UnexpectedNativeToken,
+    #[error("no native coins sent")]
+    InvalidNativeFunds,
+    #[error("native denom {0} cannot be an address")]
+    InvalidNativeDenom(String),
     #[error("zero amount")]
     ZeroAmount,
     #[error("empty denom")]
     EmptyDenom,
+    #[error("no tokens to transfer")]
+    EmptyTokens,
+    #[error("duplicate denom {0}")]
+    DuplicateDenom(String),
+    #[error("invalid forwarding: {0}")]
+    InvalidForwarding(String),
+    #[error("invalid callback: {0}")]
+    InvalidCallback(String),
+    #[error("invalid denom trace: {0}")]
+    InvalidDenomTrace(String),
+    #[error("invalid denom hash: {0}")]
+    InvalidDenomHash(String),
     #[error("amount overflow")]
     AmountOverflow,
     #[error("receiver cannot be empty")]
@@ -27,10 +43,42 @@ pub enum TransferError {
     NoForeignTokens,
     #[error("insufficient funds in escrow")]
     InsufficientFundsInEscrow { escrowed: String, requested: String },
+    #[error("escrow invariant broken for {denom}: escrowed {escrowed}, balance {balance}")]
+    EscrowInvariantBroken {
+        denom: String,
+        escrowed: String,
+        balance: String,
+    },
+    #[error("insufficient stranded funds")]
+    InsufficientStrandedFunds { stranded: String, requested: String },
+    #[error("invalid batch transfer: {0}")]
+    InvalidBatch(String),
     #[error("reentrancy safeguard")]
     Reentrancy,
+    #[error("token {0} is not allowed")]
+    TokenNotAllowed(String),
+    #[error("channel {0} is not allowed")]
+    ChannelNotAllowed(String),
+    #[error("sending is disabled on channel {0}")]
+    SendDisabled(String),
+    #[error("receiving is disabled on channel {0}")]
+    ReceiveDisabled(String),
+    #[error("invalid quota: {0}")]
+    InvalidQuota(String),
+    #[error("duplicate quota {0}")]
+    DuplicateQuota(String),
+    #[error("rate limit exceeded: quota {quota} allows a net flow of {capacity} until {reset}")]
+    RateLimitExceeded {
+        quota: String,
+        capacity: String,
+        reset: u64,
+    },
     #[error("unknown acknowledgement: {0}")]
     UnknownAcknowledgement(String),
+    #[error("invalid packet data: {0}")]
+    InvalidPacketData(String),
+    #[error("unsupported encoding {0} for ics20-2 packet data")]
+    UnsupportedEncoding(String),
 }
 
 impl TransferError {
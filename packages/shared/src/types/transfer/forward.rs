@@ -0,0 +1,191 @@
+//! This module defines the forwarding instructions of ICS-20 packets, either given as `ics20-2`
+//! forwarding hops or in the packet memo as in the packet forward middleware of `ibc-apps`.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    error::TransferError,
+    packet::{Forwarding, Ics20PacketV2},
+};
+
+/// The metadata in the memo of an ICS-20 packet. Unknown fields are ignored, so that the memo
+/// can carry metadata for other middlewares as well.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+pub struct PacketMetadata {
+    /// The forwarding instructions of the packet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward: Option<ForwardMetadata>,
+}
+
+/// The forwarding instructions in the memo of an ICS-20 packet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ForwardMetadata {
+    /// The receiver on the next chain.
+    pub receiver: String,
+    /// The port to forward the tokens on.
+    pub port: String,
+    /// The channel to forward the tokens on.
+    pub channel: String,
+    /// How long the forwarded packet lives in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// The memo of the forwarded packet, which may forward the tokens further.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<PacketMetadata>>,
+}
+
+/// Used to find out whether a memo has forwarding instructions, before parsing them.
+#[derive(Deserialize)]
+struct RawPacketMetadata {
+    #[serde(default)]
+    forward: Option<serde::de::IgnoredAny>,
+}
+
+/// The next hop of a forwarded ICS-20 packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NextHop {
+    /// The port to forward the tokens on.
+    pub port_id: String,
+    /// The channel to forward the tokens on.
+    pub channel_id: String,
+    /// The receiver on the next chain.
+    pub receiver: String,
+    /// How long the forwarded packet lives in seconds, if given.
+    pub timeout: Option<u64>,
+    /// The memo of the forwarded packet.
+    pub memo: Option<String>,
+    /// The remaining `ics20-2` forwarding hops of the forwarded packet.
+    pub forwarding: Option<Forwarding>,
+}
+
+impl Ics20PacketV2 {
+    /// Returns the next hop of the packet if it is to be forwarded. The `ics20-2` forwarding
+    /// hops take precedence over the forwarding instructions in the memo. A memo that is not
+    /// JSON or has no forwarding instructions is not an error.
+    ///
+    /// # Errors
+    /// Returns an error if the forwarding instructions in the memo are malformed.
+    pub fn next_hop(&self) -> Result<Option<NextHop>, TransferError> {
+        if let Some((hop, hops)) = self.forwarding.hops.split_first() {
+            let (memo, forwarding) = if hops.is_empty() {
+                (self.forwarding.destination_memo.clone(), None)
+            } else {
+                let forwarding = Forwarding {
+                    hops: hops.to_vec(),
+                    destination_memo: self.forwarding.destination_memo.clone(),
+                };
+                (None, Some(forwarding))
+            };
+
+            return Ok(Some(NextHop {
+                port_id: hop.port_id.clone(),
+                channel_id: hop.channel_id.clone(),
+                receiver: self.receiver.clone(),
+                timeout: None,
+                memo,
+                forwarding,
+            }));
+        }
+
+        let Some(memo) = self.memo.as_deref() else {
+            return Ok(None);
+        };
+        // NOTE: Memos that are not JSON objects, or have no forwarding instructions, are not
+        // meant for forwarding.
+        match cosmwasm_std::from_json::<RawPacketMetadata>(memo) {
+            Ok(RawPacketMetadata { forward: Some(_) }) => {}
+            _ => return Ok(None),
+        }
+
+        let forward = cosmwasm_std::from_json::<PacketMetadata>(memo)
+            .map_err(|e| TransferError::InvalidForwarding(e.to_string()))?
+            .forward
+            .ok_or_else(|| TransferError::InvalidForwarding("missing forward".to_string()))?;
+        let memo = forward
+            .next
+            .map(|next| cosmwasm_std::to_json_string(&next))
+            .transpose()
+            .map_err(|e| TransferError::InvalidForwarding(e.to_string()))?;
+
+        Ok(Some(NextHop {
+            port_id: forward.port,
+            channel_id: forward.channel,
+            receiver: forward.receiver,
+            timeout: forward.timeout,
+            memo,
+            forwarding: None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::transfer::packet::{Hop, Token};
+
+    fn packet(memo: Option<&str>) -> Ics20PacketV2 {
+        Ics20PacketV2 {
+            tokens: vec![Token {
+                denom: "uatom".to_string(),
+                amount: 100u128.into(),
+            }],
+            receiver: "bob".to_string(),
+            sender: "alice".to_string(),
+            memo: memo.map(ToString::to_string),
+            forwarding: Forwarding::default(),
+        }
+    }
+
+    #[test]
+    fn next_hop_from_memo() {
+        assert_eq!(packet(None).next_hop().unwrap(), None);
+        assert_eq!(packet(Some("hello")).next_hop().unwrap(), None);
+        assert_eq!(packet(Some(r#"{"wasm":{}}"#)).next_hop().unwrap(), None);
+
+        let memo = r#"{"forward":{"receiver":"carol","port":"transfer","channel":"08-wasm-1","next":{"forward":{"receiver":"dave","port":"transfer","channel":"08-wasm-2"}}}}"#;
+        let next_hop = packet(Some(memo)).next_hop().unwrap().unwrap();
+        assert_eq!(next_hop.receiver, "carol");
+        assert_eq!(next_hop.channel_id, "08-wasm-1");
+        assert_eq!(
+            next_hop.memo.as_deref(),
+            Some(r#"{"forward":{"receiver":"dave","port":"transfer","channel":"08-wasm-2"}}"#)
+        );
+
+        let memo = r#"{"forward":{"receiver":"carol"}}"#;
+        assert!(matches!(
+            packet(Some(memo)).next_hop(),
+            Err(TransferError::InvalidForwarding(_))
+        ));
+    }
+
+    #[test]
+    fn next_hop_from_forwarding() {
+        let hop = |channel_id: &str| Hop {
+            port_id: "transfer".to_string(),
+            channel_id: channel_id.to_string(),
+        };
+        let mut packet = packet(None);
+        packet.forwarding = Forwarding {
+            hops: vec![hop("08-wasm-1"), hop("08-wasm-2")],
+            destination_memo: Some("memo".to_string()),
+        };
+
+        let next_hop = packet.next_hop().unwrap().unwrap();
+        assert_eq!(next_hop.channel_id, "08-wasm-1");
+        assert_eq!(next_hop.receiver, "bob");
+        assert_eq!(next_hop.memo, None);
+        assert_eq!(
+            next_hop.forwarding,
+            Some(Forwarding {
+                hops: vec![hop("08-wasm-2")],
+                destination_memo: Some("memo".to_string()),
+            })
+        );
+
+        packet.forwarding.hops.pop();
+        let next_hop = packet.next_hop().unwrap().unwrap();
+        assert_eq!(next_hop.memo.as_deref(), Some("memo"));
+        assert_eq!(next_hop.forwarding, None);
+    }
+}
@@ -1,4 +1,9 @@
 //! Contains types for ICS20-transfer messages.
 
+pub mod callbacks;
+pub mod denom;
+pub mod encoding;
 pub mod error;
+pub mod forward;
 pub mod packet;
+pub mod rate_limit;
@@ -25,6 +25,97 @@ pub struct Ics20Packet {
     pub memo: Option<String>,
 }
 
+/// The maximum number of forwarding hops of an [`Ics20PacketV2`].
+pub const MAX_FORWARDING_HOPS: usize = 8;
+
+/// The format for sending an `ics20-2` packet, which transfers several tokens at once and may be
+/// forwarded through other chains.
+/// This is compatible with the JSON serialization
+#[cosmwasm_schema::cw_serde]
+pub struct Ics20PacketV2 {
+    /// the tokens to be transferred, with unique denoms
+    pub tokens: Vec<Token>,
+    /// the recipient address on the destination chain
+    pub receiver: String,
+    /// the sender address
+    pub sender: String,
+    /// optional memo for the IBC transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// the hops the tokens are forwarded through after the destination chain
+    #[serde(default)]
+    pub forwarding: Forwarding,
+}
+
+/// A token transferred in an [`Ics20PacketV2`].
+#[cosmwasm_schema::cw_serde]
+pub struct Token {
+    /// the token denomination to be transferred
+    pub denom: String,
+    /// amount of tokens to transfer
+    pub amount: Uint128,
+}
+
+/// The forwarding instructions of an [`Ics20PacketV2`].
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct Forwarding {
+    /// the `(port_id, channel_id)` hops to forward the tokens through, in order
+    pub hops: Vec<Hop>,
+    /// the memo for the final destination of the tokens. The packet memo must be empty if
+    /// there are any hops.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_memo: Option<String>,
+}
+
+/// A forwarding hop of an [`Ics20PacketV2`].
+#[cosmwasm_schema::cw_serde]
+pub struct Hop {
+    /// the port to forward the tokens on
+    pub port_id: String,
+    /// the channel to forward the tokens on
+    pub channel_id: String,
+}
+
+/// The format for sending an `ics20-batch-1` packet, which transfers a single token to many
+/// receivers at once. This is an extension of `cw-ibc-lite`, only sent to counterparty apps known
+/// to accept it. The memo is not interpreted, so the tokens are neither forwarded nor sent with
+/// destination callbacks.
+/// This is compatible with the JSON serialization
+#[cosmwasm_schema::cw_serde]
+pub struct Ics20BatchPacket {
+    /// the token denomination to be transferred
+    pub denom: String,
+    /// the recipients on the destination chain and the amounts they are sent
+    pub recipients: Vec<BatchRecipient>,
+    /// the sender address
+    pub sender: String,
+    /// optional memo for the IBC transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// A recipient of an [`Ics20BatchPacket`].
+#[cosmwasm_schema::cw_serde]
+pub struct BatchRecipient {
+    /// The remote address to send to.
+    pub receiver: String,
+    /// The amount to send.
+    pub amount: Uint128,
+}
+
+/// The packet data of any ICS-20 version. The versions are told apart by their fields.
+#[cosmwasm_schema::cw_serde]
+#[serde(untagged)]
+pub enum Ics20PacketData {
+    /// `ics20-1` packet data
+    V1(Ics20Packet),
+    /// `ics20-2` packet data
+    V2(Ics20PacketV2),
+    /// `ics20-batch-1` packet data
+    Batch(Ics20BatchPacket),
+}
+
 /// This is a generic ICS acknowledgement format.
 /// This is compatible with the JSON serialization
 pub type Ics20Ack = AcknowledgementData;
@@ -105,6 +196,191 @@ impl Ics20Packet {
     }
 }
 
+impl Ics20PacketV2 {
+    /// Validates the ICS20 v2 packet.
+    ///
+    /// # Errors
+    /// Returns an error if there are no tokens, any amount is zero, any denom is empty or
+    /// repeated, the receiver or sender is empty, or the forwarding instructions are invalid.
+    pub fn validate(&self) -> Result<(), TransferError> {
+        if self.tokens.is_empty() {
+            return Err(TransferError::EmptyTokens);
+        }
+        for (i, token) in self.tokens.iter().enumerate() {
+            if token.amount.is_zero() {
+                return Err(TransferError::ZeroAmount);
+            }
+            if token.denom.is_empty() {
+                return Err(TransferError::EmptyDenom);
+            }
+            if self.tokens[..i].iter().any(|t| t.denom == token.denom) {
+                return Err(TransferError::DuplicateDenom(token.denom.clone()));
+            }
+        }
+        if self.receiver.is_empty() {
+            return Err(TransferError::EmptyReceiver);
+        }
+        if self.sender.is_empty() {
+            return Err(TransferError::EmptySender);
+        }
+
+        self.forwarding.validate()?;
+        if !self.forwarding.hops.is_empty() && self.memo.as_deref().is_some_and(|m| !m.is_empty()) {
+            return Err(TransferError::InvalidForwarding(
+                "memo must be empty when forwarding, use the destination memo".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Ics20BatchPacket {
+    /// Validates the ICS20 batch packet.
+    ///
+    /// # Errors
+    /// Returns an error if there are no recipients, any amount is zero, any receiver is empty,
+    /// the total amount overflows, or the denom or sender is empty.
+    pub fn validate(&self) -> Result<(), TransferError> {
+        if self.recipients.is_empty() {
+            return Err(TransferError::InvalidBatch("no recipients".to_string()));
+        }
+        if self
+            .recipients
+            .iter()
+            .any(|recipient| recipient.amount.is_zero())
+        {
+            return Err(TransferError::ZeroAmount);
+        }
+        if self
+            .recipients
+            .iter()
+            .any(|recipient| recipient.receiver.is_empty())
+        {
+            return Err(TransferError::EmptyReceiver);
+        }
+        self.total()?;
+        if self.denom.is_empty() {
+            return Err(TransferError::EmptyDenom);
+        }
+        if self.sender.is_empty() {
+            return Err(TransferError::EmptySender);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the total amount sent to the recipients.
+    ///
+    /// # Errors
+    /// Returns an error if the total amount overflows.
+    pub fn total(&self) -> Result<Uint128, TransferError> {
+        self.recipients
+            .iter()
+            .try_fold(Uint128::zero(), |total, recipient| {
+                total.checked_add(recipient.amount)
+            })
+            .map_err(|_| TransferError::AmountOverflow)
+    }
+}
+
+impl Forwarding {
+    /// Validates the forwarding instructions.
+    ///
+    /// # Errors
+    /// Returns an error if there are more than [`MAX_FORWARDING_HOPS`] hops, any hop has an empty
+    /// identifier, or a destination memo is given without any hops.
+    pub fn validate(&self) -> Result<(), TransferError> {
+        if self.hops.len() > MAX_FORWARDING_HOPS {
+            return Err(TransferError::InvalidForwarding(format!(
+                "at most {MAX_FORWARDING_HOPS} hops are allowed"
+            )));
+        }
+        if self
+            .hops
+            .iter()
+            .any(|hop| hop.port_id.is_empty() || hop.channel_id.is_empty())
+        {
+            return Err(TransferError::InvalidForwarding(
+                "empty hop identifier".to_string(),
+            ));
+        }
+        if self.hops.is_empty() && self.destination_memo.is_some() {
+            return Err(TransferError::InvalidForwarding(
+                "destination memo requires forwarding hops".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Ics20Packet> for Ics20PacketV2 {
+    fn from(packet: Ics20Packet) -> Self {
+        Self {
+            tokens: vec![Token {
+                denom: packet.denom,
+                amount: packet.amount,
+            }],
+            receiver: packet.receiver,
+            sender: packet.sender,
+            memo: packet.memo,
+            forwarding: Forwarding::default(),
+        }
+    }
+}
+
+impl Ics20PacketData {
+    /// Validates the packet data and converts it to an [`Ics20PacketV2`], so that both versions
+    /// with a single receiver can be handled alike.
+    ///
+    /// # Errors
+    /// Returns an error if the packet data is invalid, or has many receivers.
+    pub fn into_v2(self) -> Result<Ics20PacketV2, TransferError> {
+        let packet = match self {
+            Self::V1(packet) => {
+                packet.validate()?;
+                packet.into()
+            }
+            Self::V2(packet) => packet,
+            Self::Batch(_) => {
+                return Err(TransferError::InvalidPacketData(
+                    "ics20-batch-1 packet data has many receivers".to_string(),
+                ));
+            }
+        };
+        packet.validate()?;
+        Ok(packet)
+    }
+
+    /// Validates the packet data and splits it into one [`Ics20PacketV2`] per receiver, so that
+    /// the transfers of all versions can be handled alike.
+    ///
+    /// # Errors
+    /// Returns an error if the packet data is invalid.
+    pub fn into_transfers(self) -> Result<Vec<Ics20PacketV2>, TransferError> {
+        let Self::Batch(packet) = self else {
+            return Ok(vec![self.into_v2()?]);
+        };
+        packet.validate()?;
+
+        Ok(packet
+            .recipients
+            .into_iter()
+            .map(|recipient| Ics20PacketV2 {
+                tokens: vec![Token {
+                    denom: packet.denom.clone(),
+                    amount: recipient.amount,
+                }],
+                receiver: recipient.receiver,
+                sender: packet.sender.clone(),
+                memo: None,
+                forwarding: Forwarding::default(),
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,4 +393,127 @@ mod tests {
         let serialized = ack.to_vec();
         assert_eq!(serialized, br#"{"result":"AQ=="}"#);
     }
+
+    #[test]
+    fn packet_data_versions() {
+        let v1 = br#"{"amount":"100","denom":"uatom","receiver":"bob","sender":"alice"}"#;
+        let packet = cosmwasm_std::from_json::<Ics20PacketData>(v1)
+            .unwrap()
+            .into_v2()
+            .unwrap();
+        assert_eq!(
+            packet.tokens,
+            vec![Token {
+                denom: "uatom".to_string(),
+                amount: 100u128.into(),
+            }]
+        );
+        assert!(packet.forwarding.hops.is_empty());
+
+        let v2 = br#"{"tokens":[{"denom":"uatom","amount":"100"},{"denom":"uosmo","amount":"5"}],"receiver":"bob","sender":"alice","forwarding":{"hops":[{"port_id":"transfer","channel_id":"08-wasm-0"}]}}"#;
+        let data = cosmwasm_std::from_json::<Ics20PacketData>(v2).unwrap();
+        assert!(matches!(data, Ics20PacketData::V2(_)));
+        let packet = data.into_v2().unwrap();
+        assert_eq!(packet.tokens.len(), 2);
+        assert_eq!(packet.forwarding.hops.len(), 1);
+    }
+
+    #[test]
+    fn batch_packet_data() {
+        let batch = br#"{"denom":"uatom","recipients":[{"receiver":"bob","amount":"100"},{"receiver":"carol","amount":"5"}],"sender":"alice","memo":"airdrop"}"#;
+        let data = cosmwasm_std::from_json::<Ics20PacketData>(batch).unwrap();
+        assert!(matches!(data, Ics20PacketData::Batch(_)));
+        assert!(data.clone().into_v2().is_err());
+
+        let transfers = data.into_transfers().unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[1].receiver, "carol");
+        assert_eq!(transfers[1].sender, "alice");
+        assert_eq!(
+            transfers[1].tokens,
+            vec![Token {
+                denom: "uatom".to_string(),
+                amount: 5u128.into(),
+            }]
+        );
+        // the memo is not interpreted on the receivers' transfers
+        assert_eq!(transfers[1].memo, None);
+
+        // single receiver packet data is not split
+        let v1 = br#"{"amount":"100","denom":"uatom","receiver":"bob","sender":"alice"}"#;
+        let data = cosmwasm_std::from_json::<Ics20PacketData>(v1).unwrap();
+        assert_eq!(data.into_transfers().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn validate_batch() {
+        let recipient = |receiver: &str, amount: u128| BatchRecipient {
+            receiver: receiver.to_string(),
+            amount: amount.into(),
+        };
+        let mut packet = Ics20BatchPacket {
+            denom: "uatom".to_string(),
+            recipients: vec![recipient("bob", 100), recipient("carol", 5)],
+            sender: "alice".to_string(),
+            memo: None,
+        };
+        packet.validate().unwrap();
+        assert_eq!(packet.total().unwrap().u128(), 105);
+
+        packet.recipients.push(recipient("dave", 0));
+        assert!(matches!(packet.validate(), Err(TransferError::ZeroAmount)));
+        packet.recipients.pop();
+
+        packet.recipients.push(recipient("", 1));
+        assert!(matches!(
+            packet.validate(),
+            Err(TransferError::EmptyReceiver)
+        ));
+        packet.recipients.pop();
+
+        packet.recipients.push(recipient("dave", u128::MAX));
+        assert!(matches!(
+            packet.validate(),
+            Err(TransferError::AmountOverflow)
+        ));
+        packet.recipients.clear();
+
+        assert!(matches!(
+            packet.validate(),
+            Err(TransferError::InvalidBatch(_))
+        ));
+    }
+
+    #[test]
+    fn validate_v2() {
+        let token = Token {
+            denom: "uatom".to_string(),
+            amount: 100u128.into(),
+        };
+        let mut packet = Ics20PacketV2 {
+            tokens: vec![token.clone()],
+            receiver: "bob".to_string(),
+            sender: "alice".to_string(),
+            memo: None,
+            forwarding: Forwarding::default(),
+        };
+        packet.validate().unwrap();
+
+        packet.tokens.push(token);
+        assert!(matches!(
+            packet.validate(),
+            Err(TransferError::DuplicateDenom(_))
+        ));
+        packet.tokens.pop();
+
+        packet.forwarding.hops.push(Hop {
+            port_id: "transfer".to_string(),
+            channel_id: "08-wasm-0".to_string(),
+        });
+        packet.memo = Some("memo".to_string());
+        assert!(matches!(
+            packet.validate(),
+            Err(TransferError::InvalidForwarding(_))
+        ));
+    }
 }
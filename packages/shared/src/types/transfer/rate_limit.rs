@@ -0,0 +1,304 @@
+//! This module defines the rate limits of ICS-20 transfers. A rate limit bounds the net flow of
+//! a denom over a channel to a percentage of its channel value within a time window.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Timestamp, Uint128};
+
+use super::error::TransferError;
+
+/// The direction of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowDirection {
+    /// Tokens sent from this chain.
+    Send,
+    /// Tokens received by this chain.
+    Recv,
+}
+
+/// The value that the percentages of a [`Quota`] apply to.
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum QuotaBase {
+    /// The total supply of the denom on this chain.
+    #[default]
+    Supply,
+    /// The balance of the denom escrowed for the channel. Vouchers are only escrowed when they
+    /// are sent on a channel other than the one they were received on.
+    Escrow,
+}
+
+/// A quota on the net flow of a denom over a channel.
+#[cw_serde]
+pub struct Quota {
+    /// The name of the quota, unique for the channel and denom.
+    pub name: String,
+    /// The maximum net outflow within a period, as a percentage of the channel value.
+    pub max_send_percent: u32,
+    /// The maximum net inflow within a period, as a percentage of the channel value.
+    pub max_recv_percent: u32,
+    /// The length of a period in seconds.
+    pub duration: u64,
+    /// The value the percentages apply to.
+    #[serde(default)]
+    pub base: QuotaBase,
+    /// The maximum net flow in either direction within a period whose channel value is zero,
+    /// e.g. the first receipt of a voucher. Nothing may flow then by default.
+    #[serde(default)]
+    pub zero_value_capacity: Uint128,
+}
+
+impl Quota {
+    /// Validates the quota.
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty, the duration is zero or a percentage is above 100.
+    pub fn validate(&self) -> Result<(), TransferError> {
+        if self.name.is_empty() {
+            return Err(TransferError::InvalidQuota("empty name".to_string()));
+        }
+        if self.duration == 0 {
+            return Err(TransferError::InvalidQuota(format!(
+                "zero duration in quota {}",
+                self.name
+            )));
+        }
+        if self.max_send_percent > 100 || self.max_recv_percent > 100 {
+            return Err(TransferError::InvalidQuota(format!(
+                "percentage above 100 in quota {}",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the maximum net flow in the given direction for the channel value, which is
+    /// [`Quota::zero_value_capacity`] if the channel value is zero.
+    #[must_use]
+    pub fn capacity(&self, direction: FlowDirection, channel_value: Uint128) -> Uint128 {
+        if channel_value.is_zero() {
+            return self.zero_value_capacity;
+        }
+        let percent = match direction {
+            FlowDirection::Send => self.max_send_percent,
+            FlowDirection::Recv => self.max_recv_percent,
+        };
+        channel_value.multiply_ratio(percent, 100u32)
+    }
+}
+
+/// The flow of a denom over a channel within the current period of a [`Quota`].
+#[cw_serde]
+pub struct Flow {
+    /// The amount received within the period.
+    pub inflow: Uint128,
+    /// The amount sent within the period.
+    pub outflow: Uint128,
+    /// The end of the period.
+    pub period_end: Timestamp,
+    /// The channel value at the start of the period.
+    pub channel_value: Uint128,
+}
+
+impl Flow {
+    /// Returns the net flow in the given direction, which is zero if the flow is in the other
+    /// direction.
+    #[must_use]
+    pub fn net(&self, direction: FlowDirection) -> Uint128 {
+        match direction {
+            FlowDirection::Send => self.outflow.saturating_sub(self.inflow),
+            FlowDirection::Recv => self.inflow.saturating_sub(self.outflow),
+        }
+    }
+}
+
+/// A quota and the flow within its current period.
+#[cw_serde]
+pub struct RateLimit {
+    /// The quota.
+    pub quota: Quota,
+    /// The flow within the current period.
+    pub flow: Flow,
+}
+
+impl RateLimit {
+    /// Creates a rate limit whose first period starts now.
+    #[must_use]
+    pub fn new(quota: Quota, now: Timestamp, channel_value: Uint128) -> Self {
+        let period_end = now.plus_seconds(quota.duration);
+        Self {
+            quota,
+            flow: Flow {
+                inflow: Uint128::zero(),
+                outflow: Uint128::zero(),
+                period_end,
+                channel_value,
+            },
+        }
+    }
+
+    /// Returns whether the current period has ended.
+    #[must_use]
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now >= self.flow.period_end
+    }
+
+    /// Starts a new period now, with the given channel value.
+    pub fn reset(&mut self, now: Timestamp, channel_value: Uint128) {
+        *self = Self::new(self.quota.clone(), now, channel_value);
+    }
+
+    /// Adds the amount to the flow in the given direction.
+    ///
+    /// # Errors
+    /// Returns an error if the net flow in that direction would exceed the quota.
+    pub fn allow(
+        &mut self,
+        direction: FlowDirection,
+        amount: Uint128,
+    ) -> Result<(), TransferError> {
+        let mut flow = self.flow.clone();
+        match direction {
+            FlowDirection::Send => {
+                flow.outflow = flow
+                    .outflow
+                    .checked_add(amount)
+                    .map_err(|_| TransferError::AmountOverflow)?
+            }
+            FlowDirection::Recv => {
+                flow.inflow = flow
+                    .inflow
+                    .checked_add(amount)
+                    .map_err(|_| TransferError::AmountOverflow)?
+            }
+        }
+
+        let capacity = self.quota.capacity(direction, flow.channel_value);
+        if flow.net(direction) > capacity {
+            return Err(TransferError::RateLimitExceeded {
+                quota: self.quota.name.clone(),
+                capacity: capacity.to_string(),
+                reset: flow.period_end.seconds(),
+            });
+        }
+
+        self.flow = flow;
+        Ok(())
+    }
+
+    /// Removes the amount from the flow in the given direction, used when a transfer is
+    /// reverted.
+    pub fn undo(&mut self, direction: FlowDirection, amount: Uint128) {
+        match direction {
+            FlowDirection::Send => self.flow.outflow = self.flow.outflow.saturating_sub(amount),
+            FlowDirection::Recv => self.flow.inflow = self.flow.inflow.saturating_sub(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(max_send_percent: u32, max_recv_percent: u32) -> Quota {
+        Quota {
+            name: "daily".to_string(),
+            max_send_percent,
+            max_recv_percent,
+            duration: 86_400,
+            base: QuotaBase::Supply,
+            zero_value_capacity: Uint128::zero(),
+        }
+    }
+
+    #[test]
+    fn validate_quota() {
+        quota(10, 10).validate().unwrap();
+        assert!(quota(101, 10).validate().is_err());
+        assert!(Quota {
+            duration: 0,
+            ..quota(10, 10)
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn allow_net_flow() {
+        let now = Timestamp::from_seconds(1_000);
+        let mut rate_limit = RateLimit::new(quota(10, 5), now, 1_000u128.into());
+
+        rate_limit
+            .allow(FlowDirection::Send, 100u128.into())
+            .unwrap();
+        assert!(rate_limit.allow(FlowDirection::Send, 1u128.into()).is_err());
+        assert_eq!(rate_limit.flow.outflow.u128(), 100);
+
+        // Inflows make room for outflows and vice versa.
+        rate_limit
+            .allow(FlowDirection::Recv, 50u128.into())
+            .unwrap();
+        rate_limit
+            .allow(FlowDirection::Send, 50u128.into())
+            .unwrap();
+        rate_limit
+            .allow(FlowDirection::Recv, 150u128.into())
+            .unwrap();
+        assert!(rate_limit.allow(FlowDirection::Recv, 1u128.into()).is_err());
+
+        rate_limit.undo(FlowDirection::Recv, 50u128.into());
+        assert_eq!(rate_limit.flow.net(FlowDirection::Recv).u128(), 0);
+    }
+
+    #[test]
+    fn zero_channel_value_is_capped() {
+        let now = Timestamp::from_seconds(1_000);
+
+        // Nothing may flow by default.
+        let mut rate_limit = RateLimit::new(quota(10, 10), now, Uint128::zero());
+        assert!(rate_limit.allow(FlowDirection::Send, 1u128.into()).is_err());
+        assert!(rate_limit.allow(FlowDirection::Recv, 1u128.into()).is_err());
+        assert_eq!(rate_limit.flow.net(FlowDirection::Recv).u128(), 0);
+
+        let quota = Quota {
+            zero_value_capacity: 100u128.into(),
+            ..quota(10, 10)
+        };
+        let mut rate_limit = RateLimit::new(quota, now, Uint128::zero());
+        assert!(rate_limit
+            .allow(FlowDirection::Recv, 101u128.into())
+            .is_err());
+        rate_limit
+            .allow(FlowDirection::Recv, 100u128.into())
+            .unwrap();
+        assert!(rate_limit.allow(FlowDirection::Recv, 1u128.into()).is_err());
+        rate_limit
+            .allow(FlowDirection::Send, 150u128.into())
+            .unwrap();
+        assert_eq!(rate_limit.flow.net(FlowDirection::Send).u128(), 50);
+
+        // The percentages apply once there is a channel value.
+        rate_limit.flow.channel_value = 100u128.into();
+        assert!(rate_limit.allow(FlowDirection::Send, 1u128.into()).is_err());
+    }
+
+    #[test]
+    fn reset_period() {
+        let now = Timestamp::from_seconds(1_000);
+        let mut rate_limit = RateLimit::new(quota(10, 10), now, 1_000u128.into());
+        rate_limit
+            .allow(FlowDirection::Send, 100u128.into())
+            .unwrap();
+
+        assert!(!rate_limit.is_expired(now.plus_seconds(86_399)));
+        let later = now.plus_seconds(86_400);
+        assert!(rate_limit.is_expired(later));
+
+        rate_limit.reset(later, 2_000u128.into());
+        assert_eq!(rate_limit.flow.outflow.u128(), 0);
+        assert_eq!(rate_limit.flow.period_end, later.plus_seconds(86_400));
+        rate_limit
+            .allow(FlowDirection::Send, 200u128.into())
+            .unwrap();
+    }
+}
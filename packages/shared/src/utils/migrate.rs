@@ -0,0 +1,68 @@
+//! This module contains the [`cw2`] version checks shared by the `migrate` entry points of the
+//! `cw-ibc-lite` contracts.
+
+use cosmwasm_std::{StdResult, Storage};
+
+/// Ensures that the contract recorded in [`cw2`] is `name` at a version not newer than
+/// `version`, and records `version`. Returns the previously recorded version, from which the
+/// state migrations are run.
+///
+/// # Errors
+/// Returns an error if no version is recorded, if the recorded contract is not `name`, or if the
+/// recorded version is newer than `version`.
+pub fn ensure_from_older_version(
+    storage: &mut dyn Storage,
+    name: &str,
+    version: &str,
+) -> StdResult<semver::Version> {
+    cw2::ensure_from_older_version(storage, name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::ensure_from_older_version;
+
+    const NAME: &str = "crates.io:cw-ibc-lite-test";
+
+    fn migrate(recorded: Option<(&str, &str)>, version: &str) -> Option<semver::Version> {
+        let mut storage = MockStorage::new();
+        if let Some((name, recorded)) = recorded {
+            cw2::set_contract_version(&mut storage, name, recorded).unwrap();
+        }
+
+        let previous = ensure_from_older_version(&mut storage, NAME, version).ok()?;
+        let migrated = cw2::get_contract_version(&storage).unwrap();
+        assert_eq!(migrated.contract, NAME);
+        assert_eq!(migrated.version, version);
+        Some(previous)
+    }
+
+    #[test]
+    fn older_and_same_versions_are_migrated() {
+        assert_eq!(
+            migrate(Some((NAME, "0.0.1")), "0.1.0"),
+            Some(semver::Version::new(0, 0, 1))
+        );
+        assert_eq!(
+            migrate(Some((NAME, "0.1.0")), "0.1.0"),
+            Some(semver::Version::new(0, 1, 0))
+        );
+    }
+
+    #[test]
+    fn downgrades_are_refused() {
+        assert_eq!(migrate(Some((NAME, "0.2.0")), "0.1.0"), None);
+        assert_eq!(migrate(Some((NAME, "0.1.1")), "0.1.0"), None);
+    }
+
+    #[test]
+    fn other_contracts_are_refused() {
+        assert_eq!(
+            migrate(Some(("crates.io:cw20-base", "0.0.1")), "0.1.0"),
+            None
+        );
+        assert_eq!(migrate(None, "0.1.0"), None);
+    }
+}
@@ -1,5 +1,6 @@
 //! This module contains shared utilities for `cw-ibc-lite`.
 
 pub mod apps;
+pub mod migrate;
 pub mod timeout;
 pub mod transfer;
@@ -2,22 +2,35 @@
 
 use std::str::FromStr;
 
-use cosmwasm_std::{Env, IbcTimeout};
+use cosmwasm_std::{Env, IbcTimeout, IbcTimeoutBlock, StdResult};
 use ibc_core_host::types::identifiers::ChainId;
 
-use crate::types::error::ContractError;
+use crate::types::{error::ContractError, ibc::Height};
 
-/// Validates an [`IbcTimeout`].
+/// Returns the timeout height of an [`IbcTimeout`], if set.
+#[must_use]
+pub fn block(timeout: &IbcTimeout) -> Option<IbcTimeoutBlock> {
+    // NOTE: A zero height is a special case where the timeout height is not set.
+    timeout.block().filter(|b| b.height != 0 || b.revision != 0)
+}
+
+/// Returns whether the timeout height has been reached at the given height.
+#[must_use]
+pub fn is_height_reached(block: &IbcTimeoutBlock, height: &Height) -> bool {
+    (height.revision_number, height.revision_height) >= (block.revision, block.height)
+}
+
+/// Validates the [`IbcTimeout`] of a packet received by this chain.
 ///
 /// # Errors
-/// Returns an error if the timeout is not a timestamp or if the timestamp is in the past.
+/// Returns an error if neither a timeout height nor a timestamp is set, or if either has
+/// passed.
 pub fn validate(env: &Env, timeout: &IbcTimeout) -> Result<(), ContractError> {
-    timeout.block().map_or(Ok(()), |b| {
-        if b.height == 0 && b.revision == 0 {
-            // This is a special case where the timeout is not set.
-            return Ok(());
-        }
+    if block(timeout).is_none() && timeout.timestamp().is_none() {
+        return Err(ContractError::EmptyTimeout);
+    }
 
+    block(timeout).map_or(Ok(()), |b| {
         if env.block.height >= b.height {
             return Err(ContractError::invalid_timeout_block(
                 env.block.height,
@@ -34,17 +47,105 @@ pub fn validate(env: &Env, timeout: &IbcTimeout) -> Result<(), ContractError> {
 
         Ok(())
     })?;
-    timeout
-        .timestamp()
-        .ok_or(ContractError::EmptyTimestamp)
-        .and_then(|ts| {
-            if env.block.time >= ts {
-                return Err(ContractError::invalid_timeout_timestamp(
-                    env.block.time.seconds(),
-                    ts.seconds(),
+    validate_timestamp(env, timeout)
+}
+
+/// Validates the [`IbcTimeout`] of a packet sent by this chain. The timeout height is a height
+/// of the counterparty, so it is compared with the latest height of the counterparty client,
+/// which is only queried if the timeout height is set.
+///
+/// # Errors
+/// Returns an error if neither a timeout height nor a timestamp is set, if either has passed,
+/// or if the latest height cannot be queried.
+pub fn validate_send(
+    env: &Env,
+    timeout: &IbcTimeout,
+    latest_height: impl FnOnce() -> StdResult<Height>,
+) -> Result<(), ContractError> {
+    match block(timeout) {
+        Some(b) => {
+            let latest_height = latest_height()?;
+            if is_height_reached(&b, &latest_height) {
+                return Err(ContractError::invalid_timeout_block(
+                    latest_height.revision_height,
+                    b.height,
                 ));
             }
+        }
+        None if timeout.timestamp().is_none() => return Err(ContractError::EmptyTimeout),
+        None => {}
+    }
+
+    validate_timestamp(env, timeout)
+}
+
+/// Validates the timeout timestamp of an [`IbcTimeout`], if set.
+fn validate_timestamp(env: &Env, timeout: &IbcTimeout) -> Result<(), ContractError> {
+    timeout.timestamp().map_or(Ok(()), |ts| {
+        if env.block.time >= ts {
+            return Err(ContractError::invalid_timeout_timestamp(
+                env.block.time.seconds(),
+                ts.seconds(),
+            ));
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_env, Timestamp};
+
+    use super::*;
+
+    #[test]
+    fn validate_send_timeout() {
+        let env = mock_env();
+        let latest_height = || {
+            Ok(Height {
+                revision_number: 1,
+                revision_height: 100,
+            })
+        };
+        let later = env.block.time.plus_seconds(60);
+
+        validate_send(&env, &IbcTimeout::with_timestamp(later), latest_height).unwrap();
+        validate_send(
+            &env,
+            &IbcTimeout::with_block(IbcTimeoutBlock {
+                revision: 1,
+                height: 101,
+            }),
+            latest_height,
+        )
+        .unwrap();
 
-            Ok(())
-        })
+        assert!(validate_send(
+            &env,
+            &IbcTimeout::with_block(IbcTimeoutBlock {
+                revision: 1,
+                height: 100,
+            }),
+            latest_height,
+        )
+        .is_err());
+        assert!(validate_send(
+            &env,
+            &IbcTimeout::with_timestamp(Timestamp::from_seconds(0)),
+            latest_height,
+        )
+        .is_err());
+        assert!(matches!(
+            validate_send(
+                &env,
+                &IbcTimeout::with_block(IbcTimeoutBlock {
+                    revision: 0,
+                    height: 0,
+                }),
+                latest_height,
+            ),
+            Err(ContractError::EmptyTimeout)
+        ));
+    }
 }
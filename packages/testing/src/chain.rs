@@ -0,0 +1,529 @@
+//! This module contains [`IbcLiteChain`], a `cw-multi-test` app with IBC Lite deployed.
+
+use anyhow::{anyhow, Result as AnyResult};
+use cosmwasm_std::{Addr, Binary, Coin, Event};
+use cw_multi_test::{App, AppResponse, BankSudo, Executor, SudoMsg};
+
+use cw_ibc_lite_ics02_client::types::{
+    events as ics02_events, msg as ics02_msg, state::CounterpartyInfo,
+};
+use cw_ibc_lite_ics20_transfer::types::{keys as ics20_keys, msg as ics20_msg};
+use cw_ibc_lite_ics26_router::types::{msg as ics26_msg, state as ics26_state};
+use cw_ibc_lite_mock_client::types::{msg::ClientMessage, state as mock_state};
+use cw_ibc_lite_shared::types::{
+    clients::msg as client_msg,
+    ibc::{Height, Packet},
+    paths::ics24_host::PacketCommitmentPath,
+    storage::PureItem,
+};
+
+use crate::contracts;
+
+/// A `cw-multi-test` app with `cw-ibc-lite-ics26-router` and `cw-ibc-lite-ics02-client`
+/// deployed.
+pub struct IbcLiteChain {
+    /// The underlying `cw-multi-test` app.
+    pub app: App,
+    /// The owner of the router, which can register apps with custom port ids.
+    pub owner: Addr,
+    /// The address of the `cw-ibc-lite-ics26-router` contract.
+    pub router: Addr,
+    /// The address of the `cw-ibc-lite-ics02-client` contract.
+    pub ics02_client: Addr,
+    /// The code id of the `cw-ibc-lite-mock-client` contract.
+    pub mock_client_code_id: u64,
+    /// The code id of the `cw-ibc-lite-ics09-localhost` contract.
+    pub localhost_client_code_id: u64,
+    /// The code id of the `cw-ibc-lite-ics20-transfer` contract.
+    pub ics20_code_id: u64,
+    /// The code id of the `cw20-base` contract, used for cw20 tokens and ICS-20 vouchers.
+    pub cw20_code_id: u64,
+}
+
+impl IbcLiteChain {
+    /// Creates a new chain with the given chain id and deploys IBC Lite on it.
+    ///
+    /// # Errors
+    /// Returns an error if the deployment fails.
+    pub fn new(chain_id: &str) -> AnyResult<Self> {
+        let mut app = App::default();
+        app.update_block(|block| block.chain_id = chain_id.to_string());
+
+        let owner = app.api().addr_make("owner");
+        let ics02_client_code_id = app.store_code(contracts::ics02_client());
+        let router_code_id = app.store_code(contracts::ics26_router());
+        let mock_client_code_id = app.store_code(contracts::mock_client());
+        let localhost_client_code_id = app.store_code(contracts::ics09_localhost());
+        let ics20_code_id = app.store_code(contracts::ics20_transfer());
+        let cw20_code_id = app.store_code(contracts::cw20_base());
+
+        let router = app.instantiate_contract(
+            router_code_id,
+            owner.clone(),
+            &ics26_msg::InstantiateMsg {
+                ics02_client_code_id,
+                owner: owner.to_string(),
+                ics02_creation_policy: None,
+            },
+            &[],
+            "cw-ibc-lite-ics26-router",
+            Some(owner.to_string()),
+        )?;
+        let ics02_client = ics26_state::ICS02_CLIENT_ADDRESS.query(&app.wrap(), router.clone())?;
+
+        Ok(Self {
+            app,
+            owner,
+            router,
+            ics02_client,
+            mock_client_code_id,
+            localhost_client_code_id,
+            ics20_code_id,
+            cw20_code_id,
+        })
+    }
+
+    /// Returns the current height of the chain. The revision number is always `0`.
+    #[must_use]
+    pub fn height(&self) -> Height {
+        Height {
+            revision_number: 0,
+            revision_height: self.app.block_info().height,
+        }
+    }
+
+    /// Advances the chain by the given number of blocks, with 5 seconds per block.
+    pub fn next_blocks(&mut self, blocks: u64) {
+        self.app.update_block(|block| {
+            block.height += blocks;
+            block.time = block.time.plus_seconds(5 * blocks);
+        });
+    }
+
+    /// Returns the message instantiating a mock light client of this chain at its current
+    /// height.
+    ///
+    /// # Errors
+    /// Returns an error if the client or consensus state cannot be serialized.
+    pub fn mock_client_instantiate_msg(&self) -> AnyResult<client_msg::InstantiateMsg> {
+        Ok(client_msg::InstantiateMsg {
+            client_state: cosmwasm_std::to_json_binary(&mock_state::ClientState {
+                latest_height: self.height(),
+                behavior: mock_state::Behavior::default(),
+                status: mock_state::Status::default(),
+            })?,
+            consensus_state: cosmwasm_std::to_json_binary(&mock_state::ConsensusState {
+                timestamp: self.app.block_info().time.nanos(),
+            })?,
+            checksum: None,
+        })
+    }
+
+    /// Creates a mock light client of the counterparty chain, and returns its client id.
+    ///
+    /// # Errors
+    /// Returns an error if the client creation fails.
+    pub fn create_mock_client(
+        &mut self,
+        counterparty: &Self,
+        counterparty_info: Option<CounterpartyInfo>,
+    ) -> AnyResult<String> {
+        let instantiate_msg = counterparty.mock_client_instantiate_msg()?;
+        let response = self.app.execute_contract(
+            self.owner.clone(),
+            self.ics02_client.clone(),
+            &ics02_msg::ExecuteMsg::CreateClient {
+                code_id: self.mock_client_code_id,
+                instantiate_msg,
+                counterparty_info,
+            },
+            &[],
+        )?;
+
+        event_attribute(
+            &response.events,
+            ics02_events::EVENT_TYPE_CREATE_CLIENT,
+            ics02_events::ATTRIBUTE_KEY_CLIENT_ID,
+        )
+        .ok_or_else(|| anyhow!("client id not found in the create client events"))
+    }
+
+    /// Creates a localhost light client, which verifies proofs against the router of this
+    /// chain, and returns its client id.
+    ///
+    /// # Errors
+    /// Returns an error if the client creation fails.
+    pub fn create_localhost_client(
+        &mut self,
+        counterparty_info: Option<CounterpartyInfo>,
+    ) -> AnyResult<String> {
+        let response = self.app.execute_contract(
+            self.owner.clone(),
+            self.ics02_client.clone(),
+            &ics02_msg::ExecuteMsg::CreateClient {
+                code_id: self.localhost_client_code_id,
+                instantiate_msg: client_msg::InstantiateMsg {
+                    client_state: Binary::default(),
+                    consensus_state: Binary::default(),
+                    checksum: None,
+                },
+                counterparty_info,
+            },
+            &[],
+        )?;
+
+        event_attribute(
+            &response.events,
+            ics02_events::EVENT_TYPE_CREATE_CLIENT,
+            ics02_events::ATTRIBUTE_KEY_CLIENT_ID,
+        )
+        .ok_or_else(|| anyhow!("client id not found in the create client events"))
+    }
+
+    /// Provides the counterparty of a client created by [`Self::create_mock_client`] or
+    /// [`Self::create_localhost_client`].
+    ///
+    /// # Errors
+    /// Returns an error if the counterparty cannot be provided.
+    pub fn provide_counterparty(
+        &mut self,
+        client_id: &str,
+        counterparty_client_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.ics02_client.clone(),
+            &ics02_msg::ExecuteMsg::ProvideCounterparty {
+                client_id: client_id.to_string(),
+                counterparty_info: CounterpartyInfo {
+                    client_id: counterparty_client_id.to_string(),
+                    merkle_path_prefix: None,
+                },
+            },
+            &[],
+        )
+    }
+
+    /// Executes a [`ClientMessage`] on a mock light client.
+    ///
+    /// # Errors
+    /// Returns an error if the mock client rejects the message.
+    pub fn execute_mock_client(
+        &mut self,
+        client_id: &str,
+        client_message: &ClientMessage,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.ics02_client.clone(),
+            &ics02_msg::ExecuteMsg::ExecuteClient {
+                client_id: client_id.to_string(),
+                message: client_msg::ExecuteMsg::UpdateState(
+                    ibc_client_cw::types::UpdateStateMsgRaw {
+                        client_message: cosmwasm_std::to_json_binary(client_message)?,
+                    },
+                ),
+            },
+            &[],
+        )
+    }
+
+    /// Updates a mock light client with the current height and timestamp of the counterparty.
+    /// Returns the height the client was updated to.
+    ///
+    /// # Errors
+    /// Returns an error if the mock client rejects the update.
+    pub fn update_mock_client(
+        &mut self,
+        client_id: &str,
+        counterparty: &Self,
+    ) -> AnyResult<Height> {
+        let height = counterparty.height();
+        self.execute_mock_client(
+            client_id,
+            &ClientMessage::Update {
+                height: height.clone(),
+                timestamp: counterparty.app.block_info().time.nanos(),
+            },
+        )?;
+
+        Ok(height)
+    }
+
+    /// Registers an IBC app with the router, and returns its port id.
+    ///
+    /// # Errors
+    /// Returns an error if the app cannot be registered.
+    pub fn register_app(&mut self, address: &Addr) -> AnyResult<String> {
+        let response = self.app.execute_contract(
+            self.owner.clone(),
+            self.router.clone(),
+            &ics26_msg::ExecuteMsg::RegisterIbcApp {
+                port_id: None,
+                address: address.to_string(),
+            },
+            &[],
+        )?;
+
+        event_attribute(
+            &response.events,
+            cw_ibc_lite_ics26_router::types::events::EVENT_TYPE_REGISTER_IBC_APP,
+            cw_ibc_lite_ics26_router::types::events::ATTRIBUTE_KEY_PORT_ID,
+        )
+        .ok_or_else(|| anyhow!("port id not found in the register ibc app events"))
+    }
+
+    /// Returns whether the router holds the commitment of the given packet, that is, whether
+    /// the packet was sent from this chain and is not acknowledged or timed out yet.
+    ///
+    /// # Errors
+    /// Returns an error if the commitment cannot be queried.
+    pub fn has_packet_commitment(&self, packet: &Packet) -> AnyResult<bool> {
+        let item: PureItem = PacketCommitmentPath {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            sequence: packet.sequence,
+        }
+        .into();
+        let commitment = self
+            .app
+            .wrap()
+            .query_wasm_raw(&self.router, item.as_slice())?;
+
+        Ok(commitment.is_some_and(|commitment| commitment == packet.to_commitment_vec()))
+    }
+
+    /// Deploys `cw-ibc-lite-ics20-transfer` with `cw20-base` as its voucher code, and returns
+    /// its address. The owner of the chain is its admin. It is registered with the router under
+    /// its contract port id, which it sends packets from, and under the `transfer` port, which
+    /// counterparties send packets to.
+    ///
+    /// # Errors
+    /// Returns an error if the contract cannot be instantiated or registered.
+    pub fn deploy_ics20(&mut self) -> AnyResult<Addr> {
+        let ics20 = self.app.instantiate_contract(
+            self.ics20_code_id,
+            self.owner.clone(),
+            &ics20_msg::InstantiateMsg {
+                ics26_router_address: self.router.to_string(),
+                voucher_code_id: Some(self.cw20_code_id),
+            },
+            &[],
+            "cw-ibc-lite-ics20-transfer",
+            Some(self.owner.to_string()),
+        )?;
+
+        self.register_app(&ics20)?;
+        self.app.execute_contract(
+            self.owner.clone(),
+            self.router.clone(),
+            &ics26_msg::ExecuteMsg::RegisterIbcApp {
+                port_id: Some(ics20_keys::DEFAULT_PORT_ID.to_string()),
+                address: ics20.to_string(),
+            },
+            &[],
+        )?;
+
+        Ok(ics20)
+    }
+
+    /// Instantiates a `cw20-base` token with the given initial balances, and returns its
+    /// address.
+    ///
+    /// # Errors
+    /// Returns an error if the token cannot be instantiated.
+    pub fn create_cw20(
+        &mut self,
+        symbol: &str,
+        initial_balances: &[(Addr, u128)],
+    ) -> AnyResult<Addr> {
+        self.app.instantiate_contract(
+            self.cw20_code_id,
+            self.owner.clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: format!("{symbol} token"),
+                symbol: symbol.to_string(),
+                decimals: 6,
+                initial_balances: initial_balances
+                    .iter()
+                    .map(|(address, amount)| cw20::Cw20Coin {
+                        address: address.to_string(),
+                        amount: (*amount).into(),
+                    })
+                    .collect(),
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            symbol,
+            None,
+        )
+    }
+
+    /// Mints native tokens to the given address.
+    ///
+    /// # Errors
+    /// Returns an error if the tokens cannot be minted.
+    pub fn fund(&mut self, address: &Addr, amount: Vec<Coin>) -> AnyResult<AppResponse> {
+        self.app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: address.to_string(),
+            amount,
+        }))
+    }
+
+    /// Returns the cw20 balance of the given address.
+    ///
+    /// # Errors
+    /// Returns an error if the balance cannot be queried.
+    pub fn cw20_balance(&self, token: &Addr, address: &Addr) -> AnyResult<u128> {
+        let response: cw20::BalanceResponse = self.app.wrap().query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )?;
+        Ok(response.balance.u128())
+    }
+
+    /// Returns the native balance of the given address.
+    ///
+    /// # Errors
+    /// Returns an error if the balance cannot be queried.
+    pub fn balance(&self, address: &Addr, denom: &str) -> AnyResult<u128> {
+        Ok(self.app.wrap().query_balance(address, denom)?.amount.u128())
+    }
+}
+
+/// Returns the value of the first attribute with the given key in the first contract event of
+/// the given type. `cw-multi-test` prefixes the type of contract events with `wasm-`.
+#[must_use]
+pub fn event_attribute(events: &[Event], ty: &str, key: &str) -> Option<String> {
+    let ty = format!("wasm-{ty}");
+    events
+        .iter()
+        .filter(|event| event.ty == ty)
+        .flat_map(|event| event.attributes.iter())
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Uint128};
+    use cw_multi_test::Executor;
+
+    use cw_ibc_lite_ics20_transfer::types::msg::{
+        responses::VoucherInfo, ExecuteMsg, QueryMsg, TransferMsg,
+    };
+    use cw_ibc_lite_shared::{types::transfer::packet::Ics20Ack, utils};
+
+    use crate::relayer::Path;
+
+    #[test]
+    fn ics20_round_trip() {
+        let mut path = Path::new().unwrap();
+        let ics20_a = path.chain_a.deploy_ics20().unwrap();
+        let ics20_b = path.chain_b.deploy_ics20().unwrap();
+        let alice = path.chain_a.app.api().addr_make("alice");
+        let bob = path.chain_b.app.api().addr_make("bob");
+        path.chain_a.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+        // alice sends uatom to bob, which receives vouchers
+        let response = path
+            .chain_a
+            .app
+            .execute_contract(
+                alice.clone(),
+                ics20_a.clone(),
+                &ExecuteMsg::Transfer(TransferMsg {
+                    source_channel: path.client_a.clone(),
+                    receiver: bob.to_string(),
+                    timeout: None,
+                    memo: None,
+                    forwarding: None,
+                    callback: None,
+                }),
+                &[coin(100, "uatom")],
+            )
+            .unwrap();
+        let acks = path.relay_packets(&response).unwrap();
+        assert_eq!(acks.len(), 1);
+        assert_eq!(
+            cosmwasm_std::from_json::<Ics20Ack>(&acks[0]).unwrap(),
+            Ics20Ack::success()
+        );
+        assert_eq!(path.chain_a.balance(&alice, "uatom").unwrap(), 0);
+        assert_eq!(path.chain_a.balance(&ics20_a, "uatom").unwrap(), 100);
+        let escrowed: Uint128 = path
+            .chain_a
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &ics20_a,
+                &QueryMsg::EscrowAmount {
+                    channel: path.client_a.clone(),
+                    cw20_address: "uatom".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(escrowed.u128(), 100);
+
+        let port_b = utils::apps::contract_port_id(&ics20_b).unwrap();
+        let voucher: VoucherInfo = path
+            .chain_b
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &ics20_b,
+                &QueryMsg::Voucher {
+                    denom_trace: format!("{}/{}/uatom", port_b.as_str(), path.client_b),
+                },
+            )
+            .unwrap();
+        let voucher = path
+            .chain_b
+            .app
+            .api()
+            .addr_validate(&voucher.address)
+            .unwrap();
+        assert_eq!(path.chain_b.cw20_balance(&voucher, &bob).unwrap(), 100);
+
+        // bob sends the vouchers back to alice, which are burned
+        let response = path
+            .chain_b
+            .app
+            .execute_contract(
+                bob.clone(),
+                voucher.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: ics20_b.to_string(),
+                    amount: 100u128.into(),
+                    msg: cosmwasm_std::to_json_binary(&TransferMsg {
+                        source_channel: path.client_b.clone(),
+                        receiver: alice.to_string(),
+                        timeout: None,
+                        memo: None,
+                        forwarding: None,
+                        callback: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+        path.relay_packets(&response).unwrap();
+
+        assert_eq!(path.chain_b.cw20_balance(&voucher, &bob).unwrap(), 0);
+        assert_eq!(path.chain_b.cw20_balance(&voucher, &ics20_b).unwrap(), 0);
+        let token_info: cw20::TokenInfoResponse = path
+            .chain_b
+            .app
+            .wrap()
+            .query_wasm_smart(&voucher, &cw20::Cw20QueryMsg::TokenInfo {})
+            .unwrap();
+        assert!(token_info.total_supply.is_zero());
+
+        assert_eq!(path.chain_a.balance(&alice, "uatom").unwrap(), 100);
+        assert_eq!(path.chain_a.balance(&ics20_a, "uatom").unwrap(), 0);
+    }
+}
@@ -0,0 +1,74 @@
+//! This module contains the `cw-multi-test` wrappers of the IBC Lite contracts.
+
+use cosmwasm_std::Empty;
+use cw_multi_test::{Contract, ContractWrapper};
+
+/// Returns the `cw-ibc-lite-ics26-router` contract.
+#[must_use]
+pub fn ics26_router() -> Box<dyn Contract<Empty>> {
+    use cw_ibc_lite_ics26_router::contract;
+
+    Box::new(
+        ContractWrapper::new(contract::execute, contract::instantiate, contract::query)
+            .with_reply(contract::reply),
+    )
+}
+
+/// Returns the `cw-ibc-lite-ics02-client` contract.
+#[must_use]
+pub fn ics02_client() -> Box<dyn Contract<Empty>> {
+    use cw_ibc_lite_ics02_client::contract;
+
+    Box::new(ContractWrapper::new(
+        contract::execute,
+        contract::instantiate,
+        contract::query,
+    ))
+}
+
+/// Returns the `cw-ibc-lite-mock-client` contract.
+#[must_use]
+pub fn mock_client() -> Box<dyn Contract<Empty>> {
+    use cw_ibc_lite_mock_client::contract;
+
+    Box::new(ContractWrapper::new(
+        contract::execute,
+        contract::instantiate,
+        contract::query,
+    ))
+}
+
+/// Returns the `cw-ibc-lite-ics09-localhost` contract.
+#[must_use]
+pub fn ics09_localhost() -> Box<dyn Contract<Empty>> {
+    use cw_ibc_lite_ics09_localhost::contract;
+
+    Box::new(ContractWrapper::new(
+        contract::execute,
+        contract::instantiate,
+        contract::query,
+    ))
+}
+
+/// Returns the `cw-ibc-lite-ics20-transfer` contract.
+#[must_use]
+pub fn ics20_transfer() -> Box<dyn Contract<Empty>> {
+    use cw_ibc_lite_ics20_transfer::contract;
+
+    Box::new(
+        ContractWrapper::new(contract::execute, contract::instantiate, contract::query)
+            .with_reply(contract::reply),
+    )
+}
+
+/// Returns the `cw20-base` contract, used for cw20 tokens and ICS-20 vouchers.
+#[must_use]
+pub fn cw20_base() -> Box<dyn Contract<Empty>> {
+    use cw20_base::contract;
+
+    Box::new(ContractWrapper::new(
+        contract::execute,
+        contract::instantiate,
+        contract::query,
+    ))
+}
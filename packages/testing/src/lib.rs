@@ -0,0 +1,7 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+#![deny(clippy::nursery, clippy::pedantic, warnings)]
+
+pub mod chain;
+pub mod contracts;
+pub mod relayer;
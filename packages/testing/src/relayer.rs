@@ -0,0 +1,537 @@
+//! This module contains [`Path`], which connects two [`IbcLiteChain`]s and relays packets
+//! between them.
+
+use anyhow::{anyhow, Result as AnyResult};
+use cosmwasm_std::{Binary, Event, HexBinary, IbcTimeout, IbcTimeoutBlock, Timestamp};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw_ibc_lite_ics26_router::types::{
+    events,
+    msg::{
+        execute::{AcknowledgementMsg, RecvPacketMsg, TimeoutMsg},
+        ExecuteMsg,
+    },
+};
+use cw_ibc_lite_shared::types::ibc::Packet;
+
+use crate::chain::{event_attribute, IbcLiteChain};
+
+/// `MOCK_PROOF` is the proof submitted by the relayer. The mock light clients accept any proof.
+pub const MOCK_PROOF: &[u8] = b"mock_proof";
+
+/// Two [`IbcLiteChain`]s connected by a pair of mock light clients.
+pub struct Path {
+    /// The first chain.
+    pub chain_a: IbcLiteChain,
+    /// The second chain.
+    pub chain_b: IbcLiteChain,
+    /// The client id of the mock client of `chain_b` on `chain_a`.
+    pub client_a: String,
+    /// The client id of the mock client of `chain_a` on `chain_b`.
+    pub client_b: String,
+}
+
+impl Path {
+    /// Creates two chains, `chain-a` and `chain-b`, and connects them.
+    ///
+    /// # Errors
+    /// Returns an error if the deployment or the client creation fails.
+    pub fn new() -> AnyResult<Self> {
+        Self::with_chains(IbcLiteChain::new("chain-a")?, IbcLiteChain::new("chain-b")?)
+    }
+
+    /// Connects the given chains, see [`connect`].
+    ///
+    /// # Errors
+    /// Returns an error if the client creation fails.
+    pub fn with_chains(mut chain_a: IbcLiteChain, mut chain_b: IbcLiteChain) -> AnyResult<Self> {
+        let (client_a, client_b) = connect(&mut chain_a, &mut chain_b)?;
+
+        Ok(Self {
+            chain_a,
+            chain_b,
+            client_a,
+            client_b,
+        })
+    }
+
+    /// Returns the source and destination chains of a packet, and the client id on each. The
+    /// source chain is the one holding the commitment of the packet, since both chains may use
+    /// the same client ids.
+    fn endpoints(
+        &mut self,
+        packet: &Packet,
+    ) -> AnyResult<(&mut IbcLiteChain, String, &mut IbcLiteChain, String)> {
+        let source_channel = packet.source_channel.as_str();
+        if source_channel == self.client_a && self.chain_a.has_packet_commitment(packet)? {
+            Ok((
+                &mut self.chain_a,
+                self.client_a.clone(),
+                &mut self.chain_b,
+                self.client_b.clone(),
+            ))
+        } else if source_channel == self.client_b && self.chain_b.has_packet_commitment(packet)? {
+            Ok((
+                &mut self.chain_b,
+                self.client_b.clone(),
+                &mut self.chain_a,
+                self.client_a.clone(),
+            ))
+        } else {
+            Err(anyhow!(
+                "packet {} on source channel {source_channel} is not committed on this path",
+                packet.sequence.value()
+            ))
+        }
+    }
+
+    /// Receives a packet on the destination chain and returns the written acknowledgement.
+    /// The acknowledgement is not relayed back to the source chain.
+    ///
+    /// # Errors
+    /// Returns an error if the packet cannot be received, or no acknowledgement is written.
+    pub fn recv_packet(&mut self, packet: &Packet) -> AnyResult<Binary> {
+        let (src, _, dst, dst_client) = self.endpoints(packet)?;
+        let response = recv_packet(src, dst, &dst_client, packet)?;
+        parse_write_acknowledgement(&response.events)?
+            .ok_or_else(|| anyhow!("acknowledgement not found in the recv packet events"))
+    }
+
+    /// Acknowledges a packet on the source chain.
+    ///
+    /// # Errors
+    /// Returns an error if the acknowledgement fails.
+    pub fn acknowledge_packet(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: Binary,
+    ) -> AnyResult<AppResponse> {
+        let (src, src_client, dst, _) = self.endpoints(packet)?;
+        acknowledge_packet(src, &src_client, dst, packet, acknowledgement)
+    }
+
+    /// Relays a packet to the destination chain and its acknowledgement back to the source
+    /// chain. Returns the acknowledgement.
+    ///
+    /// # Errors
+    /// Returns an error if the packet or the acknowledgement cannot be relayed.
+    pub fn relay_packet(&mut self, packet: &Packet) -> AnyResult<Binary> {
+        let acknowledgement = self.recv_packet(packet)?;
+        self.acknowledge_packet(packet, acknowledgement.clone())?;
+        Ok(acknowledgement)
+    }
+
+    /// Relays all packets sent in the given response, see [`Self::relay_packet`].
+    /// Returns the acknowledgements in the order the packets were sent.
+    ///
+    /// # Errors
+    /// Returns an error if any packet cannot be relayed.
+    pub fn relay_packets(&mut self, response: &AppResponse) -> AnyResult<Vec<Binary>> {
+        parse_send_packets(&response.events)?
+            .iter()
+            .map(|packet| self.relay_packet(packet))
+            .collect()
+    }
+
+    /// Times out a packet on the source chain. The destination chain must be past the timeout
+    /// timestamp of the packet, see [`IbcLiteChain::next_blocks`].
+    ///
+    /// # Errors
+    /// Returns an error if the timeout fails.
+    pub fn timeout_packet(&mut self, packet: &Packet) -> AnyResult<AppResponse> {
+        let (src, src_client, dst, _) = self.endpoints(packet)?;
+        timeout_packet(src, &src_client, dst, packet)
+    }
+}
+
+/// Connects two chains by creating a mock client of each chain on the other, and returns the
+/// client id on each chain. A chain can be connected to any number of chains, and packets
+/// between chains that are not in a single [`Path`] are relayed with the functions of this
+/// module.
+///
+/// # Errors
+/// Returns an error if the client creation fails.
+pub fn connect(
+    chain_a: &mut IbcLiteChain,
+    chain_b: &mut IbcLiteChain,
+) -> AnyResult<(String, String)> {
+    let client_a = chain_a.create_mock_client(chain_b, None)?;
+    let client_b = chain_b.create_mock_client(
+        chain_a,
+        Some(cw_ibc_lite_ics02_client::types::state::CounterpartyInfo {
+            client_id: client_a.clone(),
+            merkle_path_prefix: None,
+        }),
+    )?;
+    chain_a.provide_counterparty(&client_a, &client_b)?;
+
+    Ok((client_a, client_b))
+}
+
+/// Receives a packet sent from `src` on `dst`, whose client of `src` is `dst_client`.
+/// The acknowledgement, if written synchronously, is in the events of the response, see
+/// [`parse_write_acknowledgement`].
+///
+/// # Errors
+/// Returns an error if the packet cannot be received.
+pub fn recv_packet(
+    src: &IbcLiteChain,
+    dst: &mut IbcLiteChain,
+    dst_client: &str,
+    packet: &Packet,
+) -> AnyResult<AppResponse> {
+    let relayer = dst.app.api().addr_make("relayer");
+    let proof_height = dst.update_mock_client(dst_client, src)?;
+    dst.app.execute_contract(
+        relayer,
+        dst.router.clone(),
+        &ExecuteMsg::RecvPacket(RecvPacketMsg {
+            packet: packet.clone(),
+            proof_commitment: MOCK_PROOF.into(),
+            proof_height,
+        }),
+        &[],
+    )
+}
+
+/// Acknowledges a packet on `src`, whose client of the destination chain `dst` is
+/// `src_client`.
+///
+/// # Errors
+/// Returns an error if the acknowledgement fails.
+pub fn acknowledge_packet(
+    src: &mut IbcLiteChain,
+    src_client: &str,
+    dst: &IbcLiteChain,
+    packet: &Packet,
+    acknowledgement: Binary,
+) -> AnyResult<AppResponse> {
+    let relayer = src.app.api().addr_make("relayer");
+    let proof_height = src.update_mock_client(src_client, dst)?;
+    src.app.execute_contract(
+        relayer,
+        src.router.clone(),
+        &ExecuteMsg::Acknowledgement(AcknowledgementMsg {
+            packet: packet.clone(),
+            acknowledgement,
+            proof_acked: MOCK_PROOF.into(),
+            proof_height,
+        }),
+        &[],
+    )
+}
+
+/// Relays a packet from `src` to `dst` and its acknowledgement back, and returns the
+/// acknowledgement. The acknowledgement must be written synchronously.
+///
+/// # Errors
+/// Returns an error if the packet or the acknowledgement cannot be relayed.
+pub fn relay_packet(
+    src: &mut IbcLiteChain,
+    src_client: &str,
+    dst: &mut IbcLiteChain,
+    dst_client: &str,
+    packet: &Packet,
+) -> AnyResult<Binary> {
+    let response = recv_packet(src, dst, dst_client, packet)?;
+    let acknowledgement = parse_write_acknowledgement(&response.events)?
+        .ok_or_else(|| anyhow!("acknowledgement not found in the recv packet events"))?;
+    acknowledge_packet(src, src_client, dst, packet, acknowledgement.clone())?;
+    Ok(acknowledgement)
+}
+
+/// Times out a packet on `src`, whose client of the destination chain `dst` is `src_client`.
+/// `dst` must be past the timeout of the packet.
+///
+/// # Errors
+/// Returns an error if the timeout fails.
+pub fn timeout_packet(
+    src: &mut IbcLiteChain,
+    src_client: &str,
+    dst: &IbcLiteChain,
+    packet: &Packet,
+) -> AnyResult<AppResponse> {
+    let relayer = src.app.api().addr_make("relayer");
+    let proof_height = src.update_mock_client(src_client, dst)?;
+    src.app.execute_contract(
+        relayer,
+        src.router.clone(),
+        &ExecuteMsg::Timeout(TimeoutMsg {
+            packet: packet.clone(),
+            proof_unreceived: MOCK_PROOF.into(),
+            proof_height,
+            next_sequence_recv: 0,
+        }),
+        &[],
+    )
+}
+
+/// Returns the first acknowledgement written in the given events, if any.
+///
+/// # Errors
+/// Returns an error if the acknowledgement is not hex encoded.
+pub fn parse_write_acknowledgement(events: &[Event]) -> AnyResult<Option<Binary>> {
+    event_attribute(
+        events,
+        events::EVENT_TYPE_WRITE_ACKNOWLEDGEMENT,
+        events::ATTRIBUTE_KEY_ACK_DATA_HEX,
+    )
+    .map(|ack_hex| Ok(HexBinary::from_hex(&ack_hex)?.to_vec().into()))
+    .transpose()
+}
+
+/// Returns the packets sent in the given events, in the order they were sent.
+///
+/// # Errors
+/// Returns an error if a send packet event is malformed.
+pub fn parse_send_packets(events: &[Event]) -> AnyResult<Vec<Packet>> {
+    let ty = format!("wasm-{}", events::EVENT_TYPE_SEND_PACKET);
+    events
+        .iter()
+        .filter(|event| event.ty == ty)
+        .map(|event| {
+            let attribute = |key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == key)
+                    .map(|attribute| attribute.value.as_str())
+                    .ok_or_else(|| anyhow!("attribute {key} not found in the send packet event"))
+            };
+
+            let timeout = parse_timeout(
+                attribute(events::ATTRIBUTE_KEY_TIMEOUT_HEIGHT)?,
+                attribute(events::ATTRIBUTE_KEY_TIMEOUT_TIMESTAMP)?,
+            )?;
+            let sequence: u64 = attribute(events::ATTRIBUTE_KEY_SEQUENCE)?.parse()?;
+            Ok(Packet::new(
+                sequence,
+                attribute(events::ATTRIBUTE_KEY_SRC_PORT)?,
+                attribute(events::ATTRIBUTE_KEY_SRC_CHANNEL)?,
+                attribute(events::ATTRIBUTE_KEY_DST_PORT)?,
+                attribute(events::ATTRIBUTE_KEY_DST_CHANNEL)?,
+                HexBinary::from_hex(attribute(events::ATTRIBUTE_KEY_DATA_HEX)?)?
+                    .to_vec()
+                    .into(),
+                timeout,
+            )
+            .map_err(|err| anyhow!("invalid packet identifiers: {err}"))?)
+        })
+        .collect()
+}
+
+/// Parses the timeout of a send packet event, where an unset timeout height is `0-0` and an
+/// unset timeout timestamp is `0`.
+fn parse_timeout(height: &str, timestamp: &str) -> AnyResult<IbcTimeout> {
+    let (revision, height) = height
+        .split_once('-')
+        .ok_or_else(|| anyhow!("invalid timeout height {height}"))?;
+    let block = IbcTimeoutBlock {
+        revision: revision.parse()?,
+        height: height.parse()?,
+    };
+    let block = Some(block).filter(|b| b.height != 0 || b.revision != 0);
+    let timestamp = Some(Timestamp::from_nanos(timestamp.parse()?)).filter(|t| t.nanos() != 0);
+
+    match (block, timestamp) {
+        (Some(block), Some(timestamp)) => Ok(IbcTimeout::with_both(block, timestamp)),
+        (Some(block), None) => Ok(IbcTimeout::with_block(block)),
+        (None, Some(timestamp)) => Ok(IbcTimeout::with_timestamp(timestamp)),
+        (None, None) => Err(anyhow!("the send packet event has no timeout")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        Binary, Deps, DepsMut, Empty, Env, IbcTimeout, IbcTimeoutBlock, MessageInfo, Response,
+        StdResult, Timestamp,
+    };
+    use cw_multi_test::{ContractWrapper, Executor};
+
+    use cw_ibc_lite_ics26_router::types::msg::{execute::SendPacketMsg, ExecuteMsg};
+    use cw_ibc_lite_shared::types::apps::callbacks::IbcAppCallbackMsg;
+
+    use super::{parse_send_packets, Path};
+
+    const ACK_KEY: &[u8] = b"ack";
+    const TIMEOUT_KEY: &[u8] = b"timeout";
+
+    /// An app that acknowledges packets with their data, and records acknowledgements and
+    /// timeouts.
+    #[derive(serde::Deserialize, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum EchoExecuteMsg {
+        ReceiveIbcAppCallback(IbcAppCallbackMsg),
+    }
+
+    #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+    fn echo_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+    fn echo_execute(
+        deps: DepsMut,
+        _: Env,
+        _: MessageInfo,
+        msg: EchoExecuteMsg,
+    ) -> StdResult<Response> {
+        let EchoExecuteMsg::ReceiveIbcAppCallback(callback) = msg;
+        match callback {
+            IbcAppCallbackMsg::OnSendPacket { .. } => {}
+            IbcAppCallbackMsg::OnRecvPacket { packet, .. } => {
+                return Ok(Response::new().set_data(packet.data));
+            }
+            IbcAppCallbackMsg::OnAcknowledgementPacket {
+                acknowledgement, ..
+            } => deps.storage.set(ACK_KEY, &acknowledgement),
+            IbcAppCallbackMsg::OnTimeoutPacket { .. } => deps.storage.set(TIMEOUT_KEY, &[1]),
+        }
+        Ok(Response::new())
+    }
+
+    #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
+    fn echo_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    /// Deploys the echo app on both chains and sends a packet from `chain_a` to `chain_b`.
+    fn send_echo_packet(
+        path: &mut Path,
+        timeout: IbcTimeout,
+    ) -> (cosmwasm_std::Addr, cw_multi_test::AppResponse) {
+        let deploy = |chain: &mut crate::chain::IbcLiteChain| {
+            let code_id = chain.app.store_code(Box::new(ContractWrapper::new(
+                echo_execute,
+                echo_instantiate,
+                echo_query,
+            )));
+            let address = chain
+                .app
+                .instantiate_contract(code_id, chain.owner.clone(), &Empty {}, &[], "echo", None)
+                .unwrap();
+            let port_id = chain.register_app(&address).unwrap();
+            (address, port_id)
+        };
+        let (echo_a, port_a) = deploy(&mut path.chain_a);
+        let (_, port_b) = deploy(&mut path.chain_b);
+
+        let sender = path.chain_a.app.api().addr_make("sender");
+        let response = path
+            .chain_a
+            .app
+            .execute_contract(
+                sender,
+                path.chain_a.router.clone(),
+                &ExecuteMsg::SendPacket(SendPacketMsg {
+                    source_channel: path.client_a.clone(),
+                    source_port: port_a,
+                    dest_channel: Some(path.client_b.clone()),
+                    dest_port: port_b,
+                    data: b"hello".into(),
+                    timeout,
+                    version: "echo-1".to_string(),
+                }),
+                &[],
+            )
+            .unwrap();
+
+        (echo_a, response)
+    }
+
+    fn timestamp_timeout(path: &Path) -> IbcTimeout {
+        IbcTimeout::with_timestamp(path.chain_a.app.block_info().time.plus_seconds(600))
+    }
+
+    #[test]
+    fn relay_packet() {
+        let mut path = Path::new().unwrap();
+        let timeout = timestamp_timeout(&path);
+        let (echo_a, response) = send_echo_packet(&mut path, timeout);
+
+        let acks = path.relay_packets(&response).unwrap();
+        assert_eq!(acks, vec![Binary::from(b"hello")]);
+
+        let recorded = path
+            .chain_a
+            .app
+            .wrap()
+            .query_wasm_raw(echo_a, ACK_KEY)
+            .unwrap();
+        assert_eq!(recorded, Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn timeout_packet() {
+        let mut path = Path::new().unwrap();
+        let timeout = timestamp_timeout(&path);
+        let (echo_a, response) = send_echo_packet(&mut path, timeout);
+        let packet = parse_send_packets(&response.events).unwrap().remove(0);
+
+        // the packet cannot be timed out before the timeout
+        path.timeout_packet(&packet).unwrap_err();
+
+        path.chain_b.next_blocks(200);
+        path.recv_packet(&packet).unwrap_err();
+        path.timeout_packet(&packet).unwrap();
+
+        let recorded = path
+            .chain_a
+            .app
+            .wrap()
+            .query_wasm_raw(echo_a, TIMEOUT_KEY)
+            .unwrap();
+        assert_eq!(recorded, Some(vec![1]));
+    }
+
+    #[test]
+    fn timeout_packet_by_height() {
+        let mut path = Path::new().unwrap();
+        let timeout = IbcTimeout::with_block(IbcTimeoutBlock {
+            revision: 0,
+            height: path.chain_b.height().revision_height + 10,
+        });
+        let (echo_a, response) = send_echo_packet(&mut path, timeout.clone());
+        let packet = parse_send_packets(&response.events).unwrap().remove(0);
+        assert_eq!(packet.timeout, timeout);
+
+        path.timeout_packet(&packet).unwrap_err();
+
+        path.chain_b.next_blocks(10);
+        path.recv_packet(&packet).unwrap_err();
+        path.timeout_packet(&packet).unwrap();
+
+        let recorded = path
+            .chain_a
+            .app
+            .wrap()
+            .query_wasm_raw(echo_a, TIMEOUT_KEY)
+            .unwrap();
+        assert_eq!(recorded, Some(vec![1]));
+    }
+
+    #[test]
+    fn parse_timeout() {
+        let block = IbcTimeoutBlock {
+            revision: 1,
+            height: 42,
+        };
+        let timestamp = Timestamp::from_nanos(7);
+
+        assert_eq!(
+            super::parse_timeout("1-42", "7").unwrap(),
+            IbcTimeout::with_both(block.clone(), timestamp)
+        );
+        assert_eq!(
+            super::parse_timeout("1-42", "0").unwrap(),
+            IbcTimeout::with_block(block)
+        );
+        assert_eq!(
+            super::parse_timeout("0-0", "7").unwrap(),
+            IbcTimeout::with_timestamp(timestamp)
+        );
+        super::parse_timeout("0-0", "0").unwrap_err();
+        super::parse_timeout("42", "7").unwrap_err();
+    }
+}
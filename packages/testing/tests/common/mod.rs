@@ -0,0 +1,220 @@
+//! The setup shared by the `cw-ibc-lite-ics20-transfer` tests: three chains `A - B - C`, with
+//! the transfer app deployed on each, where `B` is connected to both `A` and `C`.
+
+// NOTE: Each test crate only uses part of the setup.
+#![allow(dead_code)]
+
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw_ibc_lite_ics20_transfer::types::msg::{
+    responses::VoucherInfo, ExecuteMsg, QueryMsg, TransferMsg,
+};
+use cw_ibc_lite_shared::{
+    types::{ibc::Packet, transfer::packet::Ics20Ack},
+    utils,
+};
+use cw_ibc_lite_testing::{chain::IbcLiteChain, relayer};
+
+/// A chain with `cw-ibc-lite-ics20-transfer` deployed.
+pub struct Chain {
+    /// The chain.
+    pub chain: IbcLiteChain,
+    /// The address of the transfer app.
+    pub ics20: Addr,
+}
+
+impl Chain {
+    fn new(chain_id: &str) -> Self {
+        let mut chain = IbcLiteChain::new(chain_id).unwrap();
+        let ics20 = chain.deploy_ics20().unwrap();
+        Self { chain, ics20 }
+    }
+
+    /// Returns the address of the given account on this chain.
+    pub fn addr(&self, account: &str) -> Addr {
+        self.chain.app.api().addr_make(account)
+    }
+
+    /// Returns the port id the transfer app sends packets from.
+    pub fn port(&self) -> String {
+        utils::apps::contract_port_id(&self.ics20)
+            .unwrap()
+            .as_str()
+            .to_string()
+    }
+
+    /// Executes the transfer app.
+    pub fn execute(
+        &mut self,
+        sender: &Addr,
+        msg: &ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<AppResponse> {
+        self.chain
+            .app
+            .execute_contract(sender.clone(), self.ics20.clone(), msg, funds)
+    }
+
+    /// Transfers the native tokens sent as funds.
+    pub fn transfer(&mut self, sender: &Addr, msg: TransferMsg, funds: &[Coin]) -> AppResponse {
+        self.execute(sender, &ExecuteMsg::Transfer(msg), funds)
+            .unwrap()
+    }
+
+    /// Transfers cw20 tokens, or vouchers, by sending them to the transfer app.
+    pub fn send_cw20(
+        &mut self,
+        token: &Addr,
+        sender: &Addr,
+        amount: u128,
+        msg: &impl serde::Serialize,
+    ) -> AppResponse {
+        self.chain
+            .app
+            .execute_contract(
+                sender.clone(),
+                token.clone(),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: self.ics20.to_string(),
+                    amount: amount.into(),
+                    msg: cosmwasm_std::to_json_binary(msg).unwrap(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    /// Queries the transfer app.
+    pub fn query<T: serde::de::DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.chain
+            .app
+            .wrap()
+            .query_wasm_smart(&self.ics20, msg)
+            .unwrap()
+    }
+
+    /// Returns the voucher contract of the given denom trace.
+    pub fn voucher(&self, denom_trace: &str) -> Addr {
+        let voucher: VoucherInfo = self.query(&QueryMsg::Voucher {
+            denom_trace: denom_trace.to_string(),
+        });
+        Addr::unchecked(voucher.address)
+    }
+
+    /// Returns the escrowed amount of the denom on the channel.
+    pub fn escrowed(&self, channel: &str, denom: &str) -> u128 {
+        let escrowed: Uint128 = self.query(&QueryMsg::EscrowAmount {
+            channel: channel.to_string(),
+            cw20_address: denom.to_string(),
+        });
+        escrowed.u128()
+    }
+
+    /// Returns the native balance of the address.
+    pub fn balance(&self, address: &Addr, denom: &str) -> u128 {
+        self.chain.balance(address, denom).unwrap()
+    }
+
+    /// Returns the cw20 balance of the address.
+    pub fn cw20_balance(&self, token: &Addr, address: &Addr) -> u128 {
+        self.chain.cw20_balance(token, address).unwrap()
+    }
+
+    /// Returns the total supply of a cw20 token.
+    pub fn cw20_supply(&self, token: &Addr) -> u128 {
+        let token_info: cw20::TokenInfoResponse = self
+            .chain
+            .app
+            .wrap()
+            .query_wasm_smart(token, &cw20::Cw20QueryMsg::TokenInfo {})
+            .unwrap();
+        token_info.total_supply.u128()
+    }
+}
+
+/// Three chains `A - B - C` with `cw-ibc-lite-ics20-transfer` deployed on each.
+pub struct Network {
+    /// Chain `A`, connected to `B`.
+    pub a: Chain,
+    /// Chain `B`, connected to `A` and `C`.
+    pub b: Chain,
+    /// Chain `C`, connected to `B`.
+    pub c: Chain,
+    /// The client of `B` on `A`.
+    pub ab: String,
+    /// The client of `A` on `B`.
+    pub ba: String,
+    /// The client of `C` on `B`.
+    pub bc: String,
+    /// The client of `B` on `C`.
+    pub cb: String,
+}
+
+impl Network {
+    /// Creates and connects the chains.
+    pub fn new() -> Self {
+        let mut a = Chain::new("chain-a");
+        let mut b = Chain::new("chain-b");
+        let mut c = Chain::new("chain-c");
+        let (ab, ba) = relayer::connect(&mut a.chain, &mut b.chain).unwrap();
+        let (bc, cb) = relayer::connect(&mut b.chain, &mut c.chain).unwrap();
+
+        Self {
+            a,
+            b,
+            c,
+            ab,
+            ba,
+            bc,
+            cb,
+        }
+    }
+}
+
+/// Returns a transfer to the receiver on the channel, with the default timeout.
+pub fn transfer_msg(source_channel: &str, receiver: &Addr) -> TransferMsg {
+    TransferMsg {
+        source_channel: source_channel.to_string(),
+        receiver: receiver.to_string(),
+        timeout: None,
+        memo: None,
+        forwarding: None,
+        callback: None,
+    }
+}
+
+/// Returns the packets sent in the response.
+pub fn sent_packets(response: &AppResponse) -> Vec<Packet> {
+    relayer::parse_send_packets(&response.events).unwrap()
+}
+
+/// Relays the packets sent in the response from `src` to `dst`, and returns their
+/// acknowledgements.
+pub fn relay(
+    src: &mut Chain,
+    src_client: &str,
+    dst: &mut Chain,
+    dst_client: &str,
+    response: &AppResponse,
+) -> Vec<Ics20Ack> {
+    sent_packets(response)
+        .iter()
+        .map(|packet| {
+            let ack = relayer::relay_packet(
+                &mut src.chain,
+                src_client,
+                &mut dst.chain,
+                dst_client,
+                packet,
+            )
+            .unwrap();
+            decode_ack(&ack)
+        })
+        .collect()
+}
+
+/// Decodes a JSON encoded ICS-20 acknowledgement.
+pub fn decode_ack(ack: &Binary) -> Ics20Ack {
+    cosmwasm_std::from_json(ack).unwrap()
+}
@@ -0,0 +1,206 @@
+//! Tests of the client creation policies of `cw-ibc-lite-ics02-client`.
+
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw_ibc_lite_ics02_client::types::{
+    events as ics02_events, msg as ics02_msg,
+    state::{self as ics02_state, CreationPolicy},
+};
+use cw_ibc_lite_ics26_router::types::msg as ics26_msg;
+use cw_ibc_lite_testing::chain::{event_attribute, IbcLiteChain};
+
+/// Creates a mock client of the chain itself on behalf of the creator.
+fn create_client(
+    chain: &mut IbcLiteChain,
+    creator: &Addr,
+    funds: &[Coin],
+) -> anyhow::Result<AppResponse> {
+    let msg = ics02_msg::ExecuteMsg::CreateClient {
+        code_id: chain.mock_client_code_id,
+        instantiate_msg: chain.mock_client_instantiate_msg()?,
+        counterparty_info: None,
+    };
+    chain
+        .app
+        .execute_contract(creator.clone(), chain.ics02_client.clone(), &msg, funds)
+}
+
+/// Updates the creation policy through the router, on behalf of the sender.
+fn update_policy(
+    chain: &mut IbcLiteChain,
+    sender: &Addr,
+    creation_policy: CreationPolicy,
+) -> anyhow::Result<AppResponse> {
+    chain.app.execute_contract(
+        sender.clone(),
+        chain.router.clone(),
+        &ics26_msg::ExecuteMsg::UpdateIcs02CreationPolicy { creation_policy },
+        &[],
+    )
+}
+
+fn set_policy(chain: &mut IbcLiteChain, creation_policy: CreationPolicy) {
+    let owner = chain.owner.clone();
+    update_policy(chain, &owner, creation_policy).unwrap();
+}
+
+fn creation_policy(chain: &IbcLiteChain) -> CreationPolicy {
+    chain
+        .app
+        .wrap()
+        .query_wasm_smart(&chain.ics02_client, &ics02_msg::QueryMsg::CreationPolicy {})
+        .unwrap()
+}
+
+fn created_clients(chain: &IbcLiteChain, creator: &Addr) -> Option<u64> {
+    ics02_state::CREATED_CLIENTS
+        .query(&chain.app.wrap(), chain.ics02_client.clone(), creator)
+        .unwrap()
+}
+
+#[test]
+fn permissionless_creation_is_free() {
+    let mut chain = IbcLiteChain::new("chain-a").unwrap();
+    let alice = chain.app.api().addr_make("alice");
+    chain.fund(&alice, vec![coin(100, "ustake")]).unwrap();
+    assert_eq!(creation_policy(&chain), CreationPolicy::Permissionless);
+
+    create_client(&mut chain, &alice, &[]).unwrap();
+    create_client(&mut chain, &alice, &[]).unwrap();
+    create_client(&mut chain, &alice, &[coin(100, "ustake")]).unwrap_err();
+    assert_eq!(created_clients(&chain, &alice), Some(2));
+    assert_eq!(chain.balance(&alice, "ustake").unwrap(), 100);
+}
+
+#[test]
+fn fee_is_collected_and_the_excess_refunded() {
+    let mut chain = IbcLiteChain::new("chain-a").unwrap();
+    let (alice, collector) = (
+        chain.app.api().addr_make("alice"),
+        chain.app.api().addr_make("collector"),
+    );
+    chain
+        .fund(&alice, vec![coin(1_000, "ustake"), coin(1_000, "uatom")])
+        .unwrap();
+    set_policy(
+        &mut chain,
+        CreationPolicy::Fee {
+            amount: vec![coin(100, "ustake")],
+            collector: collector.to_string(),
+        },
+    );
+
+    create_client(&mut chain, &alice, &[]).unwrap_err();
+    create_client(&mut chain, &alice, &[coin(99, "ustake")]).unwrap_err();
+    create_client(&mut chain, &alice, &[coin(100, "uatom")]).unwrap_err();
+    assert_eq!(chain.balance(&collector, "ustake").unwrap(), 0);
+
+    create_client(&mut chain, &alice, &[coin(100, "ustake")]).unwrap();
+    assert_eq!(chain.balance(&collector, "ustake").unwrap(), 100);
+    assert_eq!(chain.balance(&alice, "ustake").unwrap(), 900);
+
+    create_client(
+        &mut chain,
+        &alice,
+        &[coin(10, "uatom"), coin(150, "ustake")],
+    )
+    .unwrap();
+    assert_eq!(chain.balance(&collector, "ustake").unwrap(), 200);
+    assert_eq!(chain.balance(&collector, "uatom").unwrap(), 0);
+    assert_eq!(chain.balance(&alice, "ustake").unwrap(), 800);
+    assert_eq!(chain.balance(&alice, "uatom").unwrap(), 1_000);
+    let ics02_client = chain.ics02_client.clone();
+    assert_eq!(chain.balance(&ics02_client, "ustake").unwrap(), 0);
+    assert_eq!(chain.balance(&ics02_client, "uatom").unwrap(), 0);
+}
+
+#[test]
+fn quota_counts_every_client_of_a_creator() {
+    let mut chain = IbcLiteChain::new("chain-a").unwrap();
+    let (alice, bob) = (
+        chain.app.api().addr_make("alice"),
+        chain.app.api().addr_make("bob"),
+    );
+
+    // clients created before the quota count towards it
+    create_client(&mut chain, &alice, &[]).unwrap();
+    set_policy(
+        &mut chain,
+        CreationPolicy::Quota {
+            max_clients_per_creator: 2,
+        },
+    );
+
+    create_client(&mut chain, &alice, &[]).unwrap();
+    create_client(&mut chain, &alice, &[]).unwrap_err();
+    assert_eq!(created_clients(&chain, &alice), Some(2));
+
+    create_client(&mut chain, &bob, &[]).unwrap();
+    create_client(&mut chain, &bob, &[]).unwrap();
+    create_client(&mut chain, &bob, &[]).unwrap_err();
+    assert_eq!(created_clients(&chain, &bob), Some(2));
+}
+
+#[test]
+fn allowlist_restricts_the_creators() {
+    let mut chain = IbcLiteChain::new("chain-a").unwrap();
+    let (alice, bob) = (
+        chain.app.api().addr_make("alice"),
+        chain.app.api().addr_make("bob"),
+    );
+    set_policy(
+        &mut chain,
+        CreationPolicy::Allowlist {
+            creators: vec![alice.to_string()],
+        },
+    );
+
+    create_client(&mut chain, &alice, &[]).unwrap();
+    create_client(&mut chain, &bob, &[]).unwrap_err();
+    assert_eq!(created_clients(&chain, &bob), None);
+}
+
+#[test]
+fn policy_is_updated_by_the_router_owner() {
+    let mut chain = IbcLiteChain::new("chain-a").unwrap();
+    let (owner, alice) = (chain.owner.clone(), chain.app.api().addr_make("alice"));
+    let quota = CreationPolicy::Quota {
+        max_clients_per_creator: 1,
+    };
+
+    update_policy(&mut chain, &alice, quota.clone()).unwrap_err();
+    // the router owns cw-ibc-lite-ics02-client, not its owner
+    chain
+        .app
+        .execute_contract(
+            owner.clone(),
+            chain.ics02_client.clone(),
+            &ics02_msg::ExecuteMsg::UpdateCreationPolicy {
+                creation_policy: quota.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    update_policy(
+        &mut chain,
+        &owner,
+        CreationPolicy::Fee {
+            amount: vec![],
+            collector: "not-an-address".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(creation_policy(&chain), CreationPolicy::Permissionless);
+
+    let response = update_policy(&mut chain, &owner, quota.clone()).unwrap();
+    assert_eq!(
+        event_attribute(
+            &response.events,
+            ics02_events::EVENT_TYPE_UPDATE_CREATION_POLICY,
+            ics02_events::ATTRIBUTE_KEY_CREATION_POLICY,
+        ),
+        Some(cosmwasm_std::to_json_string(&quota).unwrap())
+    );
+    assert_eq!(creation_policy(&chain), quota);
+}
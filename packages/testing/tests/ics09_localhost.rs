@@ -0,0 +1,238 @@
+//! Tests of `cw-ibc-lite-ics09-localhost`, which lets `cw-ibc-lite-ics20-transfer` send packets
+//! to itself through the router of a single chain.
+
+use cosmwasm_std::{coin, Addr, Binary};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw_ibc_lite_ics02_client::types::state::{self as ics02_state, CounterpartyInfo};
+use cw_ibc_lite_ics09_localhost::types::state as localhost_state;
+use cw_ibc_lite_ics20_transfer::types::msg::{
+    responses::VoucherInfo, ExecuteMsg, QueryMsg, TransferMsg,
+};
+use cw_ibc_lite_ics26_router::types::msg::{
+    execute::{AcknowledgementMsg, RecvPacketMsg, TimeoutMsg},
+    ExecuteMsg as RouterExecuteMsg,
+};
+use cw_ibc_lite_shared::types::{
+    ibc::{Height, Packet},
+    transfer::packet::Ics20Ack,
+};
+use cw_ibc_lite_testing::{chain::IbcLiteChain, relayer};
+
+/// A chain with the transfer app deployed, and two localhost clients that are the counterparty
+/// of each other.
+struct Localhost {
+    chain: IbcLiteChain,
+    ics20: Addr,
+    /// The client the packets are sent on.
+    source: String,
+    /// The client the packets are received on.
+    destination: String,
+}
+
+impl Localhost {
+    fn new() -> Self {
+        let mut chain = IbcLiteChain::new("chain-a").unwrap();
+        let ics20 = chain.deploy_ics20().unwrap();
+        let source = chain.create_localhost_client(None).unwrap();
+        let destination = chain
+            .create_localhost_client(Some(CounterpartyInfo {
+                client_id: source.clone(),
+                merkle_path_prefix: None,
+            }))
+            .unwrap();
+        chain.provide_counterparty(&source, &destination).unwrap();
+
+        Self {
+            chain,
+            ics20,
+            source,
+            destination,
+        }
+    }
+
+    fn addr(&self, account: &str) -> Addr {
+        self.chain.app.api().addr_make(account)
+    }
+
+    /// Funds alice and sends 100 uatom of hers to bob on the source client.
+    fn send(&mut self) -> Packet {
+        let (alice, bob) = (self.addr("alice"), self.addr("bob"));
+        self.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+        let response = self
+            .chain
+            .app
+            .execute_contract(
+                alice,
+                self.ics20.clone(),
+                &ExecuteMsg::Transfer(TransferMsg {
+                    source_channel: self.source.clone(),
+                    receiver: bob.to_string(),
+                    timeout: None,
+                    memo: None,
+                    forwarding: None,
+                    callback: None,
+                }),
+                &[coin(100, "uatom")],
+            )
+            .unwrap();
+
+        relayer::parse_send_packets(&response.events)
+            .unwrap()
+            .remove(0)
+    }
+
+    /// Executes the router as the relayer.
+    fn relay(&mut self, msg: &RouterExecuteMsg) -> anyhow::Result<AppResponse> {
+        let relayer = self.addr("relayer");
+        self.chain
+            .app
+            .execute_contract(relayer, self.chain.router.clone(), msg, &[])
+    }
+
+    fn recv(&mut self, packet: &Packet, proof_height: Height) -> anyhow::Result<AppResponse> {
+        self.relay(&RouterExecuteMsg::RecvPacket(RecvPacketMsg {
+            packet: packet.clone(),
+            proof_commitment: relayer::MOCK_PROOF.into(),
+            proof_height,
+        }))
+    }
+
+    fn acknowledge(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: Binary,
+    ) -> anyhow::Result<AppResponse> {
+        let proof_height = self.chain.height();
+        self.relay(&RouterExecuteMsg::Acknowledgement(AcknowledgementMsg {
+            packet: packet.clone(),
+            acknowledgement,
+            proof_acked: relayer::MOCK_PROOF.into(),
+            proof_height,
+        }))
+    }
+
+    fn timeout(&mut self, packet: &Packet) -> anyhow::Result<AppResponse> {
+        let proof_height = self.chain.height();
+        self.relay(&RouterExecuteMsg::Timeout(TimeoutMsg {
+            packet: packet.clone(),
+            proof_unreceived: relayer::MOCK_PROOF.into(),
+            proof_height,
+            next_sequence_recv: 0,
+        }))
+    }
+
+    /// Receives the packet at the current height, and returns its acknowledgement.
+    fn recv_and_ack(&mut self, packet: &Packet) -> Binary {
+        let response = self.recv(packet, self.chain.height()).unwrap();
+        relayer::parse_write_acknowledgement(&response.events)
+            .unwrap()
+            .unwrap()
+    }
+}
+
+#[test]
+fn router_is_read_from_the_ics02_owner() {
+    let net = Localhost::new();
+    for client_id in [&net.source, &net.destination] {
+        let address = ics02_state::CLIENTS
+            .query(
+                &net.chain.app.wrap(),
+                net.chain.ics02_client.clone(),
+                client_id.as_str(),
+            )
+            .unwrap();
+        let client_state = localhost_state::CLIENT_STATE
+            .query(&net.chain.app.wrap(), address)
+            .unwrap();
+        assert_eq!(client_state.router_address, net.chain.router);
+    }
+}
+
+#[test]
+fn packets_are_relayed_on_one_chain() {
+    let mut net = Localhost::new();
+    let (alice, bob) = (net.addr("alice"), net.addr("bob"));
+    let packet = net.send();
+
+    let ack = net.recv_and_ack(&packet);
+    assert_eq!(
+        cosmwasm_std::from_json::<Ics20Ack>(&ack).unwrap(),
+        Ics20Ack::success()
+    );
+    // the packet cannot be received twice
+    net.recv(&packet, net.chain.height()).unwrap_err();
+    net.acknowledge(&packet, ack).unwrap();
+    assert!(!net.chain.has_packet_commitment(&packet).unwrap());
+
+    let voucher: VoucherInfo = net
+        .chain
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &net.ics20,
+            &QueryMsg::Voucher {
+                denom_trace: format!(
+                    "{}/{}/uatom",
+                    packet.destination_port.as_str(),
+                    net.destination
+                ),
+            },
+        )
+        .unwrap();
+    let voucher = Addr::unchecked(voucher.address);
+    assert_eq!(net.chain.cw20_balance(&voucher, &bob).unwrap(), 100);
+    assert_eq!(net.chain.balance(&alice, "uatom").unwrap(), 0);
+    assert_eq!(net.chain.balance(&net.ics20, "uatom").unwrap(), 100);
+}
+
+#[test]
+fn router_storage_must_match_the_proven_value() {
+    let mut net = Localhost::new();
+    let packet = net.send();
+
+    // the router holds no commitment of a packet that was not sent
+    let forged = Packet {
+        data: b"forged".into(),
+        ..packet.clone()
+    };
+    net.recv(&forged, net.chain.height()).unwrap_err();
+
+    // the router holds no acknowledgement before the packet is received
+    net.acknowledge(&packet, Ics20Ack::success().to_vec().into())
+        .unwrap_err();
+
+    // the router holds a different acknowledgement than the one relayed
+    let ack = net.recv_and_ack(&packet);
+    net.acknowledge(&packet, Ics20Ack::error("forged").to_vec().into())
+        .unwrap_err();
+    net.acknowledge(&packet, ack).unwrap();
+}
+
+#[test]
+fn proof_height_must_not_be_in_the_future() {
+    let mut net = Localhost::new();
+    let packet = net.send();
+
+    let mut proof_height = net.chain.height();
+    proof_height.revision_height += 1;
+    net.recv(&packet, proof_height).unwrap_err();
+    net.recv_and_ack(&packet);
+}
+
+#[test]
+fn only_unreceived_packets_time_out() {
+    let mut net = Localhost::new();
+    let alice = net.addr("alice");
+    let (unreceived, received) = (net.send(), net.send());
+    net.recv_and_ack(&received);
+
+    net.timeout(&unreceived).unwrap_err();
+    net.chain.next_blocks(200);
+    net.recv(&unreceived, net.chain.height()).unwrap_err();
+    net.timeout(&unreceived).unwrap();
+    assert_eq!(net.chain.balance(&alice, "uatom").unwrap(), 100);
+
+    // the receipt is read from the router
+    net.timeout(&received).unwrap_err();
+}
@@ -0,0 +1,245 @@
+//! Tests of the batch transfers of `cw-ibc-lite-ics20-transfer`, which send one packet per
+//! receiver, or a single `ics20-batch-1` packet to counterparties that accept it.
+
+mod common;
+
+use cosmwasm_std::{coin, Coin, IbcTimeoutBlock, Uint128};
+
+use cw_ibc_lite_ics20_transfer::types::msg::{
+    responses, BatchRecipient, BatchTransferMsg, ExecuteMsg, QueryMsg, TransferTimeout,
+};
+use cw_ibc_lite_shared::types::transfer::packet::{Ics20Ack, Ics20PacketData};
+use cw_ibc_lite_testing::relayer;
+
+use common::{relay, sent_packets, transfer_msg, Chain, Network};
+
+fn batch_msg(source_channel: &str, recipients: &[(&str, u128)]) -> BatchTransferMsg {
+    BatchTransferMsg {
+        source_channel: source_channel.to_string(),
+        recipients: recipients
+            .iter()
+            .map(|(receiver, amount)| BatchRecipient {
+                receiver: (*receiver).to_string(),
+                amount: Uint128::new(*amount),
+            })
+            .collect(),
+        timeout: None,
+        memo: None,
+    }
+}
+
+/// Records that the counterparty app on the channel accepts `ics20-batch-1` packets.
+fn accept_batches(chain: &mut Chain, channel: &str) {
+    let owner = chain.chain.owner.clone();
+    let msg = ExecuteMsg::SetChannelBatchSupport {
+        channel: channel.to_string(),
+        supported: true,
+    };
+    chain.execute(&owner, &msg, &[]).unwrap();
+}
+
+#[test]
+fn native_tokens_are_sent_in_one_packet_per_receiver() {
+    let mut net = Network::new();
+    let (alice, bob, carol) = (net.a.addr("alice"), net.b.addr("bob"), net.b.addr("carol"));
+    net.a.chain.fund(&alice, vec![coin(300, "uatom")]).unwrap();
+
+    let msg = batch_msg(&net.ab, &[(bob.as_str(), 100), (carol.as_str(), 200)]);
+    let response = net
+        .a
+        .execute(
+            &alice,
+            &ExecuteMsg::BatchTransfer(msg),
+            &[coin(300, "uatom")],
+        )
+        .unwrap();
+    let packets = sent_packets(&response);
+    assert_eq!(packets.len(), 2);
+    for packet in &packets {
+        let data: Ics20PacketData = cosmwasm_std::from_json(&packet.data).unwrap();
+        assert!(matches!(data, Ics20PacketData::V1(_)));
+    }
+
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success(), Ics20Ack::success()]);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 300);
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+    assert_eq!(net.b.cw20_balance(&voucher, &carol), 200);
+}
+
+#[test]
+fn only_failed_receivers_are_refunded() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a.chain.fund(&alice, vec![coin(150, "uatom")]).unwrap();
+
+    // the vouchers cannot be minted to an invalid address on `B`
+    let msg = batch_msg(&net.ab, &[(bob.as_str(), 100), ("not-an-address", 50)]);
+    let response = net
+        .a
+        .execute(
+            &alice,
+            &ExecuteMsg::BatchTransfer(msg),
+            &[coin(150, "uatom")],
+        )
+        .unwrap();
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks[0], Ics20Ack::success());
+    assert!(matches!(acks[1], Ics20Ack::Error(_)));
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 50);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+}
+
+#[test]
+fn batches_must_send_exactly_the_sum_of_one_coin() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom"), coin(1_000, "ustake")])
+        .unwrap();
+    let mut batch = |recipients: &[(&str, u128)], funds: &[Coin]| {
+        let msg = ExecuteMsg::BatchTransfer(batch_msg(&net.ab, recipients));
+        net.a.execute(&alice, &msg, funds).unwrap_err();
+    };
+
+    batch(&[], &[]);
+    batch(&[], &[coin(100, "uatom")]);
+    batch(&[(bob.as_str(), 100)], &[coin(99, "uatom")]);
+    batch(&[(bob.as_str(), 100)], &[coin(101, "uatom")]);
+    batch(
+        &[(bob.as_str(), 100)],
+        &[coin(50, "uatom"), coin(50, "ustake")],
+    );
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+    assert_eq!(net.a.balance(&alice, "ustake"), 1_000);
+}
+
+#[test]
+fn cw20_tokens_are_sent_to_many_receivers() {
+    let mut net = Network::new();
+    let (alice, bob, carol) = (net.a.addr("alice"), net.b.addr("bob"), net.c.addr("carol"));
+
+    // alice sends uatom to bob on `B`, who sends the vouchers on to `C` in a batch
+    net.a.chain.fund(&alice, vec![coin(300, "uatom")]).unwrap();
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(300, "uatom")]);
+    relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+
+    // the vouchers cannot be minted to an invalid address on `C`
+    let msg = batch_msg(&net.bc, &[(carol.as_str(), 250), ("not-an-address", 50)]);
+    let response = net.b.send_cw20(&voucher, &bob, 300, &msg);
+    assert_eq!(sent_packets(&response).len(), 2);
+    let acks = relay(&mut net.b, &net.bc, &mut net.c, &net.cb, &response);
+    assert_eq!(acks[0], Ics20Ack::success());
+    assert!(matches!(acks[1], Ics20Ack::Error(_)));
+
+    let trace_c = format!(
+        "{}/{}/{}/{}/uatom",
+        net.c.port(),
+        net.cb,
+        net.b.port(),
+        net.ba
+    );
+    assert_eq!(net.c.cw20_balance(&net.c.voucher(&trace_c), &carol), 250);
+    // only the vouchers of the failed receiver are returned to bob
+    let ics20_b = net.b.ics20.clone();
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 50);
+    assert_eq!(net.b.cw20_balance(&voucher, &ics20_b), 250);
+}
+
+#[test]
+fn batches_are_sent_in_one_packet_to_counterparties_that_accept_them() {
+    let mut net = Network::new();
+    let (alice, bob, carol) = (net.a.addr("alice"), net.b.addr("bob"), net.b.addr("carol"));
+    net.a.chain.fund(&alice, vec![coin(300, "uatom")]).unwrap();
+    accept_batches(&mut net.a, &net.ab);
+    let support: responses::ChannelBatchSupport = net.a.query(&QueryMsg::ChannelBatchSupport {
+        channel: net.ab.clone(),
+    });
+    assert!(support.supported);
+
+    let msg = batch_msg(&net.ab, &[(bob.as_str(), 100), (carol.as_str(), 200)]);
+    let response = net
+        .a
+        .execute(
+            &alice,
+            &ExecuteMsg::BatchTransfer(msg),
+            &[coin(300, "uatom")],
+        )
+        .unwrap();
+    let packets = sent_packets(&response);
+    assert_eq!(packets.len(), 1);
+    let data: Ics20PacketData = cosmwasm_std::from_json(&packets[0].data).unwrap();
+    assert!(matches!(data, Ics20PacketData::Batch(_)));
+
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 300);
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+    assert_eq!(net.b.cw20_balance(&voucher, &carol), 200);
+}
+
+#[test]
+fn batch_packets_are_received_atomically() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a.chain.fund(&alice, vec![coin(150, "uatom")]).unwrap();
+    accept_batches(&mut net.a, &net.ab);
+
+    // the vouchers cannot be minted to an invalid address on `B`, so bob receives none either
+    let msg = batch_msg(&net.ab, &[(bob.as_str(), 100), ("not-an-address", 50)]);
+    let response = net
+        .a
+        .execute(
+            &alice,
+            &ExecuteMsg::BatchTransfer(msg),
+            &[coin(150, "uatom")],
+        )
+        .unwrap();
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 150);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
+
+#[test]
+fn batch_packets_that_time_out_refund_the_sender() {
+    let mut net = Network::new();
+    let (alice, bob, carol) = (net.a.addr("alice"), net.b.addr("bob"), net.b.addr("carol"));
+    net.a.chain.fund(&alice, vec![coin(300, "uatom")]).unwrap();
+    accept_batches(&mut net.a, &net.ab);
+
+    let block = IbcTimeoutBlock {
+        revision: 0,
+        height: net.b.chain.height().revision_height + 10,
+    };
+    let msg = BatchTransferMsg {
+        timeout: Some(TransferTimeout::Height(block)),
+        ..batch_msg(&net.ab, &[(bob.as_str(), 100), (carol.as_str(), 200)])
+    };
+    let response = net
+        .a
+        .execute(
+            &alice,
+            &ExecuteMsg::BatchTransfer(msg),
+            &[coin(300, "uatom")],
+        )
+        .unwrap();
+    let packet = sent_packets(&response).remove(0);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 300);
+
+    net.b.chain.next_blocks(10);
+    relayer::timeout_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet).unwrap();
+    assert_eq!(net.a.balance(&alice, "uatom"), 300);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
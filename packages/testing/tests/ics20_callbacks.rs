@@ -0,0 +1,248 @@
+//! Tests of the source callbacks of `cw-ibc-lite-ics20-transfer`, with a vault contract that
+//! sends transfers and records their outcomes.
+
+mod common;
+
+use cosmwasm_std::{
+    coin, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, WasmMsg,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use serde::{Deserialize, Serialize};
+
+use cw_ibc_lite_ics20_transfer::types::{
+    msg::{responses, ExecuteMsg, QueryMsg, TransferMsg},
+    state::ChannelPolicy,
+};
+use cw_ibc_lite_shared::types::transfer::{
+    callbacks::{IbcTransferCallback, TransferOutcome},
+    packet::Ics20Ack,
+};
+use cw_ibc_lite_testing::relayer;
+
+use common::{relay, sent_packets, transfer_msg, Chain, Network};
+
+const OUTCOMES_KEY: &[u8] = b"outcomes";
+const FAIL_KEY: &[u8] = b"fail";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VaultExecuteMsg {
+    /// Sends the funds with the transfer app, with the vault as the callback contract.
+    Send {
+        ics20: String,
+        transfer: TransferMsg,
+    },
+    /// Makes the vault fail its callbacks.
+    Fail {},
+    IbcTransferCallback(IbcTransferCallback),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VaultQueryMsg {
+    Outcomes {},
+}
+
+fn outcomes(deps: Deps) -> StdResult<Vec<TransferOutcome>> {
+    deps.storage
+        .get(OUTCOMES_KEY)
+        .map_or_else(|| Ok(vec![]), cosmwasm_std::from_json)
+}
+
+fn vault_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: cosmwasm_std::Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn vault_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: VaultExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        VaultExecuteMsg::Send { ics20, transfer } => {
+            let transfer = TransferMsg {
+                callback: Some(env.contract.address.to_string()),
+                ..transfer
+            };
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: ics20,
+                msg: cosmwasm_std::to_json_binary(&ExecuteMsg::Transfer(transfer))?,
+                funds: info.funds,
+            }))
+        }
+        VaultExecuteMsg::Fail {} => {
+            deps.storage.set(FAIL_KEY, &[1]);
+            Ok(Response::default())
+        }
+        VaultExecuteMsg::IbcTransferCallback(callback) => {
+            if deps.storage.get(FAIL_KEY).is_some() {
+                return Err(StdError::generic_err("vault failure"));
+            }
+            let mut outcomes = outcomes(deps.as_ref())?;
+            outcomes.push(callback.outcome);
+            deps.storage
+                .set(OUTCOMES_KEY, &cosmwasm_std::to_json_vec(&outcomes)?);
+            Ok(Response::default())
+        }
+    }
+}
+
+fn vault_query(deps: Deps, _env: Env, msg: VaultQueryMsg) -> StdResult<Binary> {
+    match msg {
+        VaultQueryMsg::Outcomes {} => cosmwasm_std::to_json_binary(&outcomes(deps)?),
+    }
+}
+
+/// Deploys a vault on `A`, and funds alice to send transfers through it.
+fn deploy_vault(net: &mut Network) -> cosmwasm_std::Addr {
+    let app = &mut net.a.chain.app;
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        vault_execute,
+        vault_instantiate,
+        vault_query,
+    )));
+    let vault = app
+        .instantiate_contract(
+            code_id,
+            net.a.chain.owner.clone(),
+            &cosmwasm_std::Empty {},
+            &[],
+            "vault",
+            None,
+        )
+        .unwrap();
+    let alice = net.a.addr("alice");
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+    vault
+}
+
+/// Sends 100 uatom of alice to bob on `B` through the vault, which is refunded if it fails.
+fn send(net: &mut Network, vault: &cosmwasm_std::Addr) -> cw_multi_test::AppResponse {
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let msg = VaultExecuteMsg::Send {
+        ics20: net.a.ics20.to_string(),
+        transfer: transfer_msg(&net.ab, &bob),
+    };
+    net.a
+        .chain
+        .app
+        .execute_contract(alice, vault.clone(), &msg, &[coin(100, "uatom")])
+        .unwrap()
+}
+
+fn vault_outcomes(chain: &Chain, vault: &cosmwasm_std::Addr) -> Vec<TransferOutcome> {
+    chain
+        .chain
+        .app
+        .wrap()
+        .query_wasm_smart(vault, &VaultQueryMsg::Outcomes {})
+        .unwrap()
+}
+
+fn disable_receive(net: &mut Network) {
+    let owner = net.b.chain.owner.clone();
+    let channel = net.ba.clone();
+    net.b
+        .execute(
+            &owner,
+            &ExecuteMsg::SetChannelPolicy {
+                channel,
+                policy: Some(ChannelPolicy {
+                    send_enabled: true,
+                    receive_enabled: false,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn source_callbacks_are_notified_of_every_outcome() {
+    let mut net = Network::new();
+    let vault = deploy_vault(&mut net);
+
+    let response = send(&mut net, &vault);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(
+        vault_outcomes(&net.a, &vault),
+        vec![TransferOutcome::Success]
+    );
+
+    disable_receive(&mut net);
+    let response = send(&mut net, &vault);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+    assert!(matches!(
+        vault_outcomes(&net.a, &vault)[..],
+        [TransferOutcome::Success, TransferOutcome::Error { .. }]
+    ));
+
+    let response = send(&mut net, &vault);
+    let packet = sent_packets(&response).remove(0);
+    net.b.chain.next_blocks(200);
+    relayer::timeout_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet).unwrap();
+    assert!(matches!(
+        vault_outcomes(&net.a, &vault)[..],
+        [
+            TransferOutcome::Success,
+            TransferOutcome::Error { .. },
+            TransferOutcome::Timeout
+        ]
+    ));
+
+    // only the first transfer was received, the others were refunded to the vault
+    assert_eq!(net.a.balance(&vault, "uatom"), 200);
+}
+
+#[test]
+fn failing_callbacks_do_not_block_refunds() {
+    let mut net = Network::new();
+    let vault = deploy_vault(&mut net);
+    net.a
+        .chain
+        .app
+        .execute_contract(vault.clone(), vault.clone(), &VaultExecuteMsg::Fail {}, &[])
+        .unwrap();
+    disable_receive(&mut net);
+
+    let response = send(&mut net, &vault);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    assert_eq!(vault_outcomes(&net.a, &vault), vec![]);
+    assert_eq!(net.a.balance(&vault, "uatom"), 100);
+}
+
+#[test]
+fn callback_gas_limit() {
+    let mut net = Network::new();
+    let owner = net.a.chain.owner.clone();
+    let alice = net.a.addr("alice");
+    let gas_limit = |chain: &Chain| {
+        chain
+            .query::<responses::CallbackGasLimit>(&QueryMsg::CallbackGasLimit {})
+            .gas_limit
+    };
+    assert_eq!(gas_limit(&net.a), 1_000_000);
+
+    let set = |gas_limit| ExecuteMsg::SetCallbackGasLimit { gas_limit };
+    net.a.execute(&alice, &set(Some(50_000)), &[]).unwrap_err();
+    net.a.execute(&owner, &set(Some(0)), &[]).unwrap_err();
+
+    net.a.execute(&owner, &set(Some(50_000)), &[]).unwrap();
+    assert_eq!(gas_limit(&net.a), 50_000);
+
+    net.a.execute(&owner, &set(None), &[]).unwrap();
+    assert_eq!(gas_limit(&net.a), 1_000_000);
+}
@@ -0,0 +1,122 @@
+//! Tests of the denom trace registry of `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::{coin, StdResult};
+
+use cw_ibc_lite_ics20_transfer::types::msg::{responses, QueryMsg};
+use cw_ibc_lite_shared::types::transfer::{denom::DenomTrace, packet::Ics20Ack};
+
+use common::{relay, transfer_msg, Chain, Network};
+
+/// Sends 100 of each denom from alice on `A` to bob on `B`.
+fn send_to_b(net: &mut Network, denoms: &[&str]) {
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    for denom in denoms {
+        net.a.chain.fund(&alice, vec![coin(100, *denom)]).unwrap();
+        let response = net
+            .a
+            .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, *denom)]);
+        let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+        assert_eq!(acks, vec![Ics20Ack::success()]);
+    }
+}
+
+fn try_query<T: serde::de::DeserializeOwned>(chain: &Chain, msg: &QueryMsg) -> StdResult<T> {
+    chain.chain.app.wrap().query_wasm_smart(&chain.ics20, msg)
+}
+
+#[test]
+fn received_denom_traces_are_registered() {
+    let mut net = Network::new();
+    send_to_b(&mut net, &["uatom"]);
+    let expected = DenomTrace {
+        path: format!("{}/{}", net.b.port(), net.ba),
+        base_denom: "uatom".to_string(),
+    };
+    let hash = expected.hash();
+
+    let info: responses::DenomTraceInfo = net.b.query(&QueryMsg::DenomTrace { hash: hash.clone() });
+    assert_eq!(info.hash, hash);
+    assert_eq!(info.denom_trace, expected);
+
+    // the `ibc/` prefix is optional, and the hash is not case sensitive
+    let info: responses::DenomTraceInfo = net.b.query(&QueryMsg::DenomTrace {
+        hash: format!("ibc/{}", hash.to_lowercase()),
+    });
+    assert_eq!(info.denom_trace, expected);
+
+    let denom_hash: responses::DenomHash = net.b.query(&QueryMsg::DenomHash {
+        trace: expected.full_path(),
+    });
+    assert_eq!(denom_hash.hash, hash);
+
+    // native denoms of `A` are not registered there
+    let uatom = DenomTrace {
+        path: String::new(),
+        base_denom: "uatom".to_string(),
+    };
+    let msg = QueryMsg::DenomTrace { hash: uatom.hash() };
+    try_query::<responses::DenomTraceInfo>(&net.a, &msg).unwrap_err();
+    try_query::<responses::DenomTraceInfo>(
+        &net.b,
+        &QueryMsg::DenomTrace {
+            hash: "ibc/not-a-hash".to_string(),
+        },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn denom_traces_are_listed_by_hash() {
+    let mut net = Network::new();
+    send_to_b(&mut net, &["uatom", "ustake", "uosmo"]);
+    let list = |start_after: Option<String>, limit: Option<u32>| {
+        let msg = QueryMsg::ListDenomTraces { start_after, limit };
+        net.b.query::<responses::DenomTraceList>(&msg).list
+    };
+
+    let all = list(None, None);
+    assert_eq!(all.len(), 3);
+    assert!(all.windows(2).all(|pair| pair[0].hash < pair[1].hash));
+    let mut base_denoms: Vec<_> = all
+        .iter()
+        .map(|info| info.denom_trace.base_denom.as_str())
+        .collect();
+    base_denoms.sort_unstable();
+    assert_eq!(base_denoms, ["uatom", "uosmo", "ustake"]);
+
+    let first = list(None, Some(2));
+    assert_eq!(first, all[..2]);
+    let rest = list(Some(format!("ibc/{}", first[1].hash)), Some(2));
+    assert_eq!(rest, all[2..]);
+}
+
+#[test]
+fn denom_traces_of_vouchers_sent_onward_have_every_hop() {
+    let mut net = Network::new();
+    send_to_b(&mut net, &["uatom"]);
+    let (bob, carol) = (net.b.addr("bob"), net.c.addr("carol"));
+    let trace_b = format!("{}/{}/uatom", net.b.port(), net.ba);
+    let voucher = net.b.voucher(&trace_b);
+
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 100, &transfer_msg(&net.bc, &carol));
+    let acks = relay(&mut net.b, &net.bc, &mut net.c, &net.cb, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    let expected = DenomTrace {
+        path: format!("{}/{}/{}/{}", net.c.port(), net.cb, net.b.port(), net.ba),
+        base_denom: "uatom".to_string(),
+    };
+    let info: responses::DenomTraceInfo = net.c.query(&QueryMsg::DenomTrace {
+        hash: expected.hash(),
+    });
+    assert_eq!(info.denom_trace, expected);
+    assert_eq!(
+        net.c
+            .cw20_balance(&net.c.voucher(&expected.full_path()), &carol),
+        100
+    );
+}
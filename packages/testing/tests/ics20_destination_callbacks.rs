@@ -0,0 +1,185 @@
+//! Tests of the destination callbacks requested in the memos of the packets received by
+//! `cw-ibc-lite-ics20-transfer`, with a contract on `B` that records the funds it is executed
+//! with.
+
+mod common;
+
+use cosmwasm_std::{
+    coin, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{ContractWrapper, Executor};
+use serde::{Deserialize, Serialize};
+
+use cw_ibc_lite_ics20_transfer::types::msg::TransferMsg;
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+
+use common::{relay, transfer_msg, Network};
+
+const FUNDS_KEY: &[u8] = b"funds";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DexExecuteMsg {
+    /// Records the funds it is executed with.
+    Record {},
+    Fail {},
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DexQueryMsg {
+    Funds {},
+}
+
+fn dex_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn dex_execute(
+    deps: DepsMut,
+    _: Env,
+    info: MessageInfo,
+    msg: DexExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        DexExecuteMsg::Record {} => {
+            deps.storage
+                .set(FUNDS_KEY, &cosmwasm_std::to_json_vec(&info.funds)?);
+            Ok(Response::default())
+        }
+        DexExecuteMsg::Fail {} => Err(StdError::generic_err("dex failure")),
+    }
+}
+
+fn dex_query(deps: Deps, _: Env, msg: DexQueryMsg) -> StdResult<Binary> {
+    match msg {
+        DexQueryMsg::Funds {} => {
+            let funds: Option<Vec<Coin>> = deps
+                .storage
+                .get(FUNDS_KEY)
+                .map(cosmwasm_std::from_json)
+                .transpose()?;
+            cosmwasm_std::to_json_binary(&funds)
+        }
+    }
+}
+
+/// Deploys the dex on `B`.
+fn deploy_dex(net: &mut Network) -> Addr {
+    let app = &mut net.b.chain.app;
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        dex_execute,
+        dex_instantiate,
+        dex_query,
+    )));
+    app.instantiate_contract(
+        code_id,
+        net.b.chain.owner.clone(),
+        &Empty {},
+        &[],
+        "dex",
+        None,
+    )
+    .unwrap()
+}
+
+/// Returns the funds the dex was executed with, if it was.
+fn recorded_funds(net: &Network, dex: &Addr) -> Option<Vec<Coin>> {
+    net.b
+        .chain
+        .app
+        .wrap()
+        .query_wasm_smart(dex, &DexQueryMsg::Funds {})
+        .unwrap()
+}
+
+/// Returns a transfer on `A` to the contract on `B`, which is executed with the message.
+fn callback_msg(net: &Network, contract: &Addr, msg: &DexExecuteMsg) -> TransferMsg {
+    let msg = Binary::from(cosmwasm_std::to_json_vec(msg).unwrap()).to_base64();
+    TransferMsg {
+        memo: Some(format!(
+            r#"{{"wasm":{{"contract":"{contract}","msg":"{msg}"}}}}"#
+        )),
+        ..transfer_msg(&net.ab, contract)
+    }
+}
+
+#[test]
+fn vouchers_are_transferred_to_the_callback_contract() {
+    let mut net = Network::new();
+    let dex = deploy_dex(&mut net);
+    let alice = net.a.addr("alice");
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let msg = callback_msg(&net, &dex, &DexExecuteMsg::Record {});
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    // the vouchers are cw20 tokens, so they are not sent as funds
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    assert_eq!(net.b.cw20_balance(&voucher, &dex), 100);
+    assert_eq!(recorded_funds(&net, &dex), Some(vec![]));
+}
+
+#[test]
+fn returning_native_tokens_are_sent_as_funds() {
+    let mut net = Network::new();
+    let dex = deploy_dex(&mut net);
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+
+    // bob sends ubtc of `B` to alice on `A`, who sends them back to the dex
+    net.b.chain.fund(&bob, vec![coin(100, "ubtc")]).unwrap();
+    let response = net
+        .b
+        .transfer(&bob, transfer_msg(&net.ba, &alice), &[coin(100, "ubtc")]);
+    let acks = relay(&mut net.b, &net.ba, &mut net.a, &net.ab, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    let voucher = net.a.voucher(&format!("{}/{}/ubtc", net.a.port(), net.ab));
+
+    let msg = callback_msg(&net, &dex, &DexExecuteMsg::Record {});
+    let response = net.a.send_cw20(&voucher, &alice, 100, &msg);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    assert_eq!(recorded_funds(&net, &dex), Some(vec![coin(100, "ubtc")]));
+    assert_eq!(net.b.balance(&dex, "ubtc"), 100);
+    assert_eq!(net.b.escrowed(&net.ba, "ubtc"), 0);
+}
+
+#[test]
+fn failing_callbacks_are_acknowledged_with_an_error() {
+    let mut net = Network::new();
+    let dex = deploy_dex(&mut net);
+    let alice = net.a.addr("alice");
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let msg = callback_msg(&net, &dex, &DexExecuteMsg::Fail {});
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    // nothing was received on `B`, and alice is refunded
+    assert_eq!(recorded_funds(&net, &dex), None);
+    assert_eq!(net.a.balance(&alice, "uatom"), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
+
+#[test]
+fn callback_contract_must_be_the_receiver() {
+    let mut net = Network::new();
+    let dex = deploy_dex(&mut net);
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let msg = TransferMsg {
+        receiver: bob.to_string(),
+        ..callback_msg(&net, &dex, &DexExecuteMsg::Record {})
+    };
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    assert_eq!(recorded_funds(&net, &dex), None);
+    assert_eq!(net.a.balance(&alice, "uatom"), 100);
+}
@@ -0,0 +1,90 @@
+//! Tests of the packet data encodings of `cw-ibc-lite-ics20-transfer`. Packet data is encoded as
+//! set on each channel, while acknowledgements are always JSON encoded, as in `ibc-go`.
+
+mod common;
+
+use cosmwasm_std::{coin, Addr, Binary};
+
+use cw_ibc_lite_ics20_transfer::types::msg::{ExecuteMsg, TransferMsg};
+use cw_ibc_lite_shared::types::transfer::{
+    encoding::PacketEncoding,
+    packet::{Ics20Ack, Ics20PacketData},
+};
+use cw_ibc_lite_testing::relayer;
+
+use common::{decode_ack, sent_packets, transfer_msg, Chain, Network};
+
+/// Sets the encoding of the packet data on the channel.
+fn set_encoding(chain: &mut Chain, channel: &str, encoding: PacketEncoding) {
+    let owner = chain.chain.owner.clone();
+    let msg = ExecuteMsg::SetChannelEncoding {
+        channel: channel.to_string(),
+        encoding: Some(encoding),
+    };
+    chain.execute(&owner, &msg, &[]).unwrap();
+}
+
+/// Sends 100 uatom from alice on `A` to the receiver on `B` over protobuf channels, checks the
+/// packet data and returns the raw acknowledgement written on `B`.
+fn send_protobuf(net: &mut Network, alice: &Addr, receiver: &str) -> Binary {
+    set_encoding(&mut net.a, &net.ab, PacketEncoding::Protobuf);
+    set_encoding(&mut net.b, &net.ba, PacketEncoding::Protobuf);
+
+    let msg = TransferMsg {
+        receiver: receiver.to_string(),
+        ..transfer_msg(&net.ab, alice)
+    };
+    let response = net.a.transfer(alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    assert!(cosmwasm_std::from_json::<Ics20PacketData>(&packet.data).is_err());
+    let Ics20PacketData::V1(data) =
+        Ics20PacketData::decode(&packet.data, PacketEncoding::Protobuf).unwrap()
+    else {
+        panic!("protobuf packet data must be ics20-1");
+    };
+    assert_eq!(data.receiver, receiver);
+
+    relayer::relay_packet(
+        &mut net.a.chain,
+        &net.ab,
+        &mut net.b.chain,
+        &net.ba,
+        &packet,
+    )
+    .unwrap()
+}
+
+#[test]
+fn protobuf_packets_are_acknowledged_in_json() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    let ack = send_protobuf(&mut net, &alice, bob.as_str());
+    assert_eq!(ack.as_slice(), br#"{"result":"AQ=="}"#);
+    assert_eq!(decode_ack(&ack), Ics20Ack::success());
+
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+}
+
+#[test]
+fn protobuf_packets_are_refunded_on_json_error_acknowledgements() {
+    let mut net = Network::new();
+    let alice = net.a.addr("alice");
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    // the vouchers cannot be minted to an invalid address on `B`
+    let ack = send_protobuf(&mut net, &alice, "not-an-address");
+    assert!(matches!(decode_ack(&ack), Ics20Ack::Error(_)));
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
@@ -0,0 +1,160 @@
+//! Tests of the escrow invariant of `cw-ibc-lite-ics20-transfer`, and of the recovery actions of
+//! its admin.
+
+mod common;
+
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw_ibc_lite_ics20_transfer::types::{
+    events,
+    msg::{responses, ExecuteMsg, QueryMsg, Recovery},
+};
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+use cw_ibc_lite_testing::chain::event_attribute;
+
+use common::{relay, transfer_msg, Chain, Network};
+
+/// Sends `amount` uatom from alice on `A` to bob on `B`.
+fn send_to_b(net: &mut Network, amount: u128) {
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(amount, "uatom")])
+        .unwrap();
+    let response = net.a.transfer(
+        &alice,
+        transfer_msg(&net.ab, &bob),
+        &[coin(amount, "uatom")],
+    );
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+}
+
+fn invariant(chain: &Chain, denom: &str) -> responses::EscrowInvariant {
+    chain.query(&QueryMsg::EscrowInvariant {
+        denom: denom.to_string(),
+    })
+}
+
+/// Recovers as the admin of the chain.
+fn recover(chain: &mut Chain, recovery: Recovery) -> anyhow::Result<AppResponse> {
+    let owner = chain.chain.owner.clone();
+    chain.execute(&owner, &ExecuteMsg::Recover(recovery), &[])
+}
+
+fn return_funds(amount: u128, recipient: &Addr) -> Recovery {
+    Recovery::ReturnFunds {
+        denom: "uatom".to_string(),
+        amount: Uint128::new(amount),
+        recipient: recipient.to_string(),
+    }
+}
+
+fn set_escrow(channel: &str, amount: u128) -> Recovery {
+    Recovery::SetEscrow {
+        channel: channel.to_string(),
+        denom: "uatom".to_string(),
+        amount: Uint128::new(amount),
+    }
+}
+
+#[test]
+fn escrow_invariant_holds_across_transfers() {
+    let mut net = Network::new();
+    send_to_b(&mut net, 100);
+    assert_eq!(
+        invariant(&net.a, "uatom"),
+        responses::EscrowInvariant {
+            denom: "uatom".to_string(),
+            escrowed: Uint128::new(100),
+            balance: Uint128::new(100),
+            holds: true,
+        }
+    );
+
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 40, &transfer_msg(&net.ba, &alice));
+    let acks = relay(&mut net.b, &net.ba, &mut net.a, &net.ab, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    let check = invariant(&net.a, "uatom");
+    assert_eq!(check.escrowed.u128(), 60);
+    assert_eq!(check.balance.u128(), 60);
+    assert!(check.holds);
+}
+
+#[test]
+fn stranded_funds_are_returned_by_the_admin() {
+    let mut net = Network::new();
+    send_to_b(&mut net, 100);
+    let (alice, carol) = (net.a.addr("alice"), net.a.addr("carol"));
+    let ics20 = net.a.ics20.clone();
+
+    // tokens sent to the transfer app directly are stranded
+    net.a.chain.fund(&alice, vec![coin(30, "uatom")]).unwrap();
+    net.a
+        .chain
+        .app
+        .send_tokens(alice.clone(), ics20.clone(), &[coin(30, "uatom")])
+        .unwrap();
+    let check = invariant(&net.a, "uatom");
+    assert_eq!(check.escrowed.u128(), 100);
+    assert_eq!(check.balance.u128(), 130);
+    assert!(check.holds);
+
+    net.a
+        .execute(&alice, &ExecuteMsg::Recover(return_funds(30, &alice)), &[])
+        .unwrap_err();
+    recover(&mut net.a, return_funds(31, &carol)).unwrap_err();
+
+    let response = recover(&mut net.a, return_funds(30, &carol)).unwrap();
+    let attribute = |key| event_attribute(&response.events, events::EVENT_TYPE_RETURN_FUNDS, key);
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_DENOM).unwrap(), "uatom");
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_AMOUNT).unwrap(), "30");
+    assert_eq!(
+        attribute(events::ATTRIBUTE_KEY_RECIPIENT).unwrap(),
+        carol.as_str()
+    );
+
+    assert_eq!(net.a.balance(&carol, "uatom"), 30);
+    assert_eq!(net.a.balance(&ics20, "uatom"), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+}
+
+#[test]
+fn escrows_are_set_by_the_admin() {
+    let mut net = Network::new();
+    send_to_b(&mut net, 100);
+    let (alice, carol) = (net.a.addr("alice"), net.a.addr("carol"));
+    let ab = net.ab.clone();
+
+    net.a
+        .execute(&alice, &ExecuteMsg::Recover(set_escrow(&ab, 200)), &[])
+        .unwrap_err();
+
+    let response = recover(&mut net.a, set_escrow(&ab, 200)).unwrap();
+    let attribute = |key| event_attribute(&response.events, events::EVENT_TYPE_SET_ESCROW, key);
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_CHANNEL).unwrap(), ab);
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_DENOM).unwrap(), "uatom");
+    assert_eq!(
+        attribute(events::ATTRIBUTE_KEY_PREVIOUS_AMOUNT).unwrap(),
+        "100"
+    );
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_AMOUNT).unwrap(), "200");
+
+    // no funds can be returned while the invariant is broken
+    assert!(!invariant(&net.a, "uatom").holds);
+    recover(&mut net.a, return_funds(1, &carol)).unwrap_err();
+
+    recover(&mut net.a, set_escrow(&ab, 0)).unwrap();
+    assert_eq!(net.a.escrowed(&ab, "uatom"), 0);
+    let check = invariant(&net.a, "uatom");
+    assert_eq!(check.escrowed.u128(), 0);
+    assert!(check.holds);
+    recover(&mut net.a, return_funds(100, &carol)).unwrap();
+    assert_eq!(net.a.balance(&carol, "uatom"), 100);
+}
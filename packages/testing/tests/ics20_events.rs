@@ -0,0 +1,209 @@
+//! Tests of the transfer events and the in-flight transfers of `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::{coin, Addr, Event};
+use cw_multi_test::AppResponse;
+
+use cw_ibc_lite_ics20_transfer::types::{
+    events,
+    msg::{responses, QueryMsg, TransferMsg},
+    state::InFlightTransfer,
+};
+use cw_ibc_lite_shared::types::{ibc::Packet, transfer::packet::Ics20Ack};
+use cw_ibc_lite_testing::{chain::event_attribute, relayer};
+
+use common::{decode_ack, sent_packets, transfer_msg, Chain, Network};
+
+/// Funds alice on `A` and sends 100 uatom of hers with the transfer.
+fn send(net: &mut Network, msg: TransferMsg) -> (AppResponse, Packet) {
+    let alice = net.a.addr("alice");
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    (response, packet)
+}
+
+/// Asserts that the events have a transfer event of the given type with the attributes.
+fn assert_transfer_event(
+    emitted: &[Event],
+    ty: &str,
+    sender: &Addr,
+    receiver: &str,
+    channel: &str,
+    packet: &Packet,
+) {
+    let attribute = |key| event_attribute(emitted, ty, key);
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_DENOM).unwrap(), "uatom");
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_AMOUNT).unwrap(), "100");
+    assert_eq!(
+        attribute(events::ATTRIBUTE_KEY_SENDER).unwrap(),
+        sender.as_str()
+    );
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_RECEIVER).unwrap(), receiver);
+    assert_eq!(attribute(events::ATTRIBUTE_KEY_CHANNEL).unwrap(), channel);
+    assert_eq!(
+        attribute(events::ATTRIBUTE_KEY_SEQUENCE).unwrap(),
+        packet.sequence.value().to_string()
+    );
+}
+
+fn in_flight(
+    chain: &Chain,
+    sender: &Addr,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> Vec<InFlightTransfer> {
+    let list: responses::InFlightTransferList = chain.query(&QueryMsg::InFlightTransfers {
+        sender: sender.to_string(),
+        start_after,
+        limit,
+    });
+    list.list
+}
+
+#[test]
+fn send_receive_and_ack_events() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let ab = net.ab.clone();
+    let (response, packet) = send(&mut net, transfer_msg(&ab, &bob));
+    assert_transfer_event(
+        &response.events,
+        events::EVENT_TYPE_SEND,
+        &alice,
+        bob.as_str(),
+        &ab,
+        &packet,
+    );
+
+    let response = relayer::recv_packet(&net.a.chain, &mut net.b.chain, &net.ba, &packet).unwrap();
+    let ba = net.ba.clone();
+    assert_transfer_event(
+        &response.events,
+        events::EVENT_TYPE_RECEIVE,
+        &alice,
+        bob.as_str(),
+        &ba,
+        &packet,
+    );
+
+    let ack = relayer::parse_write_acknowledgement(&response.events)
+        .unwrap()
+        .unwrap();
+    assert_eq!(decode_ack(&ack), Ics20Ack::success());
+    let response =
+        relayer::acknowledge_packet(&mut net.a.chain, &ab, &net.b.chain, &packet, ack).unwrap();
+    assert_transfer_event(
+        &response.events,
+        events::EVENT_TYPE_ACK_SUCCESS,
+        &alice,
+        bob.as_str(),
+        &ab,
+        &packet,
+    );
+    assert_eq!(
+        event_attribute(
+            &response.events,
+            events::EVENT_TYPE_REFUND,
+            events::ATTRIBUTE_KEY_DENOM
+        ),
+        None
+    );
+}
+
+#[test]
+fn error_ack_and_refund_events() {
+    let mut net = Network::new();
+    let alice = net.a.addr("alice");
+    let ab = net.ab.clone();
+
+    // the vouchers cannot be minted to an invalid address on `B`
+    let msg = TransferMsg {
+        receiver: "not-an-address".to_string(),
+        ..transfer_msg(&ab, &alice)
+    };
+    let (_, packet) = send(&mut net, msg);
+    let response = relayer::recv_packet(&net.a.chain, &mut net.b.chain, &net.ba, &packet).unwrap();
+    let ack = relayer::parse_write_acknowledgement(&response.events)
+        .unwrap()
+        .unwrap();
+    let Ics20Ack::Error(error) = decode_ack(&ack) else {
+        panic!("expected an error acknowledgement");
+    };
+    let response =
+        relayer::acknowledge_packet(&mut net.a.chain, &ab, &net.b.chain, &packet, ack).unwrap();
+
+    for ty in [events::EVENT_TYPE_ACK_ERROR, events::EVENT_TYPE_REFUND] {
+        assert_transfer_event(&response.events, ty, &alice, "not-an-address", &ab, &packet);
+    }
+    assert_eq!(
+        event_attribute(
+            &response.events,
+            events::EVENT_TYPE_ACK_ERROR,
+            events::ATTRIBUTE_KEY_ERROR
+        ),
+        Some(error)
+    );
+}
+
+#[test]
+fn timeout_and_refund_events() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let ab = net.ab.clone();
+    let (_, packet) = send(&mut net, transfer_msg(&ab, &bob));
+
+    net.b.chain.next_blocks(200);
+    let response = relayer::timeout_packet(&mut net.a.chain, &ab, &net.b.chain, &packet).unwrap();
+    for ty in [events::EVENT_TYPE_TIMEOUT, events::EVENT_TYPE_REFUND] {
+        assert_transfer_event(&response.events, ty, &alice, bob.as_str(), &ab, &packet);
+    }
+}
+
+#[test]
+fn in_flight_transfers_are_listed_until_settled() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let ab = net.ab.clone();
+    let packets: Vec<_> = (0..3)
+        .map(|_| send(&mut net, transfer_msg(&ab, &bob)).1)
+        .collect();
+
+    let all = in_flight(&net.a, &alice, None, None);
+    let sequences: Vec<_> = all.iter().map(|transfer| transfer.sequence).collect();
+    let expected: Vec<_> = packets
+        .iter()
+        .map(|packet| packet.sequence.value())
+        .collect();
+    assert_eq!(sequences, expected);
+    assert!(all.iter().all(|transfer| transfer.channel == ab
+        && transfer.receiver == bob.as_str()
+        && transfer.tokens.len() == 1
+        && transfer.tokens[0].denom == "uatom"
+        && transfer.tokens[0].amount.u128() == 100));
+    assert_eq!(all[0].timeout, packets[0].timeout);
+
+    let first = in_flight(&net.a, &alice, None, Some(2));
+    assert_eq!(first, all[..2]);
+    let rest = in_flight(&net.a, &alice, Some((ab.clone(), all[1].sequence)), None);
+    assert_eq!(rest, all[2..]);
+    assert!(in_flight(&net.a, &bob, None, None).is_empty());
+
+    // settled transfers are removed, whether acknowledged or timed out
+    relayer::relay_packet(
+        &mut net.a.chain,
+        &ab,
+        &mut net.b.chain,
+        &net.ba,
+        &packets[0],
+    )
+    .unwrap();
+    assert_eq!(in_flight(&net.a, &alice, None, None), all[1..]);
+
+    net.b.chain.next_blocks(200);
+    for packet in &packets[1..] {
+        relayer::timeout_packet(&mut net.a.chain, &ab, &net.b.chain, packet).unwrap();
+    }
+    assert!(in_flight(&net.a, &alice, None, None).is_empty());
+}
@@ -0,0 +1,224 @@
+//! Tests of the tokens forwarded by `cw-ibc-lite-ics20-transfer` from `A` to `C` through `B`,
+//! whose acknowledgement on `B` is written once the forwarded packet is acknowledged or times
+//! out.
+
+mod common;
+
+use cosmwasm_std::{coin, Addr, Binary};
+
+use cw_ibc_lite_ics20_transfer::types::{
+    msg::{ExecuteMsg, TransferMsg},
+    state::ChannelPolicy,
+};
+use cw_ibc_lite_shared::types::{
+    ibc::Packet,
+    transfer::packet::{Forwarding, Hop, Ics20Ack},
+};
+use cw_ibc_lite_testing::relayer;
+
+use common::{decode_ack, relay, sent_packets, transfer_msg, Chain, Network};
+
+/// Returns a transfer to the receiver on `C` through `B`.
+fn forward_msg(net: &Network, receiver: &Addr) -> TransferMsg {
+    TransferMsg {
+        forwarding: Some(Forwarding {
+            hops: vec![Hop {
+                port_id: net.b.port(),
+                channel_id: net.bc.clone(),
+            }],
+            destination_memo: None,
+        }),
+        ..transfer_msg(&net.ab, receiver)
+    }
+}
+
+/// Receives the packet sent from `A` on `B`, and returns the packet forwarded to `C`.
+fn receive_on_b(net: &mut Network, packet: &Packet) -> Packet {
+    let response = relayer::recv_packet(&net.a.chain, &mut net.b.chain, &net.ba, packet).unwrap();
+    // the acknowledgement is held until the forwarded packet is acknowledged
+    assert_eq!(
+        relayer::parse_write_acknowledgement(&response.events).unwrap(),
+        None
+    );
+    sent_packets(&response).remove(0)
+}
+
+/// Sends 100 uatom of alice on `A` to carol on `C` through `B`. Returns the packet sent from
+/// `A` and the packet forwarded from `B`.
+fn send_uatom_to_c(net: &mut Network) -> (Packet, Packet) {
+    let (alice, carol) = (net.a.addr("alice"), net.c.addr("carol"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    let msg = forward_msg(net, &carol);
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    let forwarded = receive_on_b(net, &packet);
+    (packet, forwarded)
+}
+
+/// Receives the forwarded packet on `C`, and returns its acknowledgement.
+fn receive_on_c(net: &mut Network, forwarded: &Packet) -> Binary {
+    let response =
+        relayer::recv_packet(&net.b.chain, &mut net.c.chain, &net.cb, forwarded).unwrap();
+    relayer::parse_write_acknowledgement(&response.events)
+        .unwrap()
+        .unwrap()
+}
+
+/// Acknowledges the forwarded packet on `B`, and returns the acknowledgement `B` writes in turn
+/// for the packet sent from `A`.
+fn acknowledge_on_b(net: &mut Network, forwarded: &Packet, ack: Binary) -> Binary {
+    let response =
+        relayer::acknowledge_packet(&mut net.b.chain, &net.bc, &net.c.chain, forwarded, ack)
+            .unwrap();
+    relayer::parse_write_acknowledgement(&response.events)
+        .unwrap()
+        .unwrap()
+}
+
+fn disable_receive(chain: &mut Chain, channel: &str) {
+    let owner = chain.chain.owner.clone();
+    chain
+        .execute(
+            &owner,
+            &ExecuteMsg::SetChannelPolicy {
+                channel: channel.to_string(),
+                policy: Some(ChannelPolicy {
+                    send_enabled: true,
+                    receive_enabled: false,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn forwarded_tokens_are_acknowledged_once_received() {
+    let mut net = Network::new();
+    let (packet, forwarded) = send_uatom_to_c(&mut net);
+    let (alice, carol) = (net.a.addr("alice"), net.c.addr("carol"));
+    let trace_b = format!("{}/{}/uatom", net.b.port(), net.ba);
+    let voucher_b = net.b.voucher(&trace_b);
+
+    // `B` keeps the vouchers in escrow for the forwarding channel
+    assert_eq!(net.b.cw20_supply(&voucher_b), 100);
+    assert_eq!(net.b.escrowed(&net.bc, &trace_b), 100);
+
+    let ack = receive_on_c(&mut net, &forwarded);
+    assert_eq!(decode_ack(&ack), Ics20Ack::success());
+    let ack = acknowledge_on_b(&mut net, &forwarded, ack);
+    assert_eq!(decode_ack(&ack), Ics20Ack::success());
+    relayer::acknowledge_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet, ack).unwrap();
+
+    let trace_c = format!("{}/{}/{trace_b}", net.c.port(), net.cb);
+    assert_eq!(net.c.cw20_balance(&net.c.voucher(&trace_c), &carol), 100);
+    assert_eq!(net.b.escrowed(&net.bc, &trace_b), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+    assert_eq!(net.a.balance(&alice, "uatom"), 900);
+}
+
+#[test]
+fn forwarded_tokens_are_refunded_on_error_ack() {
+    let mut net = Network::new();
+    let (packet, forwarded) = send_uatom_to_c(&mut net);
+    let alice = net.a.addr("alice");
+    let trace_b = format!("{}/{}/uatom", net.b.port(), net.ba);
+    let voucher_b = net.b.voucher(&trace_b);
+    let cb = net.cb.clone();
+    disable_receive(&mut net.c, &cb);
+
+    let ack = receive_on_c(&mut net, &forwarded);
+    assert!(matches!(decode_ack(&ack), Ics20Ack::Error(_)));
+    let ack = acknowledge_on_b(&mut net, &forwarded, ack);
+    assert!(matches!(decode_ack(&ack), Ics20Ack::Error(_)));
+
+    // the vouchers minted for the incoming packet are burned, as it is refunded on `A`
+    assert_eq!(net.b.cw20_supply(&voucher_b), 0);
+    assert_eq!(net.b.escrowed(&net.bc, &trace_b), 0);
+
+    relayer::acknowledge_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet, ack).unwrap();
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+}
+
+#[test]
+fn forwarded_tokens_are_refunded_on_timeout() {
+    let mut net = Network::new();
+    let (packet, forwarded) = send_uatom_to_c(&mut net);
+    let alice = net.a.addr("alice");
+    let trace_b = format!("{}/{}/uatom", net.b.port(), net.ba);
+    let voucher_b = net.b.voucher(&trace_b);
+
+    net.c.chain.next_blocks(200);
+    let response =
+        relayer::timeout_packet(&mut net.b.chain, &net.bc, &net.c.chain, &forwarded).unwrap();
+    let ack = relayer::parse_write_acknowledgement(&response.events)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(decode_ack(&ack), Ics20Ack::Error(_)));
+    assert_eq!(net.b.cw20_supply(&voucher_b), 0);
+    assert_eq!(net.b.escrowed(&net.bc, &trace_b), 0);
+
+    relayer::acknowledge_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet, ack).unwrap();
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+}
+
+#[test]
+fn vouchers_forwarded_to_their_source_are_minted_back_on_error_ack() {
+    let mut net = Network::new();
+    let (alice, bob, carol, dave) = (
+        net.a.addr("alice"),
+        net.b.addr("bob"),
+        net.c.addr("carol"),
+        net.c.addr("dave"),
+    );
+
+    // carol sends ucoin of `C` to bob on `B`, who sends the vouchers on to alice on `A`
+    net.c.chain.fund(&carol, vec![coin(100, "ucoin")]).unwrap();
+    let response = net
+        .c
+        .transfer(&carol, transfer_msg(&net.cb, &bob), &[coin(100, "ucoin")]);
+    let acks = relay(&mut net.c, &net.cb, &mut net.b, &net.bc, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    let trace_b = format!("{}/{}/ucoin", net.b.port(), net.bc);
+    let voucher_b = net.b.voucher(&trace_b);
+
+    let response = net
+        .b
+        .send_cw20(&voucher_b, &bob, 100, &transfer_msg(&net.ba, &alice));
+    let acks = relay(&mut net.b, &net.ba, &mut net.a, &net.ab, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    let trace_a = format!("{}/{}/{trace_b}", net.a.port(), net.ab);
+    let voucher_a = net.a.voucher(&trace_a);
+    assert_eq!(net.b.escrowed(&net.ba, &trace_b), 100);
+
+    // alice sends them back to dave on `C` through `B`, where they return to their source
+    let msg = forward_msg(&net, &dave);
+    let response = net.a.send_cw20(&voucher_a, &alice, 100, &msg);
+    let packet = sent_packets(&response).remove(0);
+    let forwarded = receive_on_b(&mut net, &packet);
+    assert_eq!(net.b.escrowed(&net.ba, &trace_b), 0);
+    assert_eq!(net.b.cw20_supply(&voucher_b), 0);
+
+    let cb = net.cb.clone();
+    disable_receive(&mut net.c, &cb);
+    let ack = receive_on_c(&mut net, &forwarded);
+    let ack = acknowledge_on_b(&mut net, &forwarded, ack);
+    assert!(matches!(decode_ack(&ack), Ics20Ack::Error(_)));
+
+    // the burned vouchers are minted back, and escrowed again for the incoming channel
+    let ics20_b = net.b.ics20.clone();
+    assert_eq!(net.b.cw20_supply(&voucher_b), 100);
+    assert_eq!(net.b.cw20_balance(&voucher_b, &ics20_b), 100);
+    assert_eq!(net.b.escrowed(&net.ba, &trace_b), 100);
+
+    relayer::acknowledge_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet, ack).unwrap();
+    assert_eq!(net.a.cw20_balance(&voucher_a, &alice), 100);
+    assert_eq!(net.c.balance(&dave, "ucoin"), 0);
+    assert_eq!(net.c.escrowed(&net.cb, "ucoin"), 100);
+}
@@ -0,0 +1,75 @@
+//! Tests of the multi-token `ics20-2` transfers of `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::coin;
+
+use cw_ibc_lite_ics20_transfer::types::msg::TransferMsg;
+use cw_ibc_lite_shared::types::transfer::packet::{Ics20Ack, Ics20PacketData};
+
+use common::{relay, sent_packets, transfer_msg, Network};
+
+fn packet_data(response: &cw_multi_test::AppResponse) -> Ics20PacketData {
+    let packets = sent_packets(response);
+    assert_eq!(packets.len(), 1);
+    cosmwasm_std::from_json(&packets[0].data).unwrap()
+}
+
+#[test]
+fn several_coins_are_sent_in_one_packet() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let funds = [coin(100, "uatom"), coin(50, "ustake")];
+    net.a.chain.fund(&alice, funds.to_vec()).unwrap();
+
+    let response = net.a.transfer(&alice, transfer_msg(&net.ab, &bob), &funds);
+    let Ics20PacketData::V2(data) = packet_data(&response) else {
+        panic!("expected ics20-2 packet data");
+    };
+    assert_eq!(data.tokens.len(), 2);
+
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "ustake"), 50);
+    for (denom, amount) in [("uatom", 100), ("ustake", 50)] {
+        let voucher = net
+            .b
+            .voucher(&format!("{}/{}/{denom}", net.b.port(), net.ba));
+        assert_eq!(net.b.cw20_balance(&voucher, &bob), amount);
+    }
+}
+
+#[test]
+fn single_coins_are_sent_as_ics20_1() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    assert!(matches!(packet_data(&response), Ics20PacketData::V1(_)));
+}
+
+#[test]
+fn every_coin_is_refunded_on_error_ack() {
+    let mut net = Network::new();
+    let alice = net.a.addr("alice");
+    let funds = [coin(100, "uatom"), coin(50, "ustake")];
+    net.a.chain.fund(&alice, funds.to_vec()).unwrap();
+
+    // the vouchers cannot be minted to an invalid address on `B`
+    let msg = TransferMsg {
+        receiver: "not-an-address".to_string(),
+        ..transfer_msg(&net.ab, &alice)
+    };
+    let response = net.a.transfer(&alice, msg, &funds);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 100);
+    assert_eq!(net.a.balance(&alice, "ustake"), 50);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+    assert_eq!(net.a.escrowed(&net.ab, "ustake"), 0);
+}
@@ -0,0 +1,89 @@
+//! Tests of the transfers of native bank tokens with `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::coin;
+
+use cw_ibc_lite_ics20_transfer::types::msg::{ExecuteMsg, TransferMsg};
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+
+use common::{relay, transfer_msg, Network};
+
+#[test]
+fn native_tokens_are_escrowed_and_released_on_return() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let ics20_a = net.a.ics20.clone();
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.a.balance(&alice, "uatom"), 900);
+    assert_eq!(net.a.balance(&ics20_a, "uatom"), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+
+    let voucher = net.b.voucher(&format!("{}/{}/uatom", net.b.port(), net.ba));
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 40, &transfer_msg(&net.ba, &alice));
+    let acks = relay(&mut net.b, &net.ba, &mut net.a, &net.ab, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 940);
+    assert_eq!(net.a.balance(&ics20_a, "uatom"), 60);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 60);
+}
+
+#[test]
+fn native_tokens_are_refunded_on_error_ack() {
+    let mut net = Network::new();
+    let alice = net.a.addr("alice");
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    // the vouchers cannot be minted to an invalid address on `B`
+    let msg = TransferMsg {
+        receiver: "not-an-address".to_string(),
+        ..transfer_msg(&net.ab, &alice)
+    };
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
+
+#[test]
+fn transfers_need_native_funds_and_a_known_channel() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::Transfer(transfer_msg(&net.ab, &bob)),
+            &[],
+        )
+        .unwrap_err();
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::Transfer(transfer_msg("08-wasm-9", &bob)),
+            &[coin(100, "uatom")],
+        )
+        .unwrap_err();
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+}
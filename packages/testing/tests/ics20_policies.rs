@@ -0,0 +1,193 @@
+//! Tests of the token and channel policies of `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::coin;
+
+use cw_ibc_lite_ics20_transfer::types::{
+    msg::{responses, ExecuteMsg, QueryMsg},
+    state::{Allowlists, ChannelPolicy, TokenPolicy},
+};
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+
+use common::{relay, transfer_msg, Chain, Network};
+
+/// Executes an admin message on the chain.
+fn admin(chain: &mut Chain, msg: &ExecuteMsg) {
+    let owner = chain.chain.owner.clone();
+    chain.execute(&owner, msg, &[]).unwrap();
+}
+
+fn token_policy(denom: &str, policy: Option<TokenPolicy>) -> ExecuteMsg {
+    ExecuteMsg::SetTokenPolicy {
+        denom: denom.to_string(),
+        policy,
+    }
+}
+
+fn channel_policy(channel: &str, send_enabled: bool, receive_enabled: bool) -> ExecuteMsg {
+    ExecuteMsg::SetChannelPolicy {
+        channel: channel.to_string(),
+        policy: Some(ChannelPolicy {
+            send_enabled,
+            receive_enabled,
+        }),
+    }
+}
+
+/// Funds alice on `A`, and returns whether she can send 100 uatom to bob on `B`.
+fn can_send(net: &mut Network) -> bool {
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::Transfer(transfer_msg(&net.ab, &bob)),
+            &[coin(100, "uatom")],
+        )
+        .is_ok()
+}
+
+#[test]
+fn blocked_tokens_are_not_sent() {
+    let mut net = Network::new();
+    admin(
+        &mut net.a,
+        &token_policy("uatom", Some(TokenPolicy::Blocked)),
+    );
+    assert!(!can_send(&mut net));
+
+    admin(&mut net.a, &token_policy("uatom", None));
+    assert!(can_send(&mut net));
+}
+
+#[test]
+fn blocked_vouchers_are_not_received() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    let trace = format!("{}/{}/uatom", net.b.port(), net.ba);
+    admin(
+        &mut net.b,
+        &token_policy(&trace, Some(TokenPolicy::Blocked)),
+    );
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+    assert_eq!(net.a.balance(&alice, "uatom"), 100);
+}
+
+#[test]
+fn token_allowlist() {
+    let mut net = Network::new();
+    let allowlists = Allowlists {
+        tokens: true,
+        channels: false,
+    };
+    admin(&mut net.a, &ExecuteMsg::SetAllowlists(allowlists.clone()));
+    assert_eq!(
+        net.a.query::<Allowlists>(&QueryMsg::Allowlists {}),
+        allowlists
+    );
+    assert!(!can_send(&mut net));
+
+    admin(
+        &mut net.a,
+        &token_policy("uatom", Some(TokenPolicy::Allowed)),
+    );
+    assert!(can_send(&mut net));
+}
+
+#[test]
+fn channel_allowlist_and_directions() {
+    let mut net = Network::new();
+    let ab = net.ab.clone();
+    admin(
+        &mut net.a,
+        &ExecuteMsg::SetAllowlists(Allowlists {
+            tokens: false,
+            channels: true,
+        }),
+    );
+    assert!(!can_send(&mut net));
+
+    admin(&mut net.a, &channel_policy(&ab, false, true));
+    assert!(!can_send(&mut net));
+
+    admin(&mut net.a, &channel_policy(&ab, true, false));
+    assert!(can_send(&mut net));
+}
+
+#[test]
+fn policies_are_set_by_the_admin_and_listed() {
+    let mut net = Network::new();
+    let alice = net.a.addr("alice");
+    let ab = net.ab.clone();
+    net.a
+        .execute(
+            &alice,
+            &token_policy("uatom", Some(TokenPolicy::Blocked)),
+            &[],
+        )
+        .unwrap_err();
+    net.a
+        .execute(&alice, &channel_policy(&ab, false, false), &[])
+        .unwrap_err();
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::SetAllowlists(Allowlists::default()),
+            &[],
+        )
+        .unwrap_err();
+
+    admin(
+        &mut net.a,
+        &token_policy("uatom", Some(TokenPolicy::Blocked)),
+    );
+    admin(
+        &mut net.a,
+        &token_policy("ustake", Some(TokenPolicy::Allowed)),
+    );
+    admin(&mut net.a, &channel_policy(&ab, true, false));
+
+    let tokens: responses::TokenPolicyList = net.a.query(&QueryMsg::ListTokenPolicies {
+        start_after: None,
+        limit: None,
+    });
+    let tokens: Vec<_> = tokens
+        .list
+        .into_iter()
+        .map(|info| (info.denom, info.policy))
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            ("uatom".to_string(), TokenPolicy::Blocked),
+            ("ustake".to_string(), TokenPolicy::Allowed)
+        ]
+    );
+    let tokens: responses::TokenPolicyList = net.a.query(&QueryMsg::ListTokenPolicies {
+        start_after: Some("uatom".to_string()),
+        limit: Some(1),
+    });
+    assert_eq!(tokens.list.len(), 1);
+    assert_eq!(tokens.list[0].denom, "ustake");
+
+    let channels: responses::ChannelPolicyList = net.a.query(&QueryMsg::ListChannelPolicies {
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(channels.list.len(), 1);
+    assert_eq!(channels.list[0].channel, ab);
+    assert_eq!(
+        channels.list[0].policy,
+        ChannelPolicy {
+            send_enabled: true,
+            receive_enabled: false,
+        }
+    );
+}
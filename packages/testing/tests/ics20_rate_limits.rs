@@ -0,0 +1,141 @@
+//! Tests of the rate limits of `cw-ibc-lite-ics20-transfer` on channels whose value is zero.
+
+mod common;
+
+use cosmwasm_std::coin;
+
+use cw_ibc_lite_ics20_transfer::types::msg::ExecuteMsg;
+use cw_ibc_lite_shared::types::transfer::{
+    packet::Ics20Ack,
+    rate_limit::{Quota, QuotaBase},
+};
+
+use common::{relay, transfer_msg, Chain, Network};
+
+/// A daily quota of 10% that allows a net flow of 100 while the channel value is zero.
+fn quota(base: QuotaBase) -> Quota {
+    Quota {
+        name: "daily".to_string(),
+        max_send_percent: 10,
+        max_recv_percent: 10,
+        duration: 86_400,
+        base,
+        zero_value_capacity: 100u128.into(),
+    }
+}
+
+fn set_rate_limits(chain: &mut Chain, channel: &str, denom: &str, quota: Quota) {
+    let owner = chain.chain.owner.clone();
+    chain
+        .execute(
+            &owner,
+            &ExecuteMsg::SetRateLimits {
+                channel: channel.to_string(),
+                denom: denom.to_string(),
+                quotas: vec![quota],
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn escrow_quota_applies_after_the_first_transfer() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+    set_rate_limits(
+        &mut net.a,
+        &net.ab.clone(),
+        "uatom",
+        quota(QuotaBase::Escrow),
+    );
+
+    // nothing is escrowed yet, so the zero value capacity applies
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::Transfer(transfer_msg(&net.ab, &bob)),
+            &[coin(101, "uatom")],
+        )
+        .unwrap_err();
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    // 10% of the escrowed 100 is already exceeded by the net outflow of this period
+    net.a
+        .execute(
+            &alice,
+            &ExecuteMsg::Transfer(transfer_msg(&net.ab, &bob)),
+            &[coin(1, "uatom")],
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn supply_quota_on_a_new_voucher() {
+    let mut net = Network::new();
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+    let trace = format!("{}/{}/uatom", net.b.port(), net.ba);
+    set_rate_limits(
+        &mut net.b,
+        &net.ba.clone(),
+        &trace,
+        quota(QuotaBase::Supply),
+    );
+
+    // the voucher has no supply before its first receipt, so the zero value capacity applies
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(101, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    let voucher = net.b.voucher(&trace);
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+
+    // the quota applies to the supply of 100 from then on
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(1, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert!(matches!(acks[..], [Ics20Ack::Error(_)]));
+    assert_eq!(net.a.balance(&alice, "uatom"), 900);
+}
+
+#[test]
+fn supply_query_errors_are_propagated() {
+    let mut net = Network::new();
+    let owner = net.a.chain.owner.clone();
+    // the router is a contract, but not a cw20 token
+    let router = net.a.chain.router.to_string();
+    let ab = net.ab.clone();
+
+    net.a
+        .execute(
+            &owner,
+            &ExecuteMsg::SetRateLimits {
+                channel: ab,
+                denom: router,
+                quotas: vec![quota(QuotaBase::Supply)],
+            },
+            &[],
+        )
+        .unwrap_err();
+}
@@ -0,0 +1,129 @@
+//! Tests of the timeouts of the transfers sent by `cw-ibc-lite-ics20-transfer`.
+
+mod common;
+
+use cosmwasm_std::{coin, IbcTimeout, IbcTimeoutBlock};
+
+use cw_ibc_lite_ics20_transfer::types::msg::{
+    responses, ExecuteMsg, QueryMsg, TransferMsg, TransferTimeout,
+};
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+use cw_ibc_lite_testing::relayer;
+
+use common::{relay, sent_packets, transfer_msg, Network};
+
+/// Funds alice on `A` and returns a transfer of hers to bob on `B`.
+fn setup(net: &mut Network) -> (cosmwasm_std::Addr, TransferMsg) {
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    net.a
+        .chain
+        .fund(&alice, vec![coin(1_000, "uatom")])
+        .unwrap();
+    (alice, transfer_msg(&net.ab, &bob))
+}
+
+fn height_timeout(net: &Network, blocks: u64) -> IbcTimeoutBlock {
+    IbcTimeoutBlock {
+        revision: 0,
+        height: net.b.chain.height().revision_height + blocks,
+    }
+}
+
+#[test]
+fn height_timeout_is_received_before_the_height() {
+    let mut net = Network::new();
+    let (alice, msg) = setup(&mut net);
+    let block = height_timeout(&net, 10);
+
+    let msg = TransferMsg {
+        timeout: Some(TransferTimeout::Height(block.clone())),
+        ..msg
+    };
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    assert_eq!(packet.timeout, IbcTimeout::with_block(block));
+
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+}
+
+#[test]
+fn height_timeout_refunds_the_sender() {
+    let mut net = Network::new();
+    let (alice, msg) = setup(&mut net);
+    let block = height_timeout(&net, 10);
+
+    let msg = TransferMsg {
+        timeout: Some(TransferTimeout::Height(block)),
+        ..msg
+    };
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    assert_eq!(net.a.balance(&alice, "uatom"), 900);
+
+    // the timestamp of `B` is not past any timeout, only its height is
+    net.b.chain.next_blocks(10);
+    relayer::recv_packet(&net.a.chain, &mut net.b.chain, &net.ba, &packet).unwrap_err();
+    relayer::timeout_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet).unwrap();
+
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 0);
+}
+
+#[test]
+fn height_timeout_must_not_have_passed() {
+    let mut net = Network::new();
+    let (alice, msg) = setup(&mut net);
+    let block = height_timeout(&net, 0);
+
+    let msg = TransferMsg {
+        timeout: Some(TransferTimeout::Height(block)),
+        ..msg
+    };
+    net.a
+        .execute(&alice, &ExecuteMsg::Transfer(msg), &[coin(100, "uatom")])
+        .unwrap_err();
+}
+
+#[test]
+fn default_timeout() {
+    let mut net = Network::new();
+    let (alice, msg) = setup(&mut net);
+    let owner = net.a.chain.owner.clone();
+    let default_timeout = |net: &Network| {
+        net.a
+            .query::<responses::DefaultTimeout>(&QueryMsg::DefaultTimeout {
+                channel: net.ab.clone(),
+            })
+            .timeout_seconds
+    };
+    let ab = net.ab.clone();
+    let set = |timeout_seconds| ExecuteMsg::SetDefaultTimeout {
+        channel: ab.clone(),
+        timeout_seconds,
+    };
+    assert_eq!(default_timeout(&net), 600);
+
+    net.a.execute(&alice, &set(Some(60)), &[]).unwrap_err();
+    net.a.execute(&owner, &set(Some(0)), &[]).unwrap_err();
+    net.a.execute(&owner, &set(Some(60)), &[]).unwrap();
+    assert_eq!(default_timeout(&net), 60);
+
+    let now = net.a.chain.app.block_info().time;
+    let response = net.a.transfer(&alice, msg.clone(), &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    assert_eq!(packet.timeout.timestamp(), Some(now.plus_seconds(60)));
+
+    // the packet times out after the default timeout of the channel, not the contract's
+    net.b.chain.next_blocks(12);
+    relayer::timeout_packet(&mut net.a.chain, &net.ab, &net.b.chain, &packet).unwrap();
+    assert_eq!(net.a.balance(&alice, "uatom"), 1_000);
+
+    net.a.execute(&owner, &set(None), &[]).unwrap();
+    assert_eq!(default_timeout(&net), 600);
+    let now = net.a.chain.app.block_info().time;
+    let response = net.a.transfer(&alice, msg, &[coin(100, "uatom")]);
+    let packet = sent_packets(&response).remove(0);
+    assert_eq!(packet.timeout.timestamp(), Some(now.plus_seconds(600)));
+}
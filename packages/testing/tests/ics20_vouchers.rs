@@ -0,0 +1,126 @@
+//! Tests of the vouchers minted by `cw-ibc-lite-ics20-transfer` for foreign tokens, which are
+//! burned when they return to their source and escrowed when they are sent onward.
+
+mod common;
+
+use cosmwasm_std::coin;
+
+use cw_ibc_lite_shared::types::transfer::packet::Ics20Ack;
+use cw_ibc_lite_testing::relayer;
+
+use common::{relay, sent_packets, transfer_msg, Network};
+
+/// Sends 100 `uatom` from `alice` on `A` to `bob` on `B`, and returns the denom trace of the
+/// vouchers on `B`.
+fn send_uatom_to_b(net: &mut Network) -> String {
+    let alice = net.a.addr("alice");
+    let bob = net.b.addr("bob");
+    net.a.chain.fund(&alice, vec![coin(100, "uatom")]).unwrap();
+
+    let response = net
+        .a
+        .transfer(&alice, transfer_msg(&net.ab, &bob), &[coin(100, "uatom")]);
+    let acks = relay(&mut net.a, &net.ab, &mut net.b, &net.ba, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+
+    format!("{}/{}/uatom", net.b.port(), net.ba)
+}
+
+#[test]
+fn returning_vouchers_are_burned() {
+    let mut net = Network::new();
+    let trace = send_uatom_to_b(&mut net);
+    let voucher = net.b.voucher(&trace);
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 40, &transfer_msg(&net.ba, &alice));
+    // the vouchers are burned rather than escrowed
+    assert_eq!(net.b.cw20_supply(&voucher), 60);
+    assert_eq!(net.b.cw20_balance(&voucher, &net.b.ics20.clone()), 0);
+    assert_eq!(net.b.escrowed(&net.ba, &trace), 0);
+
+    let acks = relay(&mut net.b, &net.ba, &mut net.a, &net.ab, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.a.balance(&alice, "uatom"), 40);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 60);
+}
+
+#[test]
+fn returning_vouchers_are_minted_back_on_timeout() {
+    let mut net = Network::new();
+    let trace = send_uatom_to_b(&mut net);
+    let voucher = net.b.voucher(&trace);
+    let (alice, bob) = (net.a.addr("alice"), net.b.addr("bob"));
+
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 100, &transfer_msg(&net.ba, &alice));
+    assert_eq!(net.b.cw20_supply(&voucher), 0);
+
+    let packet = sent_packets(&response).remove(0);
+    net.a.chain.next_blocks(200);
+    relayer::timeout_packet(&mut net.b.chain, &net.ba, &net.a.chain, &packet).unwrap();
+
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+    assert_eq!(net.b.cw20_supply(&voucher), 100);
+    assert_eq!(net.a.escrowed(&net.ab, "uatom"), 100);
+}
+
+#[test]
+fn vouchers_sent_onward_are_escrowed() {
+    let mut net = Network::new();
+    let trace = send_uatom_to_b(&mut net);
+    let voucher = net.b.voucher(&trace);
+    let (bob, carol, dave) = (net.b.addr("bob"), net.c.addr("carol"), net.b.addr("dave"));
+
+    // bob sends the vouchers to carol on `C`, which is not their source
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 100, &transfer_msg(&net.bc, &carol));
+    let ics20_b = net.b.ics20.clone();
+    assert_eq!(net.b.cw20_supply(&voucher), 100);
+    assert_eq!(net.b.cw20_balance(&voucher, &ics20_b), 100);
+    assert_eq!(net.b.escrowed(&net.bc, &trace), 100);
+
+    let acks = relay(&mut net.b, &net.bc, &mut net.c, &net.cb, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    let trace_c = format!("{}/{}/{trace}", net.c.port(), net.cb);
+    let voucher_c = net.c.voucher(&trace_c);
+    assert_eq!(net.c.cw20_balance(&voucher_c, &carol), 100);
+
+    // carol sends them back to dave on `B`, where they are released from escrow
+    let response = net
+        .c
+        .send_cw20(&voucher_c, &carol, 100, &transfer_msg(&net.cb, &dave));
+    assert_eq!(net.c.cw20_supply(&voucher_c), 0);
+
+    let acks = relay(&mut net.c, &net.cb, &mut net.b, &net.bc, &response);
+    assert_eq!(acks, vec![Ics20Ack::success()]);
+    assert_eq!(net.b.cw20_balance(&voucher, &dave), 100);
+    assert_eq!(net.b.cw20_balance(&voucher, &ics20_b), 0);
+    assert_eq!(net.b.escrowed(&net.bc, &trace), 0);
+    assert_eq!(net.b.cw20_supply(&voucher), 100);
+}
+
+#[test]
+fn vouchers_sent_onward_are_refunded_from_escrow_on_timeout() {
+    let mut net = Network::new();
+    let trace = send_uatom_to_b(&mut net);
+    let voucher = net.b.voucher(&trace);
+    let (bob, carol) = (net.b.addr("bob"), net.c.addr("carol"));
+
+    let response = net
+        .b
+        .send_cw20(&voucher, &bob, 100, &transfer_msg(&net.bc, &carol));
+    let packet = sent_packets(&response).remove(0);
+    net.c.chain.next_blocks(200);
+    relayer::timeout_packet(&mut net.b.chain, &net.bc, &net.c.chain, &packet).unwrap();
+
+    // the escrowed vouchers are returned rather than minted again
+    assert_eq!(net.b.cw20_balance(&voucher, &bob), 100);
+    assert_eq!(net.b.cw20_supply(&voucher), 100);
+    assert_eq!(net.b.escrowed(&net.bc, &trace), 0);
+}